@@ -0,0 +1,101 @@
+//! Integration with [`aho_corasick`], enabled by the `aho-corasick` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{aho_corasick::AhoCorasickPositionsExt, LineCol};
+//!
+//! let text = "let key = \"🦀\";\nlet password = \"hunter2\";\n";
+//!
+//! let found = text.find_all_positions(&["password", "key", "hunter2"]).unwrap();
+//! let found = found
+//!     .into_iter()
+//!     .map(|(i, span)| (i, span.start_line_col()))
+//!     .collect::<Vec<_>>();
+//!
+//! assert_eq!(
+//!     found,
+//!     [(1, LineCol(1, 5)), (0, LineCol(2, 5)), (2, LineCol(2, 17))],
+//! );
+//! ```
+
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+use ::aho_corasick::{AhoCorasick, BuildError, FindIter, Match};
+
+use crate::{Span, SpanResolver};
+
+pub trait AhoCorasickPositionsExt {
+    /// Returns an iterator over all non-overlapping matches of `ac`,
+    /// and their [`Span`]s.
+    ///
+    /// The positions are resolved in a single forward pass over the text.
+    fn aho_corasick_positions<'a, 'h>(
+        &'h self,
+        ac: &'a AhoCorasick,
+    ) -> AhoCorasickPositions<'a, 'h>;
+
+    /// Searches for all non-overlapping occurrences of `patterns`, returning
+    /// the index of the pattern matched and the [`Span`] of each match.
+    ///
+    /// Building an [`AhoCorasick`] is relatively expensive, so when searching
+    /// multiple texts, build it once and use [`aho_corasick_positions()`] instead.
+    ///
+    /// [`aho_corasick_positions()`]: Self::aho_corasick_positions
+    fn find_all_positions<I, P>(&self, patterns: I) -> Result<Vec<(usize, Span)>, BuildError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>;
+}
+
+impl AhoCorasickPositionsExt for str {
+    #[inline]
+    fn aho_corasick_positions<'a, 'h>(
+        &'h self,
+        ac: &'a AhoCorasick,
+    ) -> AhoCorasickPositions<'a, 'h> {
+        AhoCorasickPositions {
+            matches: ac.find_iter(self),
+            resolver: SpanResolver::new(self),
+        }
+    }
+
+    fn find_all_positions<I, P>(&self, patterns: I) -> Result<Vec<(usize, Span)>, BuildError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        let ac = AhoCorasick::new(patterns)?;
+        let found = self
+            .aho_corasick_positions(&ac)
+            .map(|(span, m)| (m.pattern().as_usize(), span))
+            .collect();
+        Ok(found)
+    }
+}
+
+/// An iterator over Aho-Corasick matches and their [`Span`]s.
+///
+/// See [`AhoCorasickPositionsExt::aho_corasick_positions()`].
+#[derive(Debug)]
+pub struct AhoCorasickPositions<'a, 'h> {
+    matches: FindIter<'a, 'h>,
+    resolver: SpanResolver<'h>,
+}
+
+impl Iterator for AhoCorasickPositions<'_, '_> {
+    type Item = (Span, Match);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let m = self.matches.next()?;
+            // Byte patterns can match within a `char`
+            if let Some(span) = self.resolver.span(m.range()) {
+                return Some((span, m));
+            }
+        }
+    }
+}
+
+impl FusedIterator for AhoCorasickPositions<'_, '_> {}
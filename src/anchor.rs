@@ -0,0 +1,113 @@
+use alloc::vec::Vec;
+
+use crate::{map_through_edits, Bias, LineColByte, SourceFile, TextEdit};
+
+/// Identifies an anchored byte position in an [`AnchorSet`].
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct Anchor(u32);
+
+/// A set of byte positions which are remapped as edits are applied,
+/// such that each [`Anchor`] keeps pointing at the same place in the
+/// text, e.g. a breakpoint or a diagnostic.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{AnchorSet, Bias, LineColByte, SourceFile, TextEdit};
+///
+/// let mut anchors = AnchorSet::new();
+///
+/// // "Hello World"
+/// let hello = anchors.insert(0, Bias::Left);
+/// let world = anchors.insert(6, Bias::Right);
+///
+/// // "Hello World" -> "Hello 👋\nWorld"
+/// anchors.apply(&TextEdit::insert(6, "👋\n"));
+/// assert_eq!(anchors.get(hello), Some(0));
+/// assert_eq!(anchors.get(world), Some(11));
+///
+/// let file = SourceFile::new("hello.txt", "Hello 👋\nWorld");
+/// assert_eq!(anchors.position(world, &file), Some(LineColByte(2, 1, 11)));
+///
+/// // "Hello 👋\nWorld" -> "World"
+/// anchors.apply(&TextEdit::delete(0..11));
+/// assert_eq!(anchors.get(hello), Some(0));
+/// assert_eq!(anchors.get(world), Some(0));
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct AnchorSet {
+    anchors: Vec<Option<(usize, Bias)>>,
+}
+
+impl AnchorSet {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            anchors: Vec::new(),
+        }
+    }
+
+    /// Anchors the byte position `byte`, using `bias` to decide
+    /// which way it moves when text is inserted at it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of anchors exceeds [`u32::MAX`].
+    pub fn insert(&mut self, byte: usize, bias: Bias) -> Anchor {
+        let id = u32::try_from(self.anchors.len()).expect("too many anchors");
+        self.anchors.push(Some((byte, bias)));
+        Anchor(id)
+    }
+
+    /// Removes the anchor, returning its current byte position.
+    #[inline]
+    pub fn remove(&mut self, anchor: Anchor) -> Option<usize> {
+        let (byte, _bias) = self.anchors.get_mut(anchor.0 as usize)?.take()?;
+        Some(byte)
+    }
+
+    /// Returns the current byte position of the anchor.
+    #[inline]
+    pub fn get(&self, anchor: Anchor) -> Option<usize> {
+        let (byte, _bias) = (*self.anchors.get(anchor.0 as usize)?)?;
+        Some(byte)
+    }
+
+    /// Returns the current line, column, and byte position of the
+    /// anchor in `file`.
+    ///
+    /// `file` must contain the text after all edits have been applied.
+    #[inline]
+    pub fn position(&self, anchor: Anchor, file: &SourceFile<'_>) -> Option<LineColByte> {
+        file.position(self.get(anchor)?)
+    }
+
+    /// Returns an iterator over all anchors and their current byte positions.
+    pub fn iter(&self) -> impl Iterator<Item = (Anchor, usize)> + '_ {
+        self.anchors
+            .iter()
+            .enumerate()
+            .filter_map(|(i, anchor)| Some((Anchor(i as u32), anchor.as_ref()?.0)))
+    }
+
+    /// Remaps all anchors through `edit`.
+    ///
+    /// See [`TextEdit::map()`].
+    pub fn apply(&mut self, edit: &TextEdit) {
+        for (byte, bias) in self.anchors.iter_mut().flatten() {
+            *byte = edit.map(*byte, *bias);
+        }
+    }
+
+    /// Remaps all anchors through `edits`.
+    ///
+    /// `edits` must be sorted, non-overlapping, and relative to the text
+    /// before any of them are applied.
+    ///
+    /// See [`map_through_edits()`].
+    pub fn apply_all(&mut self, edits: &[TextEdit]) {
+        for (byte, bias) in self.anchors.iter_mut().flatten() {
+            *byte = map_through_edits(edits, *byte, *bias);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Integration with [`annotate_snippets`], enabled by the `annotate-snippets` feature.
+//!
+//! [`annotate_snippets`] expects the source of a [`Snippet`] to be the lines
+//! being annotated, with annotations as byte ranges relative to that source,
+//! and the line number of the first line passed separately. These helpers
+//! slice out the lines covered by the [`Span`]s, and adjust the spans
+//! accordingly.
+//!
+//! # Example
+//!
+//! ```
+//! use annotate_snippets::{Level, Renderer};
+//! use char_positions::{annotate_snippets::file_snippet, SourceFile};
+//!
+//! let file = SourceFile::new("main.rs", "fn main() {\n    let 🦀 = x;\n}\n");
+//! let span = file.span(27..28).unwrap();
+//!
+//! let message = Level::Error
+//!     .title("cannot find value `x` in this scope")
+//!     .snippet(file_snippet(&file, [(Level::Error, span, "not found")]));
+//!
+//! let out = Renderer::plain().render(message).to_string();
+//! assert!(out.contains("main.rs"));
+//! assert!(out.contains("let 🦀 = x;"));
+//! ```
+
+use ::annotate_snippets::{Annotation, Level, Message, Snippet};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::render::{Diagnostic, Severity};
+use crate::{SourceFile, Span};
+
+/// Returns a [`Snippet`] of the lines of `source` covered by the spans,
+/// with each span annotated by its level and label.
+///
+/// # Panics
+///
+/// Panics if any span is out of bounds or not on [`char`] boundaries of `source`.
+pub fn snippet<'a>(
+    source: &'a str,
+    annotations: impl IntoIterator<Item = (Level, Span, &'a str)>,
+) -> Snippet<'a> {
+    let annotations = annotations.into_iter().collect::<Vec<_>>();
+
+    let Some(first) = annotations.iter().map(|&(_, span, _)| span).min() else {
+        return Snippet::source(source);
+    };
+    let end = annotations
+        .iter()
+        .map(|(_, span, _)| span.byte_end())
+        .max()
+        .unwrap_or(first.byte_end());
+
+    let lines = covering_lines(source, first.byte_start()..end);
+    let offset = lines.start;
+
+    Snippet::source(&source[lines])
+        .line_start(first.start.0)
+        .annotations(
+            annotations
+                .into_iter()
+                .map(|(level, span, label)| annotation(level, span, offset).label(label)),
+        )
+}
+
+/// Returns a [`Snippet`] like [`snippet()`], with the name of
+/// `file` as its origin.
+#[inline]
+pub fn file_snippet<'a>(
+    file: &'a SourceFile<'_>,
+    annotations: impl IntoIterator<Item = (Level, Span, &'a str)>,
+) -> Snippet<'a> {
+    snippet(file.text(), annotations).origin(file.name())
+}
+
+/// Returns a [`Message`] of `diagnostic`, with a [`file_snippet()`] of its
+/// labels, and its notes as footers.
+///
+/// The primary label uses the [`Level`] of the [`Severity`],
+/// while secondary labels use [`Level::Info`].
+///
+/// # Example
+///
+/// ```
+/// use annotate_snippets::Renderer;
+/// use char_positions::annotate_snippets::diagnostic_message;
+/// use char_positions::render::Diagnostic;
+/// use char_positions::SourceFile;
+///
+/// let file = SourceFile::new("main.rs", "fn main() {\n    let 🦀 = x;\n}\n");
+/// let diagnostic = Diagnostic::error("cannot find value `x` in this scope")
+///     .code("E0425")
+///     .primary(file.span(27..28).unwrap(), "not found");
+///
+/// let out = Renderer::plain().render(diagnostic_message(&file, &diagnostic)).to_string();
+/// assert!(out.contains("error[E0425]"));
+/// assert!(out.contains("let 🦀 = x;"));
+/// ```
+///
+/// # Panics
+///
+/// Panics if any span is out of bounds or not on [`char`] boundaries of `file`.
+pub fn diagnostic_message<'a>(
+    file: &'a SourceFile<'_>,
+    diagnostic: &'a Diagnostic<'_>,
+) -> Message<'a> {
+    let level = level(diagnostic.severity);
+
+    let mut message = level.title(&diagnostic.message);
+    if let Some(code) = &diagnostic.code {
+        message = message.id(code);
+    }
+
+    let primary = diagnostic
+        .primary
+        .iter()
+        .map(|label| (level, label.span, &*label.message));
+    let secondary = diagnostic
+        .secondary
+        .iter()
+        .map(|label| (Level::Info, label.span, &*label.message));
+    if diagnostic.labels().next().is_some() {
+        message = message.snippet(file_snippet(file, primary.chain(secondary)));
+    }
+
+    message.footers(diagnostic.notes.iter().map(|note| Level::Note.title(note)))
+}
+
+#[inline]
+fn level(severity: Severity) -> Level {
+    match severity {
+        Severity::Error => Level::Error,
+        Severity::Warning => Level::Warning,
+        Severity::Note => Level::Note,
+        Severity::Help => Level::Help,
+    }
+}
+
+#[inline]
+fn annotation<'a>(level: Level, span: Span, offset: usize) -> Annotation<'a> {
+    level.span((span.byte_start() - offset)..(span.byte_end() - offset))
+}
+
+/// Returns the byte range of the lines covered by `range`,
+/// including the `'\n'` of the last line.
+fn covering_lines(source: &str, range: Range<usize>) -> Range<usize> {
+    let start = source[..range.start].rfind('\n').map_or(0, |i| i + 1);
+    let end = if (range.start < range.end) && source[..range.end].ends_with('\n') {
+        range.end
+    } else {
+        source[range.end..]
+            .find('\n')
+            .map_or(source.len(), |i| range.end + i + 1)
+    };
+    start..end
+}
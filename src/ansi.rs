@@ -0,0 +1,168 @@
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::{LineCol, LineColByteRange};
+
+pub trait AnsiPositionsExt {
+    /// Returns an iterator over the [`char`]s and ANSI escape sequences
+    /// of text, e.g. colored terminal output, and their positions.
+    ///
+    /// Escape sequences do not advance the column, such that columns are
+    /// the visual columns of the text, as displayed in a terminal. The
+    /// position of an escape sequence is the position of the [`char`]
+    /// following it, along with the byte range of the escape sequence.
+    ///
+    /// CSI sequences, e.g. `"\x1b[31m"`, OSC sequences, e.g. hyperlinks,
+    /// and other `ESC` sequences are recognized. An unterminated escape
+    /// sequence extends to the end of the text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{AnsiPositionsExt, AnsiToken, LineColByteRange};
+    ///
+    /// let text = "\x1b[31merror\x1b[0m: 🦀";
+    ///
+    /// let mut iter = text
+    ///     .ansi_char_positions::<LineColByteRange>()
+    ///     .map(|(LineColByteRange(line, col, range), token)| (line, col, range, token));
+    ///
+    /// assert_eq!(iter.next(), Some((1, 1, 0..5, AnsiToken::Escape("\x1b[31m"))));
+    /// assert_eq!(iter.next(), Some((1, 1, 5..6, AnsiToken::Char('e'))));
+    ///
+    /// let mut iter = iter.skip(4);
+    /// assert_eq!(iter.next(), Some((1, 6, 10..14, AnsiToken::Escape("\x1b[0m"))));
+    /// assert_eq!(iter.next(), Some((1, 6, 14..15, AnsiToken::Char(':'))));
+    /// assert_eq!(iter.next(), Some((1, 7, 15..16, AnsiToken::Char(' '))));
+    /// assert_eq!(iter.next(), Some((1, 8, 16..20, AnsiToken::Char('🦀'))));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    fn ansi_char_positions<T>(&self) -> AnsiCharPositions<'_, T>
+    where
+        LineColByteRange: Into<T>;
+}
+
+impl AnsiPositionsExt for str {
+    #[inline]
+    fn ansi_char_positions<T>(&self) -> AnsiCharPositions<'_, T>
+    where
+        LineColByteRange: Into<T>,
+    {
+        AnsiCharPositions {
+            text: self,
+            byte: 0,
+            pos: LineCol::START,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// A [`char`] or an ANSI escape sequence.
+///
+/// See [`AnsiPositionsExt::ansi_char_positions()`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum AnsiToken<'a> {
+    Char(char),
+    Escape(&'a str),
+}
+
+/// An iterator over [`char`]s and ANSI escape sequences, and their positions.
+///
+/// See [`AnsiPositionsExt::ansi_char_positions()`].
+#[derive(Clone, Debug)]
+pub struct AnsiCharPositions<'a, T> {
+    text: &'a str,
+    byte: usize,
+    pos: LineCol,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T> AnsiCharPositions<'a, T> {
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.byte..]
+    }
+}
+
+impl<'a, T> Iterator for AnsiCharPositions<'a, T>
+where
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, AnsiToken<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.as_str();
+        let start = self.byte;
+
+        let token = match escape_len(rest) {
+            Some(n) => AnsiToken::Escape(&rest[..n]),
+            None => AnsiToken::Char(rest.chars().next()?),
+        };
+
+        self.byte += match token {
+            AnsiToken::Char(c) => c.len_utf8(),
+            AnsiToken::Escape(s) => s.len(),
+        };
+        let pos = LineColByteRange(self.pos.0, self.pos.1, start..self.byte);
+
+        if let AnsiToken::Char(c) = token {
+            self.pos.advance(c);
+        }
+
+        Some((pos.into(), token))
+    }
+}
+
+impl<T> FusedIterator for AnsiCharPositions<'_, T> where Self: Iterator {}
+
+/// Returns the length in bytes of the escape sequence at the start of `s`,
+/// or `None` if `s` does not start with an escape sequence.
+fn escape_len(s: &str) -> Option<usize> {
+    const ESC: u8 = 0x1B;
+    const BEL: u8 = 0x07;
+
+    let bytes = s.as_bytes();
+    match bytes {
+        [ESC, b'[', ..] => Some(2 + csi_len(&bytes[2..])),
+        [ESC, b']', ..] => {
+            // Terminated by BEL or ST, i.e. `ESC \`
+            let body = &bytes[2..];
+            let len = body
+                .iter()
+                .enumerate()
+                .find_map(|(i, &b)| match (b, body.get(i + 1)) {
+                    (BEL, _) => Some(i + 1),
+                    (ESC, Some(b'\\')) => Some(i + 2),
+                    _ => None,
+                })
+                .unwrap_or(body.len());
+            Some(2 + len)
+        }
+        [ESC, rest @ ..] => {
+            // Any intermediate bytes, followed by a final byte
+            let n = rest.iter().take_while(|b| matches!(b, 0x20..=0x2F)).count();
+            let end = match rest.get(n) {
+                Some(0x30..=0x7E) => n + 1,
+                _ => n,
+            };
+            Some(1 + end)
+        }
+        // C1 CSI, i.e. U+009B
+        [0xC2, 0x9B, ..] => Some(2 + csi_len(&bytes[2..])),
+        _ => None,
+    }
+}
+
+/// Returns the length in bytes of the parameter, intermediate,
+/// and final bytes of a CSI sequence.
+fn csi_len(bytes: &[u8]) -> usize {
+    let n = bytes
+        .iter()
+        .take_while(|b| matches!(b, 0x20..=0x3F))
+        .count();
+    match bytes.get(n) {
+        Some(0x40..=0x7E) => n + 1,
+        _ => n,
+    }
+}
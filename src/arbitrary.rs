@@ -0,0 +1,191 @@
+//! Integration with [`arbitrary`], enabled by the `arbitrary` feature.
+//!
+//! All position types implement [`Arbitrary`], producing 1-indexed lines
+//! and columns, and ranges and spans that are not inverted. However, they
+//! are not positions in any particular text. For that, use [`position_in()`]
+//! and [`span_in()`], which only produce positions that exist in a given text.
+//!
+//! # Example
+//!
+//! ```
+//! use arbitrary::Unstructured;
+//! use char_positions::{arbitrary::span_in, SpanResolver};
+//!
+//! let text = "Hello 👋\nWorld 🌏\n";
+//!
+//! let mut u = Unstructured::new(&[7, 3, 1, 9, 4, 2]);
+//! let span = span_in(&mut u, text).unwrap();
+//!
+//! assert_eq!(SpanResolver::new(text).span(span.byte_range()), Some(span));
+//! ```
+
+use ::arbitrary::{size_hint, Arbitrary, Result, Unstructured};
+
+use crate::{
+    ByteEnd, ByteRange, ByteStart, Col, ColumnUnit, Line, LineCol, LineColByte, LineColByteRange,
+    Span,
+};
+
+/// Returns an arbitrary position in `text`, i.e. the position
+/// of any of its [`char`]s, or the end of `text`.
+pub fn position_in(u: &mut Unstructured<'_>, text: &str) -> Result<LineColByte> {
+    let n = u.int_in_range(0..=text.chars().count())?;
+    let byte = text.char_indices().nth(n).map_or(text.len(), |(i, _)| i);
+
+    let mut pos = LineColByte(1, 1, 0);
+    pos.advance_str(&text[..byte]);
+    Ok(pos)
+}
+
+/// Returns an arbitrary [`Span`] in `text`, which may be empty.
+///
+/// See [`position_in()`].
+pub fn span_in(u: &mut Unstructured<'_>, text: &str) -> Result<Span> {
+    let a = position_in(u, text)?;
+    let b = position_in(u, text)?;
+    Ok(Span::new(a.min(b), a.max(b)))
+}
+
+#[inline]
+fn one_indexed(u: &mut Unstructured<'_>) -> Result<usize> {
+    u.int_in_range(1..=usize::MAX)
+}
+
+#[inline]
+fn range(u: &mut Unstructured<'_>) -> Result<(usize, usize)> {
+    let a = usize::arbitrary(u)?;
+    let b = usize::arbitrary(u)?;
+    Ok((a.min(b), a.max(b)))
+}
+
+impl<'a> Arbitrary<'a> for Line {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        one_indexed(u).map(Line)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        usize::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Col {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        one_indexed(u).map(Col)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        usize::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ByteStart {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        usize::arbitrary(u).map(ByteStart)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        usize::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ByteEnd {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        usize::arbitrary(u).map(ByteEnd)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        usize::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ByteRange {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let (start, end) = range(u)?;
+        Ok(ByteRange(start..end))
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(usize::size_hint(depth), usize::size_hint(depth))
+    }
+}
+
+impl<'a> Arbitrary<'a> for LineCol {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(LineCol(one_indexed(u)?, one_indexed(u)?))
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(usize::size_hint(depth), usize::size_hint(depth))
+    }
+}
+
+impl<'a> Arbitrary<'a> for LineColByte {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let LineCol(line, col) = LineCol::arbitrary(u)?;
+        Ok(LineColByte(line, col, usize::arbitrary(u)?))
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(LineCol::size_hint(depth), usize::size_hint(depth))
+    }
+}
+
+impl<'a> Arbitrary<'a> for LineColByteRange {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let LineCol(line, col) = LineCol::arbitrary(u)?;
+        let (start, end) = range(u)?;
+        Ok(LineColByteRange(line, col, start..end))
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(LineCol::size_hint(depth), ByteRange::size_hint(depth))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Span {
+    /// Produces a [`Span`] where neither the lines and columns,
+    /// nor the byte positions, are inverted.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let a = LineCol::arbitrary(u)?;
+        let b = LineCol::arbitrary(u)?;
+        let (LineCol(start_line, start_col), LineCol(end_line, end_col)) = (a.min(b), a.max(b));
+        let (start, end) = range(u)?;
+        Ok(Span::new(
+            LineColByte(start_line, start_col, start),
+            LineColByte(end_line, end_col, end),
+        ))
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and_all(&[
+            LineCol::size_hint(depth),
+            LineCol::size_hint(depth),
+            ByteRange::size_hint(depth),
+        ])
+    }
+}
+
+impl<'a> Arbitrary<'a> for ColumnUnit {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&[ColumnUnit::Char, ColumnUnit::Utf8, ColumnUnit::Utf16])
+            .copied()
+    }
+}
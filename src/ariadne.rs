@@ -0,0 +1,131 @@
+//! Integration with [`ariadne`], enabled by the `ariadne` feature.
+//!
+//! Implements [`ariadne::Span`] for [`Span`] and [`FileSpan`], and
+//! provides [`SourceMapCache`] for using a [`SourceMap`] as an
+//! [`ariadne::Cache`].
+//!
+//! **Note:** The spans use byte positions, while [`ariadne`] defaults to
+//! [`char`] positions. Use <code>[Config]::default().with_index_type([IndexType::Byte])</code>
+//! when building reports.
+//!
+//! [Config]: ariadne::Config
+//! [IndexType::Byte]: ariadne::IndexType::Byte
+//!
+//! # Example
+//!
+//! ```
+//! use ariadne::{Config, IndexType, Label, Report, ReportKind};
+//! use char_positions::{ariadne::SourceMapCache, SourceFile, SourceMap};
+//!
+//! let mut map = SourceMap::new();
+//! let file = map.add(SourceFile::new("main.rs", "fn main() {\n    🦀\n}\n"));
+//! let span = map.span(16..20).unwrap();
+//!
+//! let mut out = Vec::new();
+//! Report::build(ReportKind::Error, file, span.span.byte_start())
+//!     .with_config(Config::default().with_color(false).with_index_type(IndexType::Byte))
+//!     .with_message("unexpected crab")
+//!     .with_label(Label::new(span).with_message("here"))
+//!     .finish()
+//!     .write(SourceMapCache::new(&map), &mut out)
+//!     .unwrap();
+//!
+//! let out = String::from_utf8(out).unwrap();
+//! assert!(out.contains("main.rs:2:5"));
+//! ```
+
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+
+use ::ariadne::{Cache, Source};
+
+use crate::{FileId, FileSpan, SourceMap, Span};
+
+/// A span without a file, identified by `()`.
+impl ::ariadne::Span for Span {
+    type SourceId = ();
+
+    #[inline]
+    fn source(&self) -> &Self::SourceId {
+        &()
+    }
+
+    #[inline]
+    fn start(&self) -> usize {
+        self.byte_start()
+    }
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.byte_end()
+    }
+}
+
+impl ::ariadne::Span for FileSpan {
+    type SourceId = FileId;
+
+    #[inline]
+    fn source(&self) -> &Self::SourceId {
+        &self.file
+    }
+
+    #[inline]
+    fn start(&self) -> usize {
+        self.span.byte_start()
+    }
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.span.byte_end()
+    }
+}
+
+/// An [`ariadne::Cache`] over the files of a [`SourceMap`].
+///
+/// Each file's [`Source`] is built on first use.
+pub struct SourceMapCache<'m> {
+    map: &'m SourceMap<'m>,
+    sources: Vec<Option<Source<&'m str>>>,
+}
+
+impl<'m> SourceMapCache<'m> {
+    #[inline]
+    pub fn new(map: &'m SourceMap<'m>) -> Self {
+        Self {
+            map,
+            sources: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Debug for SourceMapCache<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SourceMapCache")
+            .field("map", &self.map)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'m> Cache<FileId> for SourceMapCache<'m> {
+    type Storage = &'m str;
+
+    fn fetch(&mut self, id: &FileId) -> Result<&Source<&'m str>, Box<dyn fmt::Debug + '_>> {
+        let Some(file) = self.map.file(*id) else {
+            return Err(Box::new(*id));
+        };
+
+        let i = id.index();
+        if self.sources.len() <= i {
+            self.sources.resize_with(i + 1, || None);
+        }
+        Ok(self.sources[i].get_or_insert_with(|| Source::from(file.text())))
+    }
+
+    #[inline]
+    fn display<'a>(&self, id: &'a FileId) -> Option<Box<dyn fmt::Display + 'a>> {
+        let name = self.map.file(*id)?.name();
+        Some(Box::new(name.to_string()))
+    }
+}
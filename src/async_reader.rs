@@ -0,0 +1,106 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::io::{self, ErrorKind};
+
+use ::futures_core::Stream;
+use ::futures_io::AsyncRead;
+
+use crate::reader::DecodeBuffer;
+use crate::LineColByte;
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// A [`Stream`] of [`char`]s and their positions, incrementally decoded
+/// from UTF-8 read from any [`AsyncRead`].
+///
+/// This is the async variant of [`CharPositionsReader`](crate::CharPositionsReader),
+/// and behaves the same, including for invalid UTF-8.
+///
+/// Tokio's `AsyncRead` can be adapted using [`.compat()`] from tokio-util.
+/// A `Stream<Item = io::Result<Bytes>>` can be adapted using [`.into_async_read()`]
+/// from futures.
+///
+/// [`.compat()`]: https://docs.rs/tokio-util/*/tokio_util/compat/trait.TokioAsyncReadCompatExt.html#method.compat
+/// [`.into_async_read()`]: https://docs.rs/futures/*/futures/stream/trait.TryStreamExt.html#method.into_async_read
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{AsyncCharPositionsReader, LineColByte};
+/// use futures::{executor::block_on, TryStreamExt};
+///
+/// block_on(async {
+///     let reader = "Hello 👋\nWorld 🌏\n".as_bytes();
+///
+///     let chars = AsyncCharPositionsReader::new(reader)
+///         .try_collect::<Vec<_>>()
+///         .await
+///         .unwrap();
+///
+///     assert_eq!(chars[6], (LineColByte(1, 7, 6), '👋'));
+///     assert_eq!(chars[8], (LineColByte(2, 1, 11), 'W'));
+/// });
+/// ```
+#[derive(Debug)]
+pub struct AsyncCharPositionsReader<R> {
+    reader: R,
+    buf: DecodeBuffer,
+}
+
+impl<R: AsyncRead + Unpin> AsyncCharPositionsReader<R> {
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, reader)
+    }
+
+    /// Creates a new `AsyncCharPositionsReader` with a buffer
+    /// of at least `capacity` bytes.
+    #[inline]
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        Self {
+            reader,
+            buf: DecodeBuffer::with_capacity(capacity),
+        }
+    }
+}
+
+impl<R> AsyncCharPositionsReader<R> {
+    /// Returns the position of the next [`char`].
+    #[inline]
+    pub fn position(&self) -> LineColByte {
+        self.buf.pos
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Any buffered, but not yet polled, bytes are lost.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: AsyncRead + Unpin> Stream for AsyncCharPositionsReader<R> {
+    type Item = io::Result<(LineColByte, char)>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(item) = this.buf.decode_next() {
+                return Poll::Ready(item);
+            }
+
+            match Pin::new(&mut this.reader).poll_read(cx, this.buf.spare()) {
+                Poll::Ready(Ok(n)) => this.buf.filled(n),
+                Poll::Ready(Err(err)) if err.kind() == ErrorKind::Interrupted => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Some(Err(err))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Locate and convert positions in files, enabled by the `cli` feature.
+//!
+//! ```text
+//! char-positions locate FILE --byte 1234
+//! char-positions locate FILE --at 12:5
+//! char-positions dump FILE [--lines] [--format text|json]
+//! char-positions grep PATTERN FILE... [--format text|json]
+//! ```
+
+use std::fmt::Write as _;
+use std::process::ExitCode;
+use std::{env, fs};
+
+use char_positions::json::{self, to_json};
+use char_positions::render::Label;
+use char_positions::{CharPositionsExt, LineCol, LineColByteRange, LineIndex, SpanResolver};
+
+const USAGE: &str = "\
+usage: char-positions locate FILE --byte BYTE
+       char-positions locate FILE --at LINE:COL
+       char-positions dump FILE [--lines] [--format text|json]
+       char-positions grep PATTERN FILE... [--format text|json]
+
+Lines and columns are 1-indexed, with columns counted in chars.
+The PATTERN of grep is matched literally.";
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let result = match args.as_slice() {
+        ["locate", file, rest @ ..] => locate(file, rest),
+        ["dump", file, rest @ ..] => dump(file, rest),
+        ["grep", pattern, rest @ ..] => grep(pattern, rest),
+        ["-h" | "--help"] => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        _ => Err(Error::Usage),
+    };
+
+    match result {
+        Ok(out) => {
+            print!("{out}");
+            ExitCode::SUCCESS
+        }
+        Err(Error::Usage) => {
+            eprintln!("{USAGE}");
+            ExitCode::from(2)
+        }
+        Err(Error::Message(msg)) => {
+            eprintln!("error: {msg}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+enum Error {
+    Usage,
+    Message(String),
+}
+
+fn read(file: &str) -> Result<String, Error> {
+    fs::read_to_string(file).map_err(|err| Error::Message(format!("{file}: {err}")))
+}
+
+fn locate(file: &str, args: &[&str]) -> Result<String, Error> {
+    let text = read(file)?;
+    let index = LineIndex::new(&text);
+
+    match args {
+        ["--byte", byte] => {
+            let byte = byte
+                .parse::<usize>()
+                .map_err(|err| Error::Message(format!("invalid byte `{byte}`: {err}")))?;
+            let pos = index.line_col(&text, byte).ok_or_else(|| {
+                Error::Message(format!(
+                    "byte {byte} is out of bounds or not on a char boundary"
+                ))
+            })?;
+            Ok(format!("{pos}\n"))
+        }
+        ["--at", at] => {
+            let pos = at
+                .parse::<LineCol>()
+                .map_err(|err| Error::Message(format!("invalid position `{at}`: {err}")))?;
+            let byte = index
+                .byte_of(&text, pos)
+                .ok_or_else(|| Error::Message(format!("position {pos} is out of bounds")))?;
+            Ok(format!("{byte}\n"))
+        }
+        _ => Err(Error::Usage),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn dump(file: &str, mut args: &[&str]) -> Result<String, Error> {
+    let mut lines = false;
+    let mut format = Format::Text;
+    loop {
+        args = match args {
+            ["--lines", rest @ ..] => {
+                lines = true;
+                rest
+            }
+            ["--format", "text", rest @ ..] => {
+                format = Format::Text;
+                rest
+            }
+            ["--format", "json", rest @ ..] => {
+                format = Format::Json;
+                rest
+            }
+            [] => break,
+            _ => return Err(Error::Usage),
+        };
+    }
+
+    let text = read(file)?;
+    let mut out = String::new();
+    if lines {
+        dump_lines(&mut out, &text, format);
+    } else {
+        dump_chars(&mut out, &text, format);
+    }
+    Ok(out)
+}
+
+fn grep(pattern: &str, mut args: &[&str]) -> Result<String, Error> {
+    if pattern.is_empty() {
+        return Err(Error::Message("empty pattern".to_owned()));
+    }
+
+    let mut files = Vec::new();
+    let mut format = Format::Text;
+    loop {
+        args = match args {
+            ["--format", "text", rest @ ..] => {
+                format = Format::Text;
+                rest
+            }
+            ["--format", "json", rest @ ..] => {
+                format = Format::Json;
+                rest
+            }
+            [flag, ..] if flag.starts_with("--") => return Err(Error::Usage),
+            [file, rest @ ..] => {
+                files.push(*file);
+                rest
+            }
+            [] => break,
+        };
+    }
+    if files.is_empty() {
+        return Err(Error::Usage);
+    }
+
+    let mut out = String::new();
+    let mut matches = Vec::new();
+    for file in files {
+        let text = read(file)?;
+        let mut resolver = SpanResolver::new(&text);
+        for (start, m) in text.match_indices(pattern) {
+            let Some(span) = resolver.span(start..(start + m.len())) else {
+                continue;
+            };
+            match format {
+                Format::Text => {
+                    let (start, end) = (span.start_line_col(), span.end_line_col());
+                    if start.0 == end.0 {
+                        _ = writeln!(out, "{file}:{start}-{}", end.1);
+                    } else {
+                        _ = writeln!(out, "{file}:{span}");
+                    }
+                }
+                Format::Json => matches.push((file, Label::new(span, m.to_owned()))),
+            }
+        }
+    }
+
+    if let Format::Json = format {
+        out = to_json(matches.iter().map(|(file, label)| (*file, label)));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn dump_chars(out: &mut String, text: &str, format: Format) {
+    let mut first = true;
+    if let Format::Json = format {
+        out.push('[');
+    }
+    for (LineColByteRange(line, col, r), c) in text.char_positions() {
+        match format {
+            Format::Text => {
+                _ = writeln!(out, "{line}:{col}\t{}..{}\t{c:?}", r.start, r.end);
+            }
+            Format::Json => {
+                if !std::mem::take(&mut first) {
+                    out.push(',');
+                }
+                _ = write!(
+                    out,
+                    "{{\"line\":{line},\"column\":{col},\"byte_start\":{},\"byte_end\":{},\"char\":",
+                    r.start, r.end,
+                );
+                _ = json::write_str(out, c.encode_utf8(&mut [0; 4]));
+                out.push('}');
+            }
+        }
+    }
+    if let Format::Json = format {
+        out.push_str("]\n");
+    }
+}
+
+fn dump_lines(out: &mut String, text: &str, format: Format) {
+    let index = LineIndex::new(text);
+    if let Format::Json = format {
+        out.push('[');
+    }
+    for line in 1..=index.line_count() {
+        let Some(r) = index.line_range(line) else {
+            continue;
+        };
+        let chars = text[r.clone()].trim_end_matches('\n').chars().count();
+        match format {
+            Format::Text => {
+                _ = writeln!(out, "{line}\t{}..{}\t{chars}", r.start, r.end);
+            }
+            Format::Json => {
+                if line > 1 {
+                    out.push(',');
+                }
+                _ = write!(
+                    out,
+                    "{{\"line\":{line},\"byte_start\":{},\"byte_end\":{},\"chars\":{chars}}}",
+                    r.start, r.end,
+                );
+            }
+        }
+    }
+    if let Format::Json = format {
+        out.push_str("]\n");
+    }
+}
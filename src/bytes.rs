@@ -0,0 +1,218 @@
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::counter::LineColCounter;
+use crate::utf8::{self, Decoded};
+use crate::{CharPosition, LineColByteRange};
+
+pub trait BytePositionsExt {
+    /// Returns an iterator over the bytes of the text and their positions,
+    /// where every byte of a multi-byte [`char`] has the line and column
+    /// of the [`char`], and the byte range of the byte itself.
+    ///
+    /// The text does not need to be valid UTF-8, in which case each invalid
+    /// sequence counts as a single column, see
+    /// [`CheckedUtf8Ext::char_positions_checked()`].
+    ///
+    /// Use [`BytePositions::with_sub_columns()`] to also get the index
+    /// of each byte within its [`char`].
+    ///
+    /// [`CheckedUtf8Ext::char_positions_checked()`]: crate::CheckedUtf8Ext::char_positions_checked
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{BytePositionsExt, LineColByte};
+    ///
+    /// let text = b"\xFF\xF0\x9F\xA6\x80\n\xF0\x9F\xA6x";
+    ///
+    /// let positions = text.byte_positions::<LineColByte>().collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     positions,
+    ///     [
+    ///         (LineColByte(1, 1, 0), 0xFF),
+    ///         (LineColByte(1, 2, 1), 0xF0),
+    ///         (LineColByte(1, 2, 2), 0x9F),
+    ///         (LineColByte(1, 2, 3), 0xA6),
+    ///         (LineColByte(1, 2, 4), 0x80),
+    ///         (LineColByte(1, 3, 5), b'\n'),
+    ///         // An invalid sequence of 3 bytes
+    ///         (LineColByte(2, 1, 6), 0xF0),
+    ///         (LineColByte(2, 1, 7), 0x9F),
+    ///         (LineColByte(2, 1, 8), 0xA6),
+    ///         (LineColByte(2, 2, 9), b'x'),
+    ///     ],
+    /// );
+    /// ```
+    fn byte_positions<T>(&self) -> BytePositions<'_, T>
+    where
+        T: CharPosition,
+        LineColByteRange: Into<T>;
+}
+
+impl BytePositionsExt for [u8] {
+    #[inline]
+    fn byte_positions<T>(&self) -> BytePositions<'_, T>
+    where
+        T: CharPosition,
+        LineColByteRange: Into<T>,
+    {
+        BytePositions {
+            bytes: self,
+            index: 0,
+            char_range: 0..0,
+            pos: LineColCounter::START,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl BytePositionsExt for str {
+    #[inline]
+    fn byte_positions<T>(&self) -> BytePositions<'_, T>
+    where
+        T: CharPosition,
+        LineColByteRange: Into<T>,
+    {
+        self.as_bytes().byte_positions()
+    }
+}
+
+/// An iterator over bytes and their positions.
+///
+/// See [`BytePositionsExt::byte_positions()`].
+#[derive(Clone, Debug)]
+pub struct BytePositions<'a, T> {
+    bytes: &'a [u8],
+    /// The index of the next byte.
+    index: usize,
+    /// The byte range of the [`char`] containing the previous byte.
+    char_range: Range<usize>,
+    pos: LineColCounter,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T> BytePositions<'a, T> {
+    /// Returns the remaining bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        &self.bytes[self.index..]
+    }
+
+    /// Returns an iterator which additionally produces the 0-indexed
+    /// index of each byte within its [`char`], i.e. the sub-column.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{BytePositionsExt, LineCol};
+    ///
+    /// let subs = "aé"
+    ///     .byte_positions::<LineCol>()
+    ///     .with_sub_columns()
+    ///     .map(|(pos, sub, _)| (pos, sub))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(subs, [(LineCol(1, 1), 0), (LineCol(1, 2), 0), (LineCol(1, 2), 1)]);
+    /// ```
+    #[inline]
+    pub fn with_sub_columns(self) -> SubColumnBytePositions<'a, T> {
+        SubColumnBytePositions { iter: self }
+    }
+
+    /// Returns the next byte, its position, and its index within its [`char`].
+    fn next_byte(&mut self) -> Option<(LineColByteRange, usize, u8)>
+    where
+        T: CharPosition,
+    {
+        let i = self.index;
+        let &b = self.bytes.get(i)?;
+
+        if i >= self.char_range.end {
+            // Advance past the previous `char`
+            if T::LINE_COL && (i > 0) {
+                let newline = self.bytes[self.char_range.start] == b'\n';
+                self.pos.advance(if newline { '\n' } else { ' ' });
+            }
+            self.char_range = i..(i + utf8_len(&self.bytes[i..]));
+        }
+
+        self.index += 1;
+        Some((self.pos.at(i..(i + 1)), i - self.char_range.start, b))
+    }
+}
+
+impl<T> Iterator for BytePositions<'_, T>
+where
+    T: CharPosition,
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, u8);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pos, _sub, b) = self.next_byte()?;
+        Some((T::from_position(&pos), b))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bytes.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for BytePositions<'_, T> where Self: Iterator {}
+
+impl<T> FusedIterator for BytePositions<'_, T> where Self: Iterator {}
+
+/// An iterator over bytes, their positions, and their index within their [`char`].
+///
+/// See [`BytePositions::with_sub_columns()`].
+#[derive(Clone, Debug)]
+pub struct SubColumnBytePositions<'a, T> {
+    iter: BytePositions<'a, T>,
+}
+
+impl<'a, T> SubColumnBytePositions<'a, T> {
+    /// Returns the remaining bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.iter.as_bytes()
+    }
+}
+
+impl<T> Iterator for SubColumnBytePositions<'_, T>
+where
+    T: CharPosition,
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, usize, u8);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pos, sub, b) = self.iter.next_byte()?;
+        Some((T::from_position(&pos), sub, b))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for SubColumnBytePositions<'_, T> where Self: Iterator {}
+
+impl<T> FusedIterator for SubColumnBytePositions<'_, T> where Self: Iterator {}
+
+/// Returns the length of the UTF-8 encoded [`char`] at the start of
+/// `bytes`, or of the invalid sequence it starts with.
+#[inline]
+fn utf8_len(bytes: &[u8]) -> usize {
+    match utf8::decode(bytes) {
+        Decoded::Char(c) => c.len_utf8(),
+        Decoded::Invalid(n) => n,
+        // A valid prefix of a `char` at the end of the text
+        Decoded::Incomplete => bytes.len(),
+    }
+}
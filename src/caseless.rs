@@ -0,0 +1,67 @@
+//! Integration with [`caseless`], enabled by the `caseless` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{caseless::CaselessPositionsExt, LineCol};
+//!
+//! let text = "🦀 Crab\nSTRASSE und Straße";
+//!
+//! let span = text.find_position_ignore_case("strasse").unwrap();
+//! assert_eq!(&text[span.byte_range()], "STRASSE");
+//! assert_eq!(span.start_line_col(), LineCol(2, 1));
+//!
+//! let span = text.find_position_ignore_case("STRASSE UND STRASSE").unwrap();
+//! assert_eq!(&text[span.byte_range()], "STRASSE und Straße");
+//! assert_eq!(span.end_line_col(), LineCol(2, 19));
+//! ```
+
+use alloc::vec::Vec;
+
+use ::caseless::Caseless;
+
+use crate::{Span, SpanResolver};
+
+pub trait CaselessPositionsExt {
+    /// Returns the [`Span`] of the first occurrence of `needle`,
+    /// ignoring case using Unicode default case folding.
+    ///
+    /// The span covers the original text, not the case folded text,
+    /// even if case folding changes the length, e.g. `'ß'` folds to `"ss"`.
+    /// A match must start and end on [`char`] boundaries of the original
+    /// text, i.e. `"s"` does not match half of `'ß'`.
+    ///
+    /// For ASCII only case folding, see
+    /// [`FindPositionsExt::find_position_ignore_ascii_case()`](crate::FindPositionsExt::find_position_ignore_ascii_case).
+    fn find_position_ignore_case(&self, needle: &str) -> Option<Span>;
+}
+
+impl CaselessPositionsExt for str {
+    fn find_position_ignore_case(&self, needle: &str) -> Option<Span> {
+        let needle = needle.chars().default_case_fold().collect::<Vec<_>>();
+        let (start, len) = self
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([self.len()])
+            .find_map(|start| Some((start, match_folded(&self[start..], &needle)?)))?;
+        SpanResolver::new(self).span(start..(start + len))
+    }
+}
+
+/// Returns the length in bytes of the prefix of `text`,
+/// which case folds to `needle`.
+fn match_folded(text: &str, needle: &[char]) -> Option<usize> {
+    let mut rest = needle;
+    for (i, c) in text.char_indices() {
+        if rest.is_empty() {
+            return Some(i);
+        }
+        for folded in core::iter::once(c).default_case_fold() {
+            match rest.split_first() {
+                Some((&expected, tail)) if expected == folded => rest = tail,
+                _ => return None,
+            }
+        }
+    }
+    rest.is_empty().then_some(text.len())
+}
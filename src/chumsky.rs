@@ -0,0 +1,94 @@
+//! Integration with [`chumsky`], enabled by the `chumsky` feature.
+//!
+//! Implements [`chumsky::Span`] for [`Span`], and provides [`stream()`]
+//! for parsing text where each [`char`] carries its [`Span`].
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{chumsky::stream, LineCol, Span};
+//! use chumsky::prelude::*;
+//!
+//! let parser = text::ident::<char, Simple<char, Span>>()
+//!     .map_with_span(|ident, span: Span| (ident, span))
+//!     .padded()
+//!     .repeated()
+//!     .then_ignore(end());
+//!
+//! let idents = parser.parse(stream("foo\n  bar")).unwrap();
+//! assert_eq!(idents[1].0, "bar");
+//! assert_eq!(idents[1].1.start_line_col(), LineCol(2, 3));
+//! assert_eq!(idents[1].1.end_line_col(), LineCol(2, 6));
+//! ```
+
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use ::chumsky::Stream;
+
+use crate::{CharPositions, CharPositionsExt, LineColByte, LineColByteRange, Span};
+
+/// A [`chumsky::Stream`] of [`char`]s and their [`Span`]s.
+///
+/// See [`stream()`].
+pub type CharStream<'a> = Stream<'a, char, Span, CharSpans<'a>>;
+
+/// Returns a [`chumsky::Stream`] of the [`char`]s of `text` and their [`Span`]s.
+///
+/// The span of the end of input is the empty span at the end of `text`.
+pub fn stream(text: &str) -> CharStream<'_> {
+    let mut eoi = LineColByte(1, 1, 0);
+    eoi.advance_str(text);
+    Stream::from_iter(Span::new(eoi, eoi), char_spans(text))
+}
+
+/// Returns an iterator over the [`char`]s of `text` and their [`Span`]s.
+#[inline]
+pub fn char_spans(text: &str) -> CharSpans<'_> {
+    CharSpans(text.char_positions())
+}
+
+/// An iterator over [`char`]s and their [`Span`]s.
+///
+/// See [`char_spans()`].
+#[derive(Clone, Debug)]
+pub struct CharSpans<'a>(CharPositions<'a, LineColByteRange>);
+
+impl Iterator for CharSpans<'_> {
+    type Item = (char, Span);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (LineColByteRange(line, col, r), c) = self.0.next()?;
+        let start = LineColByte(line, col, r.start);
+        let end = match c {
+            '\n' => LineColByte(line + 1, 1, r.end),
+            _ => LineColByte(line, col + 1, r.end),
+        };
+        Some((c, Span::new(start, end)))
+    }
+}
+
+impl FusedIterator for CharSpans<'_> {}
+
+impl ::chumsky::Span for Span {
+    type Context = ();
+    type Offset = LineColByte;
+
+    #[inline]
+    fn new((): Self::Context, range: Range<Self::Offset>) -> Self {
+        Self::new(range.start, range.end)
+    }
+
+    #[inline]
+    fn context(&self) -> Self::Context {}
+
+    #[inline]
+    fn start(&self) -> Self::Offset {
+        self.start
+    }
+
+    #[inline]
+    fn end(&self) -> Self::Offset {
+        self.end
+    }
+}
@@ -0,0 +1,98 @@
+//! Positioned iteration over text split into multiple chunks, e.g. ropes,
+//! gap buffers, or lists of memory mapped segments.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{chunks, LineColByteRange};
+//!
+//! let text = ["Hello 👋\nWo", "rld 🌏", "\n🦀🦀"];
+//!
+//! let mut iter = chunks::char_positions::<_, LineColByteRange>(text)
+//!     .map(|(LineColByteRange(line, col, range), c)| (line, col, range, c))
+//!     .skip(9);
+//!
+//! assert_eq!(iter.next(), Some((2, 2, 12..13, 'o')));
+//! assert_eq!(iter.next(), Some((2, 3, 13..14, 'r')));
+//! ```
+
+use core::iter::{Fuse, FusedIterator};
+use core::marker::PhantomData;
+
+use char_ranges::{CharRanges, CharRangesExt};
+
+use crate::counter::LineColCounter;
+use crate::LineColByteRange;
+
+/// Returns an iterator over the [`char`]s of `chunks` and their positions.
+///
+/// The chunks are treated as one contiguous text, i.e. lines and byte
+/// positions continue across chunks, and must not split a [`char`].
+///
+/// See [`CharPositionsExt::char_positions()`](crate::CharPositionsExt::char_positions).
+#[inline]
+pub fn char_positions<'a, I, T>(chunks: I) -> ChunkedCharPositions<'a, I::IntoIter, T>
+where
+    I: IntoIterator<Item = &'a str>,
+    LineColByteRange: Into<T>,
+{
+    ChunkedCharPositions {
+        chunks: chunks.into_iter().fuse(),
+        chunk: "".char_ranges(),
+        start: 0,
+        end: 0,
+        pos: LineColCounter::START,
+        phantom: PhantomData,
+    }
+}
+
+/// An iterator over the [`char`]s of chunks of text and their positions.
+///
+/// See [`char_positions()`].
+#[derive(Clone, Debug)]
+pub struct ChunkedCharPositions<'a, I, T> {
+    /// Fused, such that the iterator is fused regardless of `I`.
+    chunks: Fuse<I>,
+    chunk: CharRanges<'a>,
+    /// The start byte position of `chunk`.
+    start: usize,
+    /// The end byte position of `chunk`.
+    end: usize,
+    pos: LineColCounter,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, I, T> ChunkedCharPositions<'a, I, T> {
+    /// Returns the remaining substring of the current chunk.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.chunk.as_str()
+    }
+}
+
+impl<'a, I, T> Iterator for ChunkedCharPositions<'a, I, T>
+where
+    I: Iterator<Item = &'a str>,
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (r, c) = loop {
+            if let Some(next) = self.chunk.next() {
+                break next;
+            }
+            let chunk = self.chunks.next()?;
+            self.start = self.end;
+            self.end = self.end.saturating_add(chunk.len());
+            self.chunk = chunk.char_ranges();
+        };
+
+        let start = self.start;
+        let pos = self.pos.at((start + r.start)..(start + r.end));
+        self.pos.advance(c);
+        Some((pos.into(), c))
+    }
+}
+
+impl<I, T> FusedIterator for ChunkedCharPositions<'_, I, T> where Self: Iterator {}
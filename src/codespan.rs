@@ -0,0 +1,118 @@
+//! Integration with [`codespan_reporting`], enabled by the `codespan` feature.
+//!
+//! Implements [`Files`] for [`SourceFile`] and [`SourceMap`], such that
+//! they can be passed directly to [`codespan_reporting::term::emit()`].
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{codespan, SourceFile, SourceMap};
+//! use codespan_reporting::diagnostic::Diagnostic;
+//! use codespan_reporting::term::{self, termcolor::NoColor};
+//!
+//! let mut map = SourceMap::new();
+//! map.add(SourceFile::new("main.rs", "fn main() {\n    x\n}\n"));
+//! let span = map.span(16..17).unwrap();
+//!
+//! let diagnostic = Diagnostic::error()
+//!     .with_message("cannot find value `x` in this scope")
+//!     .with_labels(vec![codespan::primary_label(&span).with_message("not found")]);
+//!
+//! let mut out = NoColor::new(Vec::new());
+//! term::emit(&mut out, &term::Config::default(), &map, &diagnostic).unwrap();
+//! let out = String::from_utf8(out.into_inner()).unwrap();
+//! assert!(out.contains("main.rs:2:5"));
+//! ```
+
+use core::ops::Range;
+
+use codespan_reporting::diagnostic::Label;
+use codespan_reporting::files::{Error, Files};
+
+use crate::{FileId, FileSpan, SourceFile, SourceMap};
+
+/// Returns a primary [`Label`] for `span`.
+#[inline]
+pub fn primary_label(span: &FileSpan) -> Label<FileId> {
+    Label::primary(span.file, span.span)
+}
+
+/// Returns a secondary [`Label`] for `span`.
+#[inline]
+pub fn secondary_label(span: &FileSpan) -> Label<FileId> {
+    Label::secondary(span.file, span.span)
+}
+
+fn line_index(file: &SourceFile<'_>, byte_index: usize) -> Result<usize, Error> {
+    match file.line_index().line_of(byte_index) {
+        Some(line) => Ok(line - 1),
+        None => Err(Error::IndexTooLarge {
+            given: byte_index,
+            max: file.text().len(),
+        }),
+    }
+}
+
+fn line_range(file: &SourceFile<'_>, line_index: usize) -> Result<Range<usize>, Error> {
+    let index = file.line_index();
+    match index.line_range(line_index + 1) {
+        Some(range) => Ok(range),
+        None => Err(Error::LineTooLarge {
+            given: line_index,
+            max: index.line_count() - 1,
+        }),
+    }
+}
+
+/// A single file, identified by `()`.
+impl<'a> Files<'a> for SourceFile<'_> {
+    type FileId = ();
+    type Name = &'a str;
+    type Source = &'a str;
+
+    #[inline]
+    fn name(&'a self, (): ()) -> Result<Self::Name, Error> {
+        Ok(self.name())
+    }
+
+    #[inline]
+    fn source(&'a self, (): ()) -> Result<Self::Source, Error> {
+        Ok(self.text())
+    }
+
+    #[inline]
+    fn line_index(&'a self, (): (), byte_index: usize) -> Result<usize, Error> {
+        line_index(self, byte_index)
+    }
+
+    #[inline]
+    fn line_range(&'a self, (): (), line_index: usize) -> Result<Range<usize>, Error> {
+        line_range(self, line_index)
+    }
+}
+
+impl<'a> Files<'a> for SourceMap<'_> {
+    type FileId = FileId;
+    type Name = &'a str;
+    type Source = &'a str;
+
+    #[inline]
+    fn name(&'a self, id: FileId) -> Result<Self::Name, Error> {
+        Ok(self.file(id).ok_or(Error::FileMissing)?.name())
+    }
+
+    #[inline]
+    fn source(&'a self, id: FileId) -> Result<Self::Source, Error> {
+        Ok(self.file(id).ok_or(Error::FileMissing)?.text())
+    }
+
+    #[inline]
+    fn line_index(&'a self, id: FileId, byte_index: usize) -> Result<usize, Error> {
+        line_index(self.file(id).ok_or(Error::FileMissing)?, byte_index)
+    }
+
+    #[inline]
+    fn line_range(&'a self, id: FileId, line_index: usize) -> Result<Range<usize>, Error> {
+        line_range(self.file(id).ok_or(Error::FileMissing)?, line_index)
+    }
+}
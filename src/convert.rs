@@ -0,0 +1,131 @@
+use core::fmt;
+use core::num::NonZeroUsize;
+
+use crate::{Col, Line, LineCol, LineColByte};
+
+/// An error returned when converting a 0 line or column into
+/// a position, as lines and columns are 1-indexed.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{LineCol, LineColByte};
+///
+/// assert_eq!(LineCol::try_from((12, 5)), Ok(LineCol(12, 5)));
+/// assert!(LineCol::try_from((0, 5)).is_err());
+/// assert!(LineColByte::try_from((12, 0, 100)).is_err());
+///
+/// let (line, col) = LineCol(12, 5).into();
+/// assert_eq!((line, col), (12, 5));
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct TryFromPositionError(());
+
+impl fmt::Display for TryFromPositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("line and column must be greater than 0")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromPositionError {}
+
+#[inline]
+fn one_indexed(n: usize) -> Result<usize, TryFromPositionError> {
+    match n {
+        0 => Err(TryFromPositionError(())),
+        n => Ok(n),
+    }
+}
+
+impl TryFrom<usize> for Line {
+    type Error = TryFromPositionError;
+
+    #[inline]
+    fn try_from(line: usize) -> Result<Self, Self::Error> {
+        one_indexed(line).map(Self)
+    }
+}
+
+impl TryFrom<usize> for Col {
+    type Error = TryFromPositionError;
+
+    #[inline]
+    fn try_from(col: usize) -> Result<Self, Self::Error> {
+        one_indexed(col).map(Self)
+    }
+}
+
+impl TryFrom<(usize, usize)> for LineCol {
+    type Error = TryFromPositionError;
+
+    #[inline]
+    fn try_from((line, col): (usize, usize)) -> Result<Self, Self::Error> {
+        Ok(Self(one_indexed(line)?, one_indexed(col)?))
+    }
+}
+
+impl TryFrom<(usize, usize, usize)> for LineColByte {
+    type Error = TryFromPositionError;
+
+    #[inline]
+    fn try_from((line, col, byte): (usize, usize, usize)) -> Result<Self, Self::Error> {
+        Ok(Self(one_indexed(line)?, one_indexed(col)?, byte))
+    }
+}
+
+impl From<NonZeroUsize> for Line {
+    #[inline]
+    fn from(line: NonZeroUsize) -> Self {
+        Self(line.get())
+    }
+}
+
+impl From<NonZeroUsize> for Col {
+    #[inline]
+    fn from(col: NonZeroUsize) -> Self {
+        Self(col.get())
+    }
+}
+
+impl From<(NonZeroUsize, NonZeroUsize)> for LineCol {
+    #[inline]
+    fn from((line, col): (NonZeroUsize, NonZeroUsize)) -> Self {
+        Self(line.get(), col.get())
+    }
+}
+
+impl From<(NonZeroUsize, NonZeroUsize, usize)> for LineColByte {
+    #[inline]
+    fn from((line, col, byte): (NonZeroUsize, NonZeroUsize, usize)) -> Self {
+        Self(line.get(), col.get(), byte)
+    }
+}
+
+impl From<Line> for usize {
+    #[inline]
+    fn from(Line(line): Line) -> Self {
+        line
+    }
+}
+
+impl From<Col> for usize {
+    #[inline]
+    fn from(Col(col): Col) -> Self {
+        col
+    }
+}
+
+impl From<LineCol> for (usize, usize) {
+    #[inline]
+    fn from(LineCol(line, col): LineCol) -> Self {
+        (line, col)
+    }
+}
+
+impl From<LineColByte> for (usize, usize, usize) {
+    #[inline]
+    fn from(LineColByte(line, col, byte): LineColByte) -> Self {
+        (line, col, byte)
+    }
+}
@@ -0,0 +1,64 @@
+use core::iter::FusedIterator;
+
+use crate::{CharPositions, LineColByteRange};
+
+/// An iterator over [`char`]s and their positions, which knows the
+/// number of remaining [`char`]s.
+///
+/// See [`CharPositions::with_len()`].
+#[derive(Clone, Debug)]
+pub struct CountedCharPositions<'a, T> {
+    pub(crate) iter: CharPositions<'a, T>,
+    /// The number of remaining [`char`]s.
+    pub(crate) len: usize,
+}
+
+impl<'a, T> CountedCharPositions<'a, T> {
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+
+    /// Returns the inner iterator, discarding the count.
+    #[inline]
+    pub fn into_inner(self) -> CharPositions<'a, T> {
+        self.iter
+    }
+}
+
+impl<T> Iterator for CountedCharPositions<'_, T>
+where
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        self.len -= 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.iter.fold(init, f)
+    }
+}
+
+impl<T> ExactSizeIterator for CountedCharPositions<'_, T> where Self: Iterator {}
+
+impl<T> FusedIterator for CountedCharPositions<'_, T> where Self: Iterator {}
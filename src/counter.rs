@@ -0,0 +1,176 @@
+//! The counters tracked by iterators, stored as [`u32`] if the `compact`
+//! feature is enabled, halving their size on 64-bit targets.
+//!
+//! With the `compact` feature, lines and columns are only correct below
+//! [`u32::MAX`], while byte positions are always tracked as [`usize`].
+//! Counters which exceed the range saturate, unless an [`OverflowPolicy`]
+//! says otherwise.
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{CharPositions, LineCol, LineColByteRange};
+
+#[cfg(feature = "compact")]
+pub(crate) type Count = u32;
+#[cfg(not(feature = "compact"))]
+pub(crate) type Count = usize;
+
+#[cfg(feature = "compact")]
+#[inline]
+pub(crate) const fn uncount(n: Count) -> usize {
+    n as usize
+}
+
+#[cfg(not(feature = "compact"))]
+#[inline]
+pub(crate) const fn uncount(n: Count) -> usize {
+    n
+}
+
+/// How a line or column is handled when it exceeds the range of the
+/// counter tracking it, i.e. [`u32`] with the `compact` feature, and
+/// [`usize`] otherwise.
+///
+/// Use [`CharPositions::checked()`] to instead produce an error.
+///
+/// [`CharPositions::checked()`]: crate::CharPositions::checked
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub enum OverflowPolicy {
+    /// The line or column stays at the maximum value.
+    #[default]
+    Saturate,
+    /// The line or column wraps around to 1, as lines and columns are 1-indexed.
+    Wrap,
+}
+
+/// The line or column of a [`char`] exceeded the range of the counter
+/// tracking it.
+///
+/// See [`CharPositions::checked()`](crate::CharPositions::checked).
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct PositionOverflowError {
+    pub(crate) byte: usize,
+}
+
+impl PositionOverflowError {
+    /// Returns the start byte position of the [`char`] whose line or
+    /// column could not be represented.
+    #[inline]
+    pub fn byte(&self) -> usize {
+        self.byte
+    }
+}
+
+impl fmt::Display for PositionOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line or column overflowed at byte {}", self.byte)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PositionOverflowError {}
+
+/// An iterator over [`char`]s and their positions, which produces an
+/// error if a line or column overflows.
+///
+/// See [`CharPositions::checked()`].
+#[derive(Clone, Debug)]
+pub struct CheckedCharPositions<'a, T> {
+    pub(crate) iter: CharPositions<'a, T>,
+    pub(crate) done: bool,
+}
+
+impl<'a, T> CheckedCharPositions<'a, T> {
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+}
+
+impl<T> Iterator for CheckedCharPositions<'_, T>
+where
+    LineColByteRange: Into<T>,
+{
+    type Item = Result<(T, char), PositionOverflowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if self.iter.pos.overflowed {
+            self.done = true;
+            let (r, _) = self.iter.iter.next()?;
+            return Some(Err(PositionOverflowError { byte: r.start }));
+        }
+        self.iter.next().map(Ok)
+    }
+}
+
+impl<T> FusedIterator for CheckedCharPositions<'_, T> where Self: Iterator {}
+
+/// The line and column of the next [`char`].
+#[derive(Clone, Copy)]
+pub(crate) struct LineColCounter {
+    line: Count,
+    col: Count,
+    pub(crate) policy: OverflowPolicy,
+    /// Whether the line or column has overflowed.
+    pub(crate) overflowed: bool,
+}
+
+impl LineColCounter {
+    pub(crate) const START: Self = Self {
+        line: 1,
+        col: 1,
+        policy: OverflowPolicy::Saturate,
+        overflowed: false,
+    };
+
+    #[inline]
+    pub(crate) const fn line_col(self) -> LineCol {
+        LineCol(uncount(self.line), uncount(self.col))
+    }
+
+    /// Returns the position of a [`char`] at this line and column,
+    /// spanning the byte range `r`.
+    #[inline]
+    pub(crate) const fn at(self, r: Range<usize>) -> LineColByteRange {
+        LineColByteRange(uncount(self.line), uncount(self.col), r)
+    }
+
+    #[inline]
+    pub(crate) fn advance(&mut self, c: char) {
+        if c == '\n' {
+            self.line = self.increment(self.line);
+            self.col = 1;
+        } else {
+            self.col = self.increment(self.col);
+        }
+    }
+
+    #[inline]
+    fn increment(&mut self, n: Count) -> Count {
+        match n.checked_add(1) {
+            Some(n) => n,
+            None => self.overflow(n),
+        }
+    }
+
+    #[cold]
+    fn overflow(&mut self, n: Count) -> Count {
+        self.overflowed = true;
+        match self.policy {
+            OverflowPolicy::Saturate => n,
+            OverflowPolicy::Wrap => 1,
+        }
+    }
+}
+
+impl fmt::Debug for LineColCounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.line_col(), f)
+    }
+}
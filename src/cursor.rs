@@ -0,0 +1,524 @@
+//! A cursor for hand written lexers, which keeps track of the
+//! line, column, and byte position of the remaining text.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::cursor::PosCursor;
+//! use char_positions::{LineCol, LineColByte};
+//!
+//! let mut cursor = PosCursor::new("let 🦀 = 12;\n");
+//!
+//! let start = cursor.start_span();
+//! let ident = cursor.eat_while(|c| c.is_alphabetic());
+//! assert_eq!(ident, "let");
+//! assert_eq!(cursor.end_span(start).end_line_col(), LineCol(1, 4));
+//!
+//! cursor.eat_while(char::is_whitespace);
+//! assert_eq!(cursor.bump(), Some('🦀'));
+//! assert_eq!(cursor.position(), LineColByte(1, 6, 8));
+//!
+//! cursor.eat_while(char::is_whitespace);
+//! assert!(cursor.eat('='));
+//! cursor.eat_while(char::is_whitespace);
+//!
+//! let start = cursor.start_span();
+//! cursor.eat_while(|c| c.is_ascii_digit());
+//! assert_eq!(cursor.slice_from(start), "12");
+//!
+//! let err = cursor.expect(',').unwrap_err();
+//! assert_eq!(err.to_string(), "expected ',' but found ';' at line 1, column 11");
+//!
+//! assert_eq!(cursor.expect(';'), Ok(()));
+//! assert_eq!(cursor.peek(), Some('\n'));
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FusedIterator;
+
+#[cfg(feature = "alloc")]
+use crate::{scan, LineColByteRange};
+use crate::{LineColByte, Span, Spanned};
+
+/// Returns an iterator over the tokens produced by repeatedly calling
+/// `f` with a [`PosCursor`], until the end of `text`.
+///
+/// Each token is [`Spanned`] by the text consumed by the call to `f`
+/// producing it. If `f` returns `None`, e.g. after skipping whitespace
+/// or comments, then nothing is produced for that call.
+///
+/// Every call to `f` must consume at least one [`char`]. Otherwise a
+/// [`NoProgressError`] is produced and iteration ends, instead of
+/// looping forever.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::cursor::tokenize_with;
+/// use char_positions::LineCol;
+///
+/// #[derive(PartialEq, Debug)]
+/// enum Tok<'a> {
+///     Ident(&'a str),
+///     Punct(char),
+/// }
+///
+/// let text = "let 🦀 =\n  x;";
+///
+/// let toks = tokenize_with(text, |cursor| {
+///     let c = cursor.peek()?;
+///     if c.is_whitespace() {
+///         cursor.eat_while(char::is_whitespace);
+///         None
+///     } else if c.is_alphanumeric() || c == '🦀' {
+///         Some(Tok::Ident(cursor.eat_while(|c| c.is_alphanumeric() || c == '🦀')))
+///     } else {
+///         cursor.bump().map(Tok::Punct)
+///     }
+/// })
+/// .map(Result::unwrap)
+/// .map(|tok| (tok.span.start_line_col(), tok.value))
+/// .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     toks,
+///     [
+///         (LineCol(1, 1), Tok::Ident("let")),
+///         (LineCol(1, 5), Tok::Ident("🦀")),
+///         (LineCol(1, 7), Tok::Punct('=')),
+///         (LineCol(2, 3), Tok::Ident("x")),
+///         (LineCol(2, 4), Tok::Punct(';')),
+///     ],
+/// );
+///
+/// // Never consumes anything
+/// let mut toks = tokenize_with("x", |cursor| cursor.peek());
+/// assert!(toks.next().unwrap().is_err());
+/// assert!(toks.next().is_none());
+/// ```
+#[inline]
+pub fn tokenize_with<'a, T, F>(text: &'a str, f: F) -> Tokenize<'a, F>
+where
+    F: FnMut(&mut PosCursor<'a>) -> Option<T>,
+{
+    Tokenize {
+        cursor: PosCursor::new(text),
+        f,
+        done: false,
+    }
+}
+
+/// An iterator over [`Spanned`] tokens.
+///
+/// See [`tokenize_with()`].
+#[derive(Clone, Debug)]
+pub struct Tokenize<'a, F> {
+    cursor: PosCursor<'a>,
+    f: F,
+    done: bool,
+}
+
+impl<'a, F> Tokenize<'a, F> {
+    #[inline]
+    pub fn cursor(&self) -> &PosCursor<'a> {
+        &self.cursor
+    }
+}
+
+impl<'a, T, F> Iterator for Tokenize<'a, F>
+where
+    F: FnMut(&mut PosCursor<'a>) -> Option<T>,
+{
+    type Item = Result<Spanned<T>, NoProgressError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done && !self.cursor.is_eof() {
+            let start = self.cursor.start_span();
+            let tok = (self.f)(&mut self.cursor);
+            if self.cursor.position() == start {
+                self.done = true;
+                return Some(Err(NoProgressError { pos: start }));
+            }
+            if let Some(tok) = tok {
+                return Some(Ok(Spanned::new(tok, self.cursor.end_span(start))));
+            }
+        }
+        None
+    }
+}
+
+impl<'a, T, F> FusedIterator for Tokenize<'a, F> where F: FnMut(&mut PosCursor<'a>) -> Option<T> {}
+
+/// A cursor over a text, for hand written lexers.
+///
+/// See the [module](self) documentation.
+#[derive(Clone, Debug)]
+pub struct PosCursor<'a> {
+    text: &'a str,
+    /// The byte position of the start of `text`.
+    base: usize,
+    /// The position of the remaining text.
+    pos: LineColByte,
+}
+
+impl<'a> PosCursor<'a> {
+    #[inline]
+    pub const fn new(text: &'a str) -> Self {
+        Self::with_position(text, LineColByte(1, 1, 0))
+    }
+
+    /// Returns a cursor where `text` starts at `pos`, e.g. to resume
+    /// scanning the next chunk of a text, from the
+    /// [`position()`](Self::position) the previous chunk ended at.
+    ///
+    /// See also [`ScannerState`](crate::ScannerState), for chunks
+    /// which may split a [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::cursor::PosCursor;
+    /// use char_positions::LineColByte;
+    ///
+    /// let mut cursor = PosCursor::new("let\n");
+    /// cursor.eat_while(|_| true);
+    /// let pos = cursor.position();
+    ///
+    /// let mut cursor = PosCursor::with_position("🦀 = 1;", pos);
+    /// assert_eq!(cursor.bump(), Some('🦀'));
+    /// assert_eq!(cursor.position(), LineColByte(2, 2, 8));
+    /// ```
+    #[inline]
+    pub const fn with_position(text: &'a str, pos: LineColByte) -> Self {
+        Self {
+            text,
+            base: pos.2,
+            pos,
+        }
+    }
+
+    /// Returns the whole text, i.e. both the consumed and remaining text.
+    #[inline]
+    pub const fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the remaining text.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[(self.pos.2 - self.base)..]
+    }
+
+    /// Returns the position of the next [`char`].
+    #[inline]
+    pub const fn position(&self) -> LineColByte {
+        self.pos
+    }
+
+    /// Returns `true` if there is no remaining text.
+    #[inline]
+    pub fn is_eof(&self) -> bool {
+        self.pos.2 - self.base == self.text.len()
+    }
+
+    /// Returns the next [`char`] without consuming it.
+    #[inline]
+    pub fn peek(&self) -> Option<char> {
+        self.as_str().chars().next()
+    }
+
+    /// Returns the `n`th next [`char`] without consuming anything,
+    /// where `peek_nth(0)` is the same as [`peek()`](Self::peek).
+    #[inline]
+    pub fn peek_nth(&self, n: usize) -> Option<char> {
+        self.as_str().chars().nth(n)
+    }
+
+    /// Consumes and returns the next [`char`].
+    #[inline]
+    pub fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos.advance(c);
+        Some(c)
+    }
+
+    /// Consumes the next [`char`] if it is `c`.
+    #[inline]
+    pub fn eat(&mut self, c: char) -> bool {
+        let eaten = self.peek() == Some(c);
+        if eaten {
+            self.pos.advance(c);
+        }
+        eaten
+    }
+
+    /// Consumes the next [`char`] if it is `c`,
+    /// otherwise returns an error without consuming anything.
+    pub fn expect(&mut self, c: char) -> Result<(), ExpectError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(ExpectError {
+                expected: c,
+                found: self.peek(),
+                pos: self.pos,
+            })
+        }
+    }
+
+    /// Consumes [`char`]s while `pred` returns `true`,
+    /// and returns the consumed text.
+    pub fn eat_while(&mut self, mut pred: impl FnMut(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if !pred(c) {
+                break;
+            }
+            self.pos.advance(c);
+        }
+        self.slice_from(start)
+    }
+
+    /// Returns the current position, to be passed to
+    /// [`end_span()`](Self::end_span) after consuming a token.
+    #[inline]
+    pub const fn start_span(&self) -> LineColByte {
+        self.pos
+    }
+
+    /// Returns the [`Span`] from `start` to the current position.
+    #[inline]
+    pub const fn end_span(&self, start: LineColByte) -> Span {
+        Span::new(start, self.pos)
+    }
+
+    /// Returns the text from `start` to the current position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is after the current position, before
+    /// the start of the text, or not on a [`char`] boundary.
+    #[inline]
+    pub fn slice_from(&self, start: LineColByte) -> &'a str {
+        &self.text[(start.2 - self.base)..(self.pos.2 - self.base)]
+    }
+}
+
+/// An error returned by [`PosCursor::expect()`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct ExpectError {
+    expected: char,
+    found: Option<char>,
+    pos: LineColByte,
+}
+
+impl ExpectError {
+    #[inline]
+    pub fn expected(&self) -> char {
+        self.expected
+    }
+
+    /// Returns the [`char`] found instead,
+    /// or `None` at the end of the text.
+    #[inline]
+    pub fn found(&self) -> Option<char> {
+        self.found
+    }
+
+    #[inline]
+    pub fn position(&self) -> LineColByte {
+        self.pos
+    }
+}
+
+impl fmt::Display for ExpectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let LineColByte(line, col, _) = self.pos;
+        match self.found {
+            Some(found) => write!(f, "expected {:?} but found {found:?}", self.expected)?,
+            None => write!(f, "expected {:?} but found end of text", self.expected)?,
+        }
+        write!(f, " at line {line}, column {col}")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ExpectError {}
+
+/// An error returned by [`tokenize_with()`], when a call
+/// did not consume anything.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct NoProgressError {
+    pos: LineColByte,
+}
+
+impl NoProgressError {
+    /// Returns the position where no progress was made.
+    #[inline]
+    pub fn position(&self) -> LineColByte {
+        self.pos
+    }
+}
+
+impl fmt::Display for NoProgressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let LineColByte(line, col, _) = self.pos;
+        write!(f, "tokenizer made no progress at line {line}, column {col}")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NoProgressError {}
+
+/// A cursor which moves forwards and backwards one [`char`] at a time,
+/// keeping track of its line, column, and byte position.
+///
+/// The start byte position of every line up to the furthest line
+/// visited is cached, such that moving back across a line break does
+/// not need to search for the start of the previous line.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::cursor::PositionedCursor;
+/// use char_positions::{LineColByte, LineColByteRange};
+///
+/// let text = "f(🦀,\n  [x])";
+/// let mut cursor = PositionedCursor::at(text, text.find(']').unwrap()).unwrap();
+/// assert_eq!(cursor.position(), LineColByte(2, 5, 12));
+///
+/// // Find the opening parenthesis before the cursor
+/// let open = loop {
+///     match cursor.prev_char() {
+///         Some((pos, '(')) => break pos,
+///         Some(_) => {}
+///         None => unreachable!(),
+///     }
+/// };
+/// assert_eq!(open, LineColByteRange(1, 2, 1..2));
+///
+/// // Move back across the line break
+/// cursor.next_char();
+/// cursor.next_char();
+/// cursor.next_char();
+/// assert_eq!(cursor.next_char(), Some((LineColByteRange(1, 5, 7..8), '\n')));
+/// assert_eq!(cursor.prev_char(), Some((LineColByteRange(1, 5, 7..8), '\n')));
+/// assert_eq!(cursor.position(), LineColByte(1, 5, 7));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct PositionedCursor<'a> {
+    text: &'a str,
+    pos: LineColByte,
+    /// The start byte position of each line, up to the furthest line visited.
+    line_starts: Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> PositionedCursor<'a> {
+    /// Creates a cursor at the start of `text`.
+    #[inline]
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            pos: LineColByte(1, 1, 0),
+            line_starts: alloc::vec![0],
+        }
+    }
+
+    /// Creates a cursor at the byte position `offset` in `text`.
+    ///
+    /// Returns `None` if `offset` is out of bounds, or not on a
+    /// [`char`] boundary.
+    pub fn at(text: &'a str, offset: usize) -> Option<Self> {
+        let prefix = text.get(..offset)?;
+
+        let mut line_starts = alloc::vec![0];
+        line_starts.extend(scan::newlines(prefix).map(|i| i + 1));
+
+        let line_start = line_starts[line_starts.len() - 1];
+        let col = prefix[line_start..].chars().count() + 1;
+
+        Some(Self {
+            text,
+            pos: LineColByte(line_starts.len(), col, offset),
+            line_starts,
+        })
+    }
+
+    /// Returns the whole text.
+    #[inline]
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the position of the cursor, i.e. of the next [`char`].
+    #[inline]
+    pub fn position(&self) -> LineColByte {
+        self.pos
+    }
+
+    /// Returns the text after the cursor.
+    #[inline]
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos.2..]
+    }
+
+    /// Returns the text before the cursor.
+    #[inline]
+    pub fn before(&self) -> &'a str {
+        &self.text[..self.pos.2]
+    }
+
+    /// Returns the [`char`] after the cursor, without moving.
+    #[inline]
+    pub fn peek_next(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// Returns the [`char`] before the cursor, without moving.
+    #[inline]
+    pub fn peek_prev(&self) -> Option<char> {
+        self.before().chars().next_back()
+    }
+
+    /// Moves the cursor past the next [`char`], returning it
+    /// along with its position.
+    pub fn next_char(&mut self) -> Option<(LineColByteRange, char)> {
+        let c = self.peek_next()?;
+        let LineColByte(line, col, start) = self.pos;
+        let end = start + c.len_utf8();
+
+        if c == '\n' {
+            if self.line_starts.len() == line {
+                self.line_starts.push(end);
+            }
+            self.pos = LineColByte(line + 1, 1, end);
+        } else {
+            self.pos = LineColByte(line, col + 1, end);
+        }
+
+        Some((LineColByteRange(line, col, start..end), c))
+    }
+
+    /// Moves the cursor before the previous [`char`], returning it
+    /// along with its position.
+    pub fn prev_char(&mut self) -> Option<(LineColByteRange, char)> {
+        let c = self.peek_prev()?;
+        let LineColByte(line, col, end) = self.pos;
+        let start = end - c.len_utf8();
+
+        self.pos = if c == '\n' {
+            let line_start = self.line_starts[line - 2];
+            let col = self.text[line_start..start].chars().count() + 1;
+            LineColByte(line - 1, col, start)
+        } else {
+            LineColByte(line, col - 1, start)
+        };
+
+        let LineColByte(line, col, _) = self.pos;
+        Some((LineColByteRange(line, col, start..end), c))
+    }
+}
@@ -0,0 +1,281 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FusedIterator;
+
+use crate::utf8::{self, Decoded};
+use crate::LineColByte;
+
+/// A sans-io incremental UTF-8 decoder, producing [`char`]s and their
+/// positions from chunks of bytes pushed by the caller.
+///
+/// The line, column, and byte position persist across chunks, and `char`s
+/// split across chunks are handled transparently. For decoding from a
+/// [`Read`](std::io::Read), see [`CharPositionsReader`](crate::CharPositionsReader).
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{LineColByte, PushDecoder};
+///
+/// let mut decoder = PushDecoder::new();
+///
+/// // The chunk ends in the middle of the 🦀
+/// decoder.push_bytes(&"Hi 🦀".as_bytes()[..5]);
+/// let chars = decoder.drain().collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(chars.len(), 3);
+///
+/// decoder.push_bytes(&"Hi 🦀".as_bytes()[5..]);
+/// decoder.push_str("\nok");
+/// let chars = decoder.drain().collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(
+///     chars,
+///     [
+///         (LineColByte(1, 4, 3), '🦀'),
+///         (LineColByte(1, 5, 7), '\n'),
+///         (LineColByte(2, 1, 8), 'o'),
+///         (LineColByte(2, 2, 9), 'k'),
+///     ],
+/// );
+///
+/// // The input ends in the middle of a `char`
+/// decoder.push_bytes(&[0xF0, 0x9F]);
+/// assert_eq!(decoder.drain().next(), None);
+///
+/// let err = decoder.finish().unwrap_err();
+/// assert_eq!(err.position(), LineColByte(2, 3, 10));
+/// assert_eq!(err.invalid_len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PushDecoder {
+    buf: Vec<u8>,
+    /// The start of the undecoded bytes in `buf`.
+    start: usize,
+    pos: LineColByte,
+}
+
+impl PushDecoder {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            buf: Vec::new(),
+            start: 0,
+            pos: LineColByte(1, 1, 0),
+        }
+    }
+
+    /// Returns the position of the next [`char`].
+    #[inline]
+    pub fn position(&self) -> LineColByte {
+        self.pos
+    }
+
+    /// Pushes a chunk of UTF-8 encoded bytes, which may start
+    /// or end in the middle of a [`char`].
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        if self.start > 0 {
+            self.buf.drain(..self.start);
+            self.start = 0;
+        }
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Pushes a chunk of text.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        self.push_bytes(s.as_bytes());
+    }
+
+    /// Returns an iterator decoding the pushed [`char`]s and their positions.
+    ///
+    /// The iterator ends when all pushed bytes are decoded,
+    /// or at a trailing incomplete `char`.
+    #[inline]
+    pub fn drain(&mut self) -> DecodedChars<'_> {
+        DecodedChars { decoder: self }
+    }
+
+    /// Ends the input, returning an error if any bytes
+    /// remain undecoded, e.g. a trailing incomplete [`char`].
+    ///
+    /// The decoder should be [drained](Self::drain) first, as the remaining
+    /// bytes are discarded. The decoder can be reused afterwards, and
+    /// positions continue from where the input ended.
+    pub fn finish(&mut self) -> Result<(), Utf8Error> {
+        let n = self.buf.len() - self.start;
+        self.buf.clear();
+        self.start = 0;
+        if n == 0 {
+            return Ok(());
+        }
+        Err(self.skip_invalid(n))
+    }
+
+    /// Returns the state of the decoder, which can be persisted
+    /// and later resumed using [`from_state()`](Self::from_state),
+    /// e.g. to pause decoding at a chunk boundary.
+    ///
+    /// The decoder should be [drained](Self::drain) first. Returns `None`
+    /// if more undecoded bytes remain than a trailing incomplete [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineColByte, PushDecoder};
+    ///
+    /// let text = "Hi\n🦀!".as_bytes();
+    ///
+    /// let mut decoder = PushDecoder::new();
+    /// decoder.push_bytes(&text[..5]);
+    /// assert_eq!(decoder.drain().count(), 3);
+    ///
+    /// let state = decoder.state().unwrap();
+    /// assert_eq!(state.position(), LineColByte(2, 1, 3));
+    /// assert_eq!(state.pending_bytes(), &text[3..5]);
+    ///
+    /// let mut decoder = PushDecoder::from_state(state);
+    /// decoder.push_bytes(&text[5..]);
+    /// let chars = decoder.drain().collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert_eq!(chars, [(LineColByte(2, 1, 3), '🦀'), (LineColByte(2, 2, 7), '!')]);
+    /// ```
+    pub fn state(&self) -> Option<ScannerState> {
+        let undecoded = &self.buf[self.start..];
+        let mut pending = [0; 3];
+        pending
+            .get_mut(..undecoded.len())?
+            .copy_from_slice(undecoded);
+        Some(ScannerState {
+            pos: self.pos,
+            pending,
+            pending_len: undecoded.len() as u8,
+        })
+    }
+
+    /// Returns a decoder resuming from `state`.
+    ///
+    /// See [`state()`](Self::state).
+    pub fn from_state(state: ScannerState) -> Self {
+        let mut decoder = Self::new();
+        decoder.pos = state.pos;
+        decoder.push_bytes(state.pending_bytes());
+        decoder
+    }
+
+    /// Consumes `n` invalid bytes.
+    fn skip_invalid(&mut self, n: usize) -> Utf8Error {
+        let err = Utf8Error {
+            pos: self.pos,
+            len: n,
+        };
+        self.start += n;
+        self.pos.2 += n;
+        err
+    }
+}
+
+impl Default for PushDecoder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An iterator over [`char`]s and their positions, decoded by a [`PushDecoder`].
+///
+/// See [`PushDecoder::drain()`].
+#[derive(Debug)]
+pub struct DecodedChars<'a> {
+    decoder: &'a mut PushDecoder,
+}
+
+impl Iterator for DecodedChars<'_> {
+    type Item = Result<(LineColByte, char), Utf8Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let decoder = &mut *self.decoder;
+        match utf8::decode(&decoder.buf[decoder.start..]) {
+            Decoded::Char(c) => {
+                let pos = decoder.pos;
+                decoder.start += c.len_utf8();
+                decoder.pos.advance(c);
+                Some(Ok((pos, c)))
+            }
+            Decoded::Invalid(n) => Some(Err(decoder.skip_invalid(n))),
+            Decoded::Incomplete => None,
+        }
+    }
+}
+
+impl FusedIterator for DecodedChars<'_> {}
+
+/// The persistable state of a [`PushDecoder`], i.e. the position of the
+/// next [`char`], and the bytes of a trailing incomplete [`char`], if any.
+///
+/// See [`PushDecoder::state()`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct ScannerState {
+    pos: LineColByte,
+    pending: [u8; 3],
+    pending_len: u8,
+}
+
+impl ScannerState {
+    /// Returns the state of a decoder, which has not decoded anything yet,
+    /// and where the next [`char`] is at `pos`.
+    #[inline]
+    pub const fn new(pos: LineColByte) -> Self {
+        Self {
+            pos,
+            pending: [0; 3],
+            pending_len: 0,
+        }
+    }
+
+    /// Returns the position of the next [`char`].
+    #[inline]
+    pub const fn position(&self) -> LineColByte {
+        self.pos
+    }
+
+    /// Returns the bytes of a trailing incomplete [`char`].
+    #[inline]
+    pub fn pending_bytes(&self) -> &[u8] {
+        let len = usize::from(self.pending_len).min(self.pending.len());
+        &self.pending[..len]
+    }
+}
+
+/// An invalid or incomplete UTF-8 sequence.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Utf8Error {
+    pos: LineColByte,
+    len: usize,
+}
+
+impl Utf8Error {
+    /// Returns the position of the invalid bytes.
+    #[inline]
+    pub fn position(&self) -> LineColByte {
+        self.pos
+    }
+
+    /// Returns the number of invalid bytes.
+    #[inline]
+    pub fn invalid_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl fmt::Display for Utf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let LineColByte(line, col, byte) = self.pos;
+        write!(
+            f,
+            "invalid UTF-8 sequence of {} bytes at line {line}, column {col} (byte {byte})",
+            self.len,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Utf8Error {}
@@ -0,0 +1,163 @@
+use alloc::string::String;
+use core::ops::Range;
+
+use crate::{Bias, LineCol, LineIndex, PositionMapper};
+
+/// `TextEdit { range, new_text }`
+///
+/// Replaces the text in the byte `range` with `new_text`.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{Bias, TextEdit};
+///
+/// // "Hello World" -> "Hello Rust World"
+/// let edit = TextEdit::insert(6, "Rust ");
+///
+/// assert_eq!(edit.map(2, Bias::Left), 2);
+/// assert_eq!(edit.map(6, Bias::Left), 6);
+/// assert_eq!(edit.map(6, Bias::Right), 11);
+/// assert_eq!(edit.map(8, Bias::Left), 13);
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct TextEdit {
+    /// The start (inclusive) and end (exclusive) byte positions
+    /// of the replaced text.
+    pub range: Range<usize>,
+    /// The text inserted in place of `range`.
+    pub new_text: String,
+}
+
+impl TextEdit {
+    #[inline]
+    pub fn new(range: Range<usize>, new_text: impl Into<String>) -> Self {
+        Self {
+            range,
+            new_text: new_text.into(),
+        }
+    }
+
+    /// Inserts `text` at the byte position `at`.
+    #[inline]
+    pub fn insert(at: usize, text: impl Into<String>) -> Self {
+        Self::new(at..at, text)
+    }
+
+    /// Deletes the text in the byte `range`.
+    #[inline]
+    pub fn delete(range: Range<usize>) -> Self {
+        Self::new(range, String::new())
+    }
+
+    /// Returns the end (exclusive) byte position of `new_text`
+    /// after the edit has been applied.
+    #[inline]
+    pub fn new_end(&self) -> usize {
+        self.range.start + self.new_text.len()
+    }
+
+    /// Maps the byte position `byte` from before the edit was
+    /// applied, to the corresponding byte position after.
+    ///
+    /// Positions before `range` are unchanged, and positions after
+    /// are shifted by the change in length. Positions at the start of
+    /// or within `range` move to the start of `new_text` if `bias` is
+    /// [`Bias::Left`], and to the end of `new_text` if [`Bias::Right`].
+    pub fn map(&self, byte: usize, bias: Bias) -> usize {
+        let Range { start, end } = self.range;
+        if byte < start {
+            byte
+        } else if (byte > end) || ((byte == end) && (start != end)) {
+            byte - (end - start) + self.new_text.len()
+        } else {
+            match bias {
+                Bias::Left => start,
+                Bias::Right => self.new_end(),
+            }
+        }
+    }
+}
+
+/// Maps the byte position `byte` through `edits`, like [`TextEdit::map()`].
+///
+/// `edits` must be sorted, non-overlapping, and relative to the text
+/// before any of them are applied.
+pub fn map_through_edits(edits: &[TextEdit], byte: usize, bias: Bias) -> usize {
+    edits
+        .iter()
+        .rev()
+        .fold(byte, |byte, edit| edit.map(byte, bias))
+}
+
+/// Applies `edits` to `text`, returning the edited text along with a
+/// [`PositionMapper`] for mapping positions between `text` and the
+/// edited text.
+///
+/// `edits` must be sorted, non-overlapping, and relative to `text`.
+///
+/// See [`PositionMapper`] for an example.
+///
+/// # Panics
+///
+/// Panics if `edits` are not sorted, overlap, or if any range is out
+/// of bounds or not on [`char`] boundaries.
+pub fn apply_edits(text: &str, edits: &[TextEdit]) -> (String, PositionMapper) {
+    let mut new = String::with_capacity(text.len());
+    let mut mapper = PositionMapper::identity();
+
+    let mut last = 0;
+    for edit in edits {
+        let Range { start, end } = edit.range;
+        assert!(
+            (last <= start) && (start <= end),
+            "edits must be sorted and non-overlapping"
+        );
+
+        new.push_str(&text[last..start]);
+        let new_start = new.len();
+        new.push_str(&edit.new_text);
+        mapper.push(start..end, new_start..new.len());
+
+        last = end;
+    }
+    new.push_str(&text[last..]);
+
+    (new, mapper)
+}
+
+/// Returns the earliest byte position and its line and column, from where a
+/// positioned scan, e.g. a lexer, can safely resume after `edit` is applied.
+///
+/// This is the start of the line containing the start of `edit`. Everything
+/// before it is unaffected by `edit`, so the byte position and line and column
+/// are the same in the text before and after `edit`. Lexers with tokens
+/// spanning multiple lines, e.g. block comments, may need to resume from an
+/// earlier token.
+///
+/// `old_index` must be the [`LineIndex`] of the text before `edit` is applied.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{resume_after_edit, LineCol, LineIndex, TextEdit};
+///
+/// let text = "let a = 1;\nlet b = 2;\n";
+/// let index = LineIndex::new(text);
+///
+/// // "let b = 2;" -> "let b = 42;"
+/// let edit = TextEdit::new(19..20, "42");
+///
+/// assert_eq!(resume_after_edit(&index, &edit), (11, LineCol(2, 1)));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `edit` starts out of bounds.
+pub fn resume_after_edit(old_index: &LineIndex, edit: &TextEdit) -> (usize, LineCol) {
+    let line = old_index
+        .line_of(edit.range.start)
+        .expect("edit starts out of bounds");
+    let start = old_index.line_starts()[line - 1];
+    (start, LineCol(line, 1))
+}
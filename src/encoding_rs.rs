@@ -0,0 +1,173 @@
+//! Integration with [`encoding_rs`], enabled by the `encoding_rs` feature.
+//!
+//! Allows iterating the [`char`]s of text in legacy encodings, e.g. Shift_JIS
+//! or UTF-16LE, with byte positions in the original encoded bytes, instead of
+//! positions in the decoded UTF-8 text.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{encoding_rs::char_positions, LineColByteRange};
+//! use encoding_rs::{SHIFT_JIS, UTF_16LE};
+//!
+//! // "日本\nA" encoded as Shift_JIS
+//! let bytes = [0x93, 0xFA, 0x96, 0x7B, 0x0A, 0x41];
+//!
+//! let mut iter = char_positions::<LineColByteRange>(SHIFT_JIS, &bytes)
+//!     .map(|(LineColByteRange(line, col, range), c)| (line, col, range, c));
+//!
+//! assert_eq!(iter.next(), Some((1, 1, 0..2, '日')));
+//! assert_eq!(iter.next(), Some((1, 2, 2..4, '本')));
+//! assert_eq!(iter.next(), Some((1, 3, 4..5, '\n')));
+//! assert_eq!(iter.next(), Some((2, 1, 5..6, 'A')));
+//! assert_eq!(iter.next(), None);
+//!
+//! // "a🦀" encoded as UTF-16LE
+//! let bytes = [0x61, 0x00, 0x3E, 0xD8, 0x80, 0xDD];
+//!
+//! let mut iter = char_positions::<LineColByteRange>(UTF_16LE, &bytes)
+//!     .map(|(LineColByteRange(line, col, range), c)| (line, col, range, c));
+//!
+//! assert_eq!(iter.next(), Some((1, 1, 0..2, 'a')));
+//! assert_eq!(iter.next(), Some((1, 2, 2..6, '🦀')));
+//! assert_eq!(iter.next(), None);
+//!
+//! // "a" encoded as UTF-16LE with a BOM, which overrides the encoding
+//! let bytes = [0xFF, 0xFE, 0x61, 0x00];
+//!
+//! let mut iter = char_positions::<LineColByteRange>(SHIFT_JIS, &bytes)
+//!     .map(|(LineColByteRange(line, col, range), c)| (line, col, range, c));
+//!
+//! assert_eq!(iter.next(), Some((1, 1, 2..4, 'a')));
+//! assert_eq!(iter.next(), None);
+//! ```
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use std::string::String;
+
+use ::encoding_rs::{Decoder, Encoding};
+
+use crate::{LineCol, LineColByteRange};
+
+/// Returns an iterator over the [`char`]s of `bytes` decoded from `encoding`,
+/// and their positions.
+///
+/// The byte positions are in `bytes`, i.e. in the original encoding.
+///
+/// Like [`Encoding::decode()`], a BOM overrides `encoding`, and malformed
+/// sequences are decoded as `'\u{FFFD}'`. The BOM itself is not produced,
+/// and the byte range of the first `char` starts after it.
+/// If a byte completes multiple `char`s at once, e.g. a `'\u{FFFD}'` followed
+/// by a valid `char`, then they share the same byte range.
+///
+/// See [`CharPositionsExt::char_positions()`](crate::CharPositionsExt::char_positions).
+#[inline]
+pub fn char_positions<'a, T>(
+    encoding: &'static Encoding,
+    bytes: &'a [u8],
+) -> EncodedCharPositions<'a, T>
+where
+    LineColByteRange: Into<T>,
+{
+    // Sniff the BOM up front, such that its bytes are
+    // not included in the byte range of the first `char`
+    let (encoding, bom_len) = Encoding::for_bom(bytes).unwrap_or((encoding, 0));
+    EncodedCharPositions {
+        bytes,
+        decoder: encoding.new_decoder_without_bom_handling(),
+        decoded: String::new(),
+        decoded_pos: 0,
+        start: bom_len,
+        end: bom_len,
+        finished: false,
+        pos: LineCol::START,
+        phantom: PhantomData,
+    }
+}
+
+/// An iterator over the [`char`]s of encoded bytes and their positions.
+///
+/// See [`char_positions()`].
+pub struct EncodedCharPositions<'a, T> {
+    bytes: &'a [u8],
+    decoder: Decoder,
+    /// The `char`s decoded from `bytes[start..end]`.
+    decoded: String,
+    /// The byte position of the next `char` in `decoded`.
+    decoded_pos: usize,
+    /// The start byte position of `decoded` in `bytes`.
+    start: usize,
+    /// The end byte position of `decoded` in `bytes`.
+    end: usize,
+    finished: bool,
+    pos: LineCol,
+    phantom: PhantomData<T>,
+}
+
+impl<T> EncodedCharPositions<'_, T> {
+    /// Returns the encoding being decoded, which may
+    /// differ from the initial encoding due to a BOM.
+    #[inline]
+    pub fn encoding(&self) -> &'static Encoding {
+        self.decoder.encoding()
+    }
+
+    /// Decodes bytes until at least one `char` is decoded.
+    fn decode_next(&mut self) -> bool {
+        self.decoded.clear();
+        self.decoded_pos = 0;
+        self.start = self.end;
+
+        while self.decoded.is_empty() && !self.finished {
+            // Feed a single byte at a time, to know
+            // which bytes each `char` was decoded from
+            let (src, last) = match self.bytes.get(self.end) {
+                Some(b) => (core::slice::from_ref(b), false),
+                None => (&[][..], true),
+            };
+            if let Some(n) = self.decoder.max_utf8_buffer_length(src.len()) {
+                self.decoded.reserve(n);
+            }
+
+            let (_, read, _) = self.decoder.decode_to_string(src, &mut self.decoded, last);
+            self.end += read;
+            self.finished = last;
+        }
+
+        !self.decoded.is_empty()
+    }
+}
+
+impl<T> Iterator for EncodedCharPositions<'_, T>
+where
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.decoded_pos == self.decoded.len() && !self.decode_next() {
+            return None;
+        }
+
+        let c = self.decoded[self.decoded_pos..].chars().next()?;
+        self.decoded_pos += c.len_utf8();
+
+        let pos = LineColByteRange(self.pos.0, self.pos.1, self.start..self.end);
+        self.pos.advance(c);
+        Some((pos.into(), c))
+    }
+}
+
+impl<T> FusedIterator for EncodedCharPositions<'_, T> where Self: Iterator {}
+
+impl<T> fmt::Debug for EncodedCharPositions<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncodedCharPositions")
+            .field("encoding", &self.encoding().name())
+            .field("remaining", &&self.bytes[self.end..])
+            .field("pos", &self.pos)
+            .finish_non_exhaustive()
+    }
+}
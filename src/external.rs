@@ -0,0 +1,124 @@
+use crate::{scan, ColumnUnit, LineColByte};
+
+/// Whether lines and columns start at 0 or 1.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Indexing {
+    /// Lines and columns start at 0, e.g. the Language Server Protocol.
+    ZeroBased,
+    /// Lines and columns start at 1, e.g. this crate and most compilers.
+    OneBased,
+}
+
+pub trait ExternalPositionsExt {
+    /// Returns the byte position of a line and column reported by
+    /// another library or tool, e.g. the location of a parse error,
+    /// with the given `indexing`, and the column counted in `unit`s.
+    ///
+    /// The column may point at the line break ending the line, or one
+    /// past the last [`char`] of the last line, i.e. the end of the text.
+    ///
+    /// Returns `None` if the line or column is out of bounds,
+    /// or if the column is within a [`char`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{ColumnUnit, ExternalPositionsExt, Indexing};
+    ///
+    /// let text = "{\n  \"🦀\": tru\n}";
+    ///
+    /// // 1-indexed, with the column counted in bytes
+    /// let byte = text.offset_of_external(2, 11, Indexing::OneBased, ColumnUnit::Utf8);
+    /// assert_eq!(byte, Some(12));
+    ///
+    /// // 0-indexed, with the column counted in UTF-16 code units
+    /// let byte = text.offset_of_external(1, 8, Indexing::ZeroBased, ColumnUnit::Utf16);
+    /// assert_eq!(byte, Some(12));
+    ///
+    /// // Within the 🦀
+    /// let byte = text.offset_of_external(2, 5, Indexing::OneBased, ColumnUnit::Utf8);
+    /// assert_eq!(byte, None);
+    /// ```
+    fn offset_of_external(
+        &self,
+        line: usize,
+        col: usize,
+        indexing: Indexing,
+        unit: ColumnUnit,
+    ) -> Option<usize>;
+
+    /// Returns the position of a line and column reported by another
+    /// library or tool, with the column converted to [`char`]s.
+    ///
+    /// See [`offset_of_external()`](ExternalPositionsExt::offset_of_external).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{ColumnUnit, ExternalPositionsExt, Indexing, LineColByte};
+    ///
+    /// let text = "{\n  \"🦀\": tru\n}";
+    ///
+    /// let pos = text.position_of_external(2, 11, Indexing::OneBased, ColumnUnit::Utf8);
+    /// assert_eq!(pos, Some(LineColByte(2, 8, 12)));
+    /// ```
+    fn position_of_external(
+        &self,
+        line: usize,
+        col: usize,
+        indexing: Indexing,
+        unit: ColumnUnit,
+    ) -> Option<LineColByte>;
+}
+
+impl ExternalPositionsExt for str {
+    fn offset_of_external(
+        &self,
+        line: usize,
+        col: usize,
+        indexing: Indexing,
+        unit: ColumnUnit,
+    ) -> Option<usize> {
+        let (line, col) = match indexing {
+            Indexing::ZeroBased => (line, col),
+            Indexing::OneBased => (line.checked_sub(1)?, col.checked_sub(1)?),
+        };
+
+        let start = if line == 0 {
+            0
+        } else {
+            scan::newlines(self).nth(line - 1)? + 1
+        };
+        let rest = &self[start..];
+        let line = &rest[..scan::newlines(rest).next().map_or(rest.len(), |i| i + 1)];
+
+        let mut units = 0;
+        for (i, c) in line.char_indices() {
+            if units == col {
+                return Some(start + i);
+            } else if units > col {
+                return None;
+            }
+            units += unit.len_of(c);
+        }
+
+        (!line.ends_with('\n') && (units == col)).then_some(start + line.len())
+    }
+
+    fn position_of_external(
+        &self,
+        line: usize,
+        col: usize,
+        indexing: Indexing,
+        unit: ColumnUnit,
+    ) -> Option<LineColByte> {
+        let byte = self.offset_of_external(line, col, indexing, unit)?;
+        let line = match indexing {
+            Indexing::ZeroBased => line + 1,
+            Indexing::OneBased => line,
+        };
+        let line_start = scan::rfind_newline(&self[..byte]).map_or(0, |i| i + 1);
+        let col = self[line_start..byte].chars().count() + 1;
+        Some(LineColByte(line, col, byte))
+    }
+}
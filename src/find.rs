@@ -0,0 +1,135 @@
+use core::iter::FusedIterator;
+
+use crate::{LineColByteRange, Span, SpanResolver};
+
+pub trait FindPositionsExt {
+    /// Returns an iterator over the positions of every occurrence of `c`.
+    ///
+    /// ASCII needles are searched for at the byte level, using [memchr]
+    /// if the `memchr` feature is enabled. Other needles fall back to a
+    /// regular search.
+    ///
+    /// [memchr]: https://docs.rs/memchr
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{FindPositionsExt, LineColByteRange};
+    ///
+    /// let text = "fn main() {\n\tlet 🦀 = 1;\n\t\tdbg!(🦀);\n}";
+    ///
+    /// let tabs = text.char_occurrences('\t').collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     tabs,
+    ///     [
+    ///         LineColByteRange(2, 1, 12..13),
+    ///         LineColByteRange(3, 1, 27..28),
+    ///         LineColByteRange(3, 2, 28..29),
+    ///     ],
+    /// );
+    ///
+    /// let crabs = text.char_occurrences('🦀').collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     crabs,
+    ///     [LineColByteRange(2, 6, 17..21), LineColByteRange(3, 8, 34..38)],
+    /// );
+    /// ```
+    fn char_occurrences(&self, c: char) -> CharOccurrences<'_>;
+
+    /// Returns the [`Span`] of the first occurrence of `needle`,
+    /// ignoring ASCII case.
+    ///
+    /// The span covers the original text, not the case folded text.
+    /// For Unicode case folding, see [`caseless`](https://docs.rs/char-positions/*/char_positions/caseless/index.html),
+    /// which requires the `caseless` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{FindPositionsExt, LineCol};
+    ///
+    /// let text = "// 🦀\n// todo: Fix\n// TODO: Test";
+    ///
+    /// let span = text.find_position_ignore_ascii_case("TODO").unwrap();
+    /// assert_eq!(&text[span.byte_range()], "todo");
+    /// assert_eq!(span.start_line_col(), LineCol(2, 4));
+    /// assert_eq!(span.end_line_col(), LineCol(2, 8));
+    /// ```
+    fn find_position_ignore_ascii_case(&self, needle: &str) -> Option<Span>;
+}
+
+impl FindPositionsExt for str {
+    #[inline]
+    fn char_occurrences(&self, c: char) -> CharOccurrences<'_> {
+        CharOccurrences {
+            needle: c,
+            offset: 0,
+            resolver: SpanResolver::new(self),
+        }
+    }
+
+    fn find_position_ignore_ascii_case(&self, needle: &str) -> Option<Span> {
+        let (text, needle) = (self.as_bytes(), needle.as_bytes());
+        // Non-ASCII bytes must match exactly, so a match
+        // starting on a char boundary also ends on one
+        let start = self
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([self.len()])
+            .find(|&i| {
+                text.get(i..(i + needle.len()))
+                    .is_some_and(|s| s.eq_ignore_ascii_case(needle))
+            })?;
+        SpanResolver::new(self).span(start..(start + needle.len()))
+    }
+}
+
+/// An iterator over the positions of every occurrence of a [`char`].
+///
+/// See [`FindPositionsExt::char_occurrences()`].
+#[derive(Clone, Debug)]
+pub struct CharOccurrences<'a> {
+    needle: char,
+    /// The byte position to continue searching from.
+    offset: usize,
+    resolver: SpanResolver<'a>,
+}
+
+impl CharOccurrences<'_> {
+    fn find_next(&self) -> Option<usize> {
+        let haystack = &self.resolver.text()[self.offset..];
+        let i = if self.needle.is_ascii() {
+            find_byte(self.needle as u8, haystack.as_bytes())?
+        } else {
+            haystack.find(self.needle)?
+        };
+        Some(self.offset + i)
+    }
+}
+
+impl Iterator for CharOccurrences<'_> {
+    type Item = LineColByteRange;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.find_next()?;
+        let end = start + self.needle.len_utf8();
+        self.offset = end;
+
+        let pos = self.resolver.position(start)?;
+        Some(LineColByteRange(pos.0, pos.1, start..end))
+    }
+}
+
+impl FusedIterator for CharOccurrences<'_> {}
+
+#[cfg(feature = "memchr")]
+#[inline]
+fn find_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+    ::memchr::memchr(needle, haystack)
+}
+
+#[cfg(not(feature = "memchr"))]
+#[inline]
+fn find_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|&b| b == needle)
+}
@@ -0,0 +1,158 @@
+//! Formatting of GitHub Actions [workflow commands], which annotate files
+//! with errors, warnings, and notices in CI.
+//!
+//! [workflow commands]: https://docs.github.com/en/actions/writing-workflows/choosing-what-your-workflow-does/workflow-commands-for-github-actions
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::github::Annotation;
+//! use char_positions::SpanResolver;
+//!
+//! let text = "fn main() {\n    let x = 🦀;\n}\n";
+//! let span = SpanResolver::new(text).span(20..21).unwrap();
+//!
+//! let annotation = Annotation::warning("src/main.rs", span, "unused variable: `x`");
+//! assert_eq!(
+//!     annotation.to_string(),
+//!     "::warning file=src/main.rs,line=2,col=9,endLine=2,endColumn=9::unused variable: `x`",
+//! );
+//! ```
+
+use core::fmt;
+
+use crate::Span;
+
+/// The level of an [`Annotation`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Level {
+    Error,
+    Warning,
+    Notice,
+}
+
+impl Level {
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Notice => "notice",
+        }
+    }
+}
+
+/// A workflow command annotating a [`Span`] of a file, which can be
+/// formatted using [`Display`](fmt::Display), e.g.
+/// `::error file=...,line=...,col=...,endLine=...,endColumn=...::message`.
+///
+/// Columns are 1-indexed and counted in [`char`]s, with `endColumn` being
+/// the column of the last `char` of the span, i.e. inclusive. GitHub only
+/// supports columns for single line annotations, so they are omitted for
+/// spans spanning multiple lines. A span ending with a `'\n'` ends on the
+/// line of the `'\n'`, and also omits the columns, as the column of the
+/// `'\n'` is not known from the span alone.
+///
+/// Special characters in the file, title, and message are escaped.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Annotation<'a> {
+    level: Level,
+    file: &'a str,
+    span: Span,
+    title: Option<&'a str>,
+    message: &'a str,
+}
+
+impl<'a> Annotation<'a> {
+    #[inline]
+    pub const fn new(level: Level, file: &'a str, span: Span, message: &'a str) -> Self {
+        Self {
+            level,
+            file,
+            span,
+            title: None,
+            message,
+        }
+    }
+
+    #[inline]
+    pub const fn error(file: &'a str, span: Span, message: &'a str) -> Self {
+        Self::new(Level::Error, file, span, message)
+    }
+
+    #[inline]
+    pub const fn warning(file: &'a str, span: Span, message: &'a str) -> Self {
+        Self::new(Level::Warning, file, span, message)
+    }
+
+    #[inline]
+    pub const fn notice(file: &'a str, span: Span, message: &'a str) -> Self {
+        Self::new(Level::Notice, file, span, message)
+    }
+
+    /// Sets the title of the annotation.
+    #[inline]
+    pub const fn title(mut self, title: &'a str) -> Self {
+        self.title = Some(title);
+        self
+    }
+
+    /// Returns the lines and columns of the first and last [`char`]
+    /// of the span, both inclusive.
+    fn first_last(&self) -> ((usize, usize), (usize, usize)) {
+        let Span { start, end } = self.span;
+        let first = (start.0, start.1);
+        let last = if self.span.is_empty() {
+            first
+        } else if end.1 > 1 {
+            (end.0, end.1 - 1)
+        } else {
+            // The span ends with a `'\n'`, whose column is unknown
+            (end.0 - 1, 0)
+        };
+        (first, last)
+    }
+}
+
+impl fmt::Display for Annotation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (first, last) = self.first_last();
+
+        write!(f, "::{} file=", self.level.as_str())?;
+        write_escaped(f, self.file, true)?;
+        write!(f, ",line={}", first.0)?;
+        if (first.0 == last.0) && (last.1 > 0) {
+            write!(
+                f,
+                ",col={},endLine={},endColumn={}",
+                first.1, last.0, last.1
+            )?;
+        } else {
+            write!(f, ",endLine={}", last.0)?;
+        }
+        if let Some(title) = self.title {
+            f.write_str(",title=")?;
+            write_escaped(f, title, true)?;
+        }
+        f.write_str("::")?;
+        write_escaped(f, self.message, false)
+    }
+}
+
+/// Writes `s` escaped as the value of a property if `property`,
+/// otherwise as the message.
+fn write_escaped(f: &mut fmt::Formatter<'_>, s: &str, property: bool) -> fmt::Result {
+    use fmt::Write;
+
+    for c in s.chars() {
+        match c {
+            '%' => f.write_str("%25")?,
+            '\r' => f.write_str("%0D")?,
+            '\n' => f.write_str("%0A")?,
+            ':' if property => f.write_str("%3A")?,
+            ',' if property => f.write_str("%2C")?,
+            c => f.write_char(c)?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,144 @@
+//! Export of source code to HTML with position anchors, enabled by the `html` feature.
+//!
+//! Positions are attached as `data-*` attributes, such that scripts
+//! and stylesheets can link positions, e.g. of diagnostics, to the
+//! rendered source code:
+//!
+//! ```html
+//! <span data-line="2" data-col="5" data-byte-start="16">🦀</span>
+//! ```
+//!
+//! Lines and columns are 1-indexed, with columns counted in [`char`]s.
+//! The output is an HTML fragment, where all text is escaped, and
+//! line breaks are kept as is. Wrap it in a `<pre>` to preserve
+//! the whitespace.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::html::chars_to_html;
+//!
+//! assert_eq!(
+//!     chars_to_html("a<\n🦀"),
+//!     "<span data-line=\"1\" data-col=\"1\" data-byte-start=\"0\">a</span>\
+//!      <span data-line=\"1\" data-col=\"2\" data-byte-start=\"1\">&lt;</span>\
+//!      <span data-line=\"1\" data-col=\"3\" data-byte-start=\"2\">\n</span>\
+//!      <span data-line=\"2\" data-col=\"1\" data-byte-start=\"3\">🦀</span>",
+//! );
+//! ```
+
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::{CharPositionsExt, LineColByte, Span};
+
+/// Renders `text` to HTML, with each [`char`] wrapped in a `<span>`
+/// with its position.
+///
+/// See the [module](self) documentation for the layout.
+pub fn chars_to_html(text: &str) -> String {
+    let mut out = String::new();
+    _ = write_chars_html(&mut out, text);
+    out
+}
+
+/// Writes `text` as HTML to `w`, with each [`char`] wrapped in
+/// a `<span>` with its position.
+///
+/// See the [module](self) documentation for the layout.
+pub fn write_chars_html(w: &mut impl Write, text: &str) -> fmt::Result {
+    for (pos, c) in text.char_positions::<LineColByte>() {
+        write_open(w, pos, None)?;
+        write_escaped(w, c.encode_utf8(&mut [0; 4]))?;
+        w.write_str("</span>")?;
+    }
+    Ok(())
+}
+
+/// Renders `text` to HTML, with the text of each of the `spans` wrapped
+/// in a `<span>` with its start position and `data-byte-end`. Text outside
+/// of `spans` is only escaped.
+///
+/// `spans` must be sorted by their start. Spans overlapping a previous
+/// span are skipped, as HTML elements cannot overlap.
+///
+/// # Panics
+///
+/// Panics if a span is not within `text`, or not on [`char`] boundaries.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::html::spans_to_html;
+/// use char_positions::SpanResolver;
+///
+/// let text = "let x = \"🦀\";";
+/// let mut resolver = SpanResolver::new(text);
+/// let spans = [resolver.span(4..5).unwrap(), resolver.span(8..14).unwrap()];
+///
+/// assert_eq!(
+///     spans_to_html(text, &spans),
+///     "let <span data-line=\"1\" data-col=\"5\" data-byte-start=\"4\" data-byte-end=\"5\">x</span> = \
+///      <span data-line=\"1\" data-col=\"9\" data-byte-start=\"8\" data-byte-end=\"14\">&quot;🦀&quot;</span>;",
+/// );
+/// ```
+pub fn spans_to_html(text: &str, spans: &[Span]) -> String {
+    let mut out = String::new();
+    _ = write_spans_html(&mut out, text, spans);
+    out
+}
+
+/// Writes `text` as HTML to `w`, with the text of each of the `spans`
+/// wrapped in a `<span>`.
+///
+/// See [`spans_to_html()`].
+pub fn write_spans_html(w: &mut impl Write, text: &str, spans: &[Span]) -> fmt::Result {
+    let mut last = 0;
+    for span in spans {
+        if span.byte_start() < last {
+            continue;
+        }
+
+        write_escaped(w, &text[last..span.byte_start()])?;
+
+        write_open(w, span.start, Some(span.byte_end()))?;
+        write_escaped(w, &text[span.byte_range()])?;
+        w.write_str("</span>")?;
+
+        last = span.byte_end();
+    }
+    write_escaped(w, &text[last..])
+}
+
+fn write_open(
+    w: &mut impl Write,
+    LineColByte(line, col, byte): LineColByte,
+    byte_end: Option<usize>,
+) -> fmt::Result {
+    write!(
+        w,
+        "<span data-line=\"{line}\" data-col=\"{col}\" data-byte-start=\"{byte}\""
+    )?;
+    if let Some(byte_end) = byte_end {
+        write!(w, " data-byte-end=\"{byte_end}\"")?;
+    }
+    w.write_char('>')
+}
+
+fn write_escaped(w: &mut impl Write, s: &str) -> fmt::Result {
+    let mut last = 0;
+    for (i, c) in s.char_indices() {
+        let escaped = match c {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            '"' => "&quot;",
+            '\'' => "&#39;",
+            _ => continue,
+        };
+        w.write_str(&s[last..i])?;
+        w.write_str(escaped)?;
+        last = i + 1;
+    }
+    w.write_str(&s[last..])
+}
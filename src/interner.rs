@@ -0,0 +1,138 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{FileId, FileSpan, Span};
+
+/// Identifies a [`FileSpan`] interned in a [`SpanInterner`].
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct SpanId(u32);
+
+impl SpanId {
+    /// Returns the 0-indexed position of the span in its [`SpanInterner`],
+    /// i.e. the number of distinct spans interned before it.
+    #[inline]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Deduplicates [`FileSpan`]s into compact [`SpanId`]s, e.g. for ASTs
+/// where most spans repeat, such as generated or macro-expanded code.
+///
+/// Interning is `O(log n)`, and resolving a [`SpanId`] is `O(1)`.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{SourceFile, SourceMap, SpanInterner};
+///
+/// let mut map = SourceMap::new();
+/// map.add(SourceFile::new("a.rs", "let x = 1;\n"));
+/// map.add(SourceFile::new("b.rs", "let 🦀 = x;\n"));
+///
+/// let mut interner = SpanInterner::new();
+/// let x1 = interner.intern(map.span(4..5).unwrap());
+/// let crab = interner.intern(map.span(16..20).unwrap());
+/// let x2 = interner.intern(map.span(4..5).unwrap());
+///
+/// assert_eq!(x1, x2);
+/// assert_ne!(x1, crab);
+/// assert_eq!(interner.len(), 2);
+///
+/// assert_eq!(interner.resolve(crab), map.span(16..20).unwrap());
+/// assert_eq!(map.snippet(&interner.resolve(crab)), Some("🦀"));
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct SpanInterner {
+    spans: Vec<FileSpan>,
+    ids: BTreeMap<(FileId, Span), SpanId>,
+}
+
+impl SpanInterner {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            spans: Vec::new(),
+            ids: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the [`SpanId`] of `span`, interning it if needed.
+    ///
+    /// Spans are only deduplicated if they are equal, i.e. spans with the
+    /// same byte positions, but different lines or columns, are interned
+    /// separately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`u32::MAX`] distinct spans are interned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{FileSpan, LineColByte, SourceFile, SourceMap, Span, SpanInterner};
+    ///
+    /// let mut map = SourceMap::new();
+    /// let file = map.add(SourceFile::new("a.rs", "x"));
+    ///
+    /// let a = FileSpan { file, span: Span::new(LineColByte(1, 1, 0), LineColByte(1, 2, 1)) };
+    /// let b = FileSpan { file, span: Span::new(LineColByte(2, 1, 0), LineColByte(2, 2, 1)) };
+    ///
+    /// let mut interner = SpanInterner::new();
+    /// let (id_a, id_b) = (interner.intern(a), interner.intern(b));
+    /// assert_ne!(id_a, id_b);
+    /// assert_eq!(interner.resolve(id_a), a);
+    /// assert_eq!(interner.resolve(id_b), b);
+    /// ```
+    pub fn intern(&mut self, span: FileSpan) -> SpanId {
+        let FileSpan { file, span: s } = span;
+        *self.ids.entry((file, s)).or_insert_with(|| {
+            let id = u32::try_from(self.spans.len()).expect("too many interned spans");
+            self.spans.push(span);
+            SpanId(id)
+        })
+    }
+
+    /// Returns the [`SpanId`] of `span`, if it has been interned.
+    #[inline]
+    pub fn get(&self, span: &FileSpan) -> Option<SpanId> {
+        self.ids.get(&(span.file, span.span)).copied()
+    }
+
+    /// Returns the span identified by `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is out of bounds, e.g. if it is from another interner.
+    #[inline]
+    pub fn resolve(&self, id: SpanId) -> FileSpan {
+        self.spans[id.index()]
+    }
+
+    /// Returns the number of distinct interned spans.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Returns an iterator over the interned spans, in the order they were interned.
+    #[inline]
+    pub fn spans(&self) -> impl ExactSizeIterator<Item = (SpanId, &FileSpan)> + '_ {
+        self.spans
+            .iter()
+            .enumerate()
+            .map(|(i, span)| (SpanId(i as u32), span))
+    }
+}
@@ -0,0 +1,215 @@
+//! Serialization of labeled spans to JSON, enabled by the `json` feature.
+//!
+//! The output is an array with an object per label, in the given order:
+//!
+//! ```json
+//! [
+//!   {
+//!     "file": "main.rs",
+//!     "line": 2,
+//!     "column": 9,
+//!     "end_line": 2,
+//!     "end_column": 10,
+//!     "byte_start": 20,
+//!     "byte_end": 21,
+//!     "message": "unused variable"
+//!   }
+//! ]
+//! ```
+//!
+//! Lines and columns are 1-indexed, with columns counted in [`char`]s.
+//! The end is exclusive, i.e. `end_column` is the column following the
+//! last `char` of the span. The layout is stable, and fields will only
+//! ever be added. The output contains no insignificant whitespace.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::json::to_json;
+//! use char_positions::render::Label;
+//! use char_positions::SourceFile;
+//!
+//! let file = SourceFile::new("main.rs", "fn main() {\n    let x = \"🦀\";\n}\n");
+//! let labels = [Label::new(file.span(20..21).unwrap(), "unused variable")];
+//!
+//! let json = to_json(labels.iter().map(|label| (file.name(), label)));
+//! assert_eq!(
+//!     json,
+//!     r#"[{"file":"main.rs","line":2,"column":9,"end_line":2,"end_column":10,"byte_start":20,"byte_end":21,"message":"unused variable"}]"#,
+//! );
+//! ```
+
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::render::{Diagnostic, Label};
+use crate::Span;
+
+/// Serializes `labels`, along with the name of the file they refer to, as JSON.
+///
+/// See the [module](self) documentation for the layout.
+pub fn to_json<'a, 'b: 'a>(labels: impl IntoIterator<Item = (&'a str, &'a Label<'b>)>) -> String {
+    let mut out = String::new();
+    _ = write_json(&mut out, labels);
+    out
+}
+
+/// Writes `labels`, along with the name of the file they refer to, as JSON to `w`.
+///
+/// See the [module](self) documentation for the layout.
+pub fn write_json<'a, 'b: 'a>(
+    w: &mut impl Write,
+    labels: impl IntoIterator<Item = (&'a str, &'a Label<'b>)>,
+) -> fmt::Result {
+    w.write_char('[')?;
+    for (i, (file, label)) in labels.into_iter().enumerate() {
+        if i > 0 {
+            w.write_char(',')?;
+        }
+
+        w.write_str("{\"file\":")?;
+        write_str(w, file)?;
+        w.write_char(',')?;
+        write_label_fields(w, label)?;
+        w.write_char('}')?;
+    }
+    w.write_char(']')
+}
+
+/// Serializes `diagnostics`, along with the name of the file they refer to, as JSON.
+///
+/// The output is an array with an object per diagnostic, where `code` is
+/// `null` if absent, and `labels` has the primary label first, if any, using
+/// the same layout as [`to_json()`] without `file`:
+///
+/// ```json
+/// [
+///   {
+///     "file": "main.rs",
+///     "severity": "error",
+///     "code": "E0425",
+///     "message": "cannot find value `y` in this scope",
+///     "labels": [
+///       { "line": 1, "column": 9, ..., "message": "not found", "primary": true }
+///     ],
+///     "notes": []
+///   }
+/// ]
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use char_positions::json::diagnostics_to_json;
+/// use char_positions::render::Diagnostic;
+/// use char_positions::SourceFile;
+///
+/// let file = SourceFile::new("main.rs", "let x = y;");
+/// let diagnostic = Diagnostic::error("cannot find value `y` in this scope")
+///     .code("E0425")
+///     .primary(file.span(8..9).unwrap(), "not found")
+///     .note("`y` must be declared before use");
+///
+/// let json = diagnostics_to_json([(file.name(), &diagnostic)]);
+/// assert_eq!(
+///     json,
+///     concat!(
+///         r#"[{"file":"main.rs","severity":"error","code":"E0425","message":"cannot find value `y` in this scope","#,
+///         r#""labels":[{"line":1,"column":9,"end_line":1,"end_column":10,"byte_start":8,"byte_end":9,"message":"not found","primary":true}],"#,
+///         r#""notes":["`y` must be declared before use"]}]"#,
+///     ),
+/// );
+/// ```
+pub fn diagnostics_to_json<'a, 'b: 'a>(
+    diagnostics: impl IntoIterator<Item = (&'a str, &'a Diagnostic<'b>)>,
+) -> String {
+    let mut out = String::new();
+    _ = write_diagnostics_json(&mut out, diagnostics);
+    out
+}
+
+/// Writes `diagnostics`, along with the name of the file they refer to, as JSON to `w`.
+///
+/// See [`diagnostics_to_json()`] for the layout.
+pub fn write_diagnostics_json<'a, 'b: 'a>(
+    w: &mut impl Write,
+    diagnostics: impl IntoIterator<Item = (&'a str, &'a Diagnostic<'b>)>,
+) -> fmt::Result {
+    w.write_char('[')?;
+    for (i, (file, diagnostic)) in diagnostics.into_iter().enumerate() {
+        if i > 0 {
+            w.write_char(',')?;
+        }
+
+        w.write_str("{\"file\":")?;
+        write_str(w, file)?;
+        w.write_str(",\"severity\":")?;
+        write_str(w, diagnostic.severity.as_str())?;
+        w.write_str(",\"code\":")?;
+        match &diagnostic.code {
+            Some(code) => write_str(w, code)?,
+            None => w.write_str("null")?,
+        }
+        w.write_str(",\"message\":")?;
+        write_str(w, &diagnostic.message)?;
+
+        w.write_str(",\"labels\":[")?;
+        for (i, label) in diagnostic.labels().enumerate() {
+            if i > 0 {
+                w.write_char(',')?;
+            }
+            w.write_char('{')?;
+            write_label_fields(w, label)?;
+            let primary = (i == 0) && diagnostic.primary.is_some();
+            write!(w, ",\"primary\":{primary}}}")?;
+        }
+
+        w.write_str("],\"notes\":[")?;
+        for (i, note) in diagnostic.notes.iter().enumerate() {
+            if i > 0 {
+                w.write_char(',')?;
+            }
+            write_str(w, note)?;
+        }
+        w.write_str("]}")?;
+    }
+    w.write_char(']')
+}
+
+/// Writes the fields of `label`, excluding the enclosing braces.
+fn write_label_fields(w: &mut impl Write, label: &Label<'_>) -> fmt::Result {
+    let Span { start, end } = label.span;
+    write!(
+        w,
+        "\"line\":{},\"column\":{},\"end_line\":{},\"end_column\":{},\"byte_start\":{},\"byte_end\":{},\"message\":",
+        start.0, start.1, end.0, end.1, start.2, end.2,
+    )?;
+    write_str(w, &label.message)
+}
+
+/// Writes `s` as a JSON string, i.e. quoted and escaped.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::json::write_str;
+///
+/// let mut json = String::new();
+/// write_str(&mut json, "\"🦀\"\n").unwrap();
+/// assert_eq!(json, r#""\"🦀\"\n""#);
+/// ```
+pub fn write_str(w: &mut impl Write, s: &str) -> fmt::Result {
+    w.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => w.write_str("\\\"")?,
+            '\\' => w.write_str("\\\\")?,
+            '\n' => w.write_str("\\n")?,
+            '\r' => w.write_str("\\r")?,
+            '\t' => w.write_str("\\t")?,
+            c if c.is_control() => write!(w, "\\u{:04x}", c as u32)?,
+            c => w.write_char(c)?,
+        }
+    }
+    w.write_char('"')
+}
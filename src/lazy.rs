@@ -0,0 +1,158 @@
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use char_ranges::{CharRanges, CharRangesExt};
+
+use crate::counter::{uncount, Count};
+use crate::{LineCol, LineColByte, LineColByteRange};
+
+pub trait LazyPositionsExt {
+    /// Returns an iterator over [`char`]s and their [`LazyPos`], where
+    /// only the line and byte range are tracked during iteration.
+    ///
+    /// The column is computed on demand by [`LazyPos::col()`], by counting
+    /// the [`char`]s from the start of the line. This avoids tracking the
+    /// column for every [`char`], when it is rarely needed, e.g. only for
+    /// reporting errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LazyPositionsExt, LineCol};
+    ///
+    /// let text = "Hello 👋\nWorld 🌏\n🦀🦀";
+    ///
+    /// let (pos, c) = text
+    ///     .lazy_char_positions()
+    ///     .find(|&(_, c)| c == '🌏')
+    ///     .unwrap();
+    ///
+    /// assert_eq!(c, '🌏');
+    /// assert_eq!(pos.line(), 2);
+    /// assert_eq!(pos.byte_range(), 17..21);
+    /// assert_eq!(pos.col(), 7);
+    /// assert_eq!(pos.line_col(), LineCol(2, 7));
+    /// ```
+    fn lazy_char_positions(&self) -> LazyCharPositions<'_>;
+}
+
+impl LazyPositionsExt for str {
+    #[inline]
+    fn lazy_char_positions(&self) -> LazyCharPositions<'_> {
+        LazyCharPositions {
+            text: self,
+            iter: self.char_ranges(),
+            line: 1,
+            line_start: 0,
+        }
+    }
+}
+
+/// The position of a [`char`], where the column is computed on demand.
+///
+/// See [`LazyPositionsExt::lazy_char_positions()`].
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct LazyPos<'a> {
+    text: &'a str,
+    line: usize,
+    /// The start byte position of the line.
+    line_start: usize,
+    range: Range<usize>,
+}
+
+impl LazyPos<'_> {
+    /// Returns the 1-indexed line.
+    #[inline]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the 1-indexed column, by counting the [`char`]s from the
+    /// start of the line.
+    #[inline]
+    pub fn col(&self) -> usize {
+        self.text[self.line_start..self.range.start].chars().count() + 1
+    }
+
+    /// Returns the start byte position of the line.
+    #[inline]
+    pub const fn line_start(&self) -> usize {
+        self.line_start
+    }
+
+    #[inline]
+    pub const fn byte_start(&self) -> usize {
+        self.range.start
+    }
+
+    #[inline]
+    pub const fn byte_end(&self) -> usize {
+        self.range.end
+    }
+
+    #[inline]
+    pub const fn byte_range(&self) -> Range<usize> {
+        self.range.start..self.range.end
+    }
+
+    /// Returns the line and column, computing the column.
+    #[inline]
+    pub fn line_col(&self) -> LineCol {
+        LineCol(self.line, self.col())
+    }
+
+    /// Returns the line, column, and start byte, computing the column.
+    #[inline]
+    pub fn line_col_byte(&self) -> LineColByte {
+        LineColByte(self.line, self.col(), self.range.start)
+    }
+}
+
+impl From<LazyPos<'_>> for LineColByteRange {
+    #[inline]
+    fn from(pos: LazyPos<'_>) -> Self {
+        Self(pos.line, pos.col(), pos.range)
+    }
+}
+
+/// An iterator over [`char`]s and their [`LazyPos`].
+///
+/// See [`LazyPositionsExt::lazy_char_positions()`].
+#[derive(Clone, Debug)]
+pub struct LazyCharPositions<'a> {
+    text: &'a str,
+    iter: CharRanges<'a>,
+    line: Count,
+    /// The start byte position of the line.
+    line_start: usize,
+}
+
+impl<'a> LazyCharPositions<'a> {
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+}
+
+impl<'a> Iterator for LazyCharPositions<'a> {
+    type Item = (LazyPos<'a>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (r, c) = self.iter.next()?;
+        let pos = LazyPos {
+            text: self.text,
+            line: uncount(self.line),
+            line_start: self.line_start,
+            range: r,
+        };
+        if c == '\n' {
+            self.line = self.line.saturating_add(1);
+            self.line_start = pos.range.end;
+        }
+        Some((pos, c))
+    }
+}
+
+impl FusedIterator for LazyCharPositions<'_> {}
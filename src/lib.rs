@@ -50,16 +50,18 @@
 //! | [`usize`] | Start byte index (same as [`.char_indicies()`]) |
 //! | [`std::ops::Range<usize>`] | Start byte and end byte index, i.e. `&text[range]` is the `char` |
 //! | [`LineColByteRange`] | Line number, column number, and byte range |
+//! | [`LineColByte`] | Line number, column number, and start byte index |
 //! | [`LineCol`] | Line number and column number |
 //! | [`Line`] | Line number |
 //! | [`Col`] | Column number |
 //! | [`ByteRange`] | Same as using [`std::ops::Range<usize>`] |
-//! | [`ByteStart`] | Start byte index (same as [`.char_indicies()`]) |
+//! | [`ByteStart`] or [`Byte`] | Start byte index (same as [`.char_indicies()`]) |
 //! | [`ByteEnd`] | End byte index |
 //! | _Tuples are also supported, e.g._ | |
 //! | <code>([Line],)</code> | _Produces the tuple_ |
 //! | <code>([Line], [Col])</code> | _Produces the tuple_ |
 //! | <code>([Line], [Col], [ByteStart], [ByteEnd])</code> | _Produces the tuple_ |
+//! | <code>([LineColByte], [ByteEnd])</code> | _Produces the tuple_ |
 //! | _etc._ | |
 //!
 //! ## Example - `LineColByteRange`
@@ -100,14 +102,17 @@
 //! [char_positions]: https://docs.rs/char-positions/*/char_positions/trait.CharPositionsExt.html#tymethod.char_positions
 //!
 //! [`LineColByteRange`]: https://docs.rs/char-positions/*/char_positions/struct.LineColByteRange.html
+//! [`LineColByte`]: https://docs.rs/char-positions/*/char_positions/struct.LineColByte.html
 //! [`LineCol`]: https://docs.rs/char-positions/*/char_positions/struct.LineCol.html
 //! [`Line`]: https://docs.rs/char-positions/*/char_positions/struct.Line.html
 //! [`Col`]: https://docs.rs/char-positions/*/char_positions/struct.Line.html
 //! [`ByteRange`]: https://docs.rs/char-positions/*/char_positions/struct.ByteRange.html
 //! [`ByteStart`]: https://docs.rs/char-positions/*/char_positions/struct.ByteStart.html
+//! [`Byte`]: https://docs.rs/char-positions/*/char_positions/struct.Byte.html
 //! [`ByteEnd`]: https://docs.rs/char-positions/*/char_positions/struct.ByteEnd.html
 //!
 //! [LineColByteRange]: https://docs.rs/char-positions/*/char_positions/struct.LineColByteRange.html
+//! [LineColByte]: https://docs.rs/char-positions/*/char_positions/struct.LineColByte.html
 //! [LineCol]: https://docs.rs/char-positions/*/char_positions/struct.LineCol.html
 //! [Line]: https://docs.rs/char-positions/*/char_positions/struct.Line.html
 //! [Col]: https://docs.rs/char-positions/*/char_positions/struct.Col.html
@@ -124,12 +129,190 @@
 #![forbid(unsafe_code)]
 #![forbid(elided_lifetimes_in_paths)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod chunks;
+pub mod cursor;
+pub mod github;
+pub mod location;
+pub mod markers;
+#[cfg(feature = "alloc")]
+pub mod render;
+pub mod unescape;
+
+#[cfg(feature = "aho-corasick")]
+pub mod aho_corasick;
+#[cfg(feature = "annotate-snippets")]
+pub mod annotate_snippets;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+#[cfg(feature = "ariadne")]
+pub mod ariadne;
+#[cfg(feature = "caseless")]
+pub mod caseless;
+#[cfg(feature = "chumsky")]
+pub mod chumsky;
+#[cfg(feature = "codespan")]
+pub mod codespan;
+#[cfg(feature = "encoding_rs")]
+pub mod encoding_rs;
+#[cfg(feature = "html")]
+pub mod html;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "lsp-types")]
+pub mod lsp;
+#[cfg(feature = "miette")]
+pub mod miette;
+#[cfg(feature = "nom")]
+pub mod nom;
+#[cfg(feature = "proc-macro2")]
+pub mod proc_macro2;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "rayon")]
+pub mod rayon;
+#[cfg(feature = "regex")]
+pub mod regex;
+#[cfg(feature = "ropey")]
+pub mod ropey;
+#[cfg(feature = "sarif")]
+pub mod sarif;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "similar")]
+pub mod similar;
+#[cfg(feature = "sourcemap")]
+pub mod sourcemap;
+#[cfg(feature = "tree-sitter")]
+pub mod tree_sitter;
+#[cfg(feature = "unicode-normalization")]
+pub mod unicode_normalization;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "winnow")]
+pub mod winnow;
+
+#[cfg(feature = "alloc")]
+mod anchor;
+mod ansi;
+#[cfg(feature = "async")]
+mod async_reader;
+mod bytes;
+mod convert;
+mod counted;
+mod counter;
+#[cfg(feature = "alloc")]
+mod decoder;
+#[cfg(feature = "alloc")]
+mod edit;
+mod external;
+mod find;
+#[cfg(feature = "alloc")]
+mod interner;
+mod lazy;
+#[cfg(feature = "alloc")]
+mod line_index;
+mod lines;
+#[cfg(feature = "alloc")]
+mod mapper;
+mod newlines;
+mod ops;
+mod parse;
+#[cfg(feature = "alloc")]
+mod positioned_str;
+#[cfg(feature = "std")]
+mod reader;
+mod relative;
+mod resolver;
+mod rev;
+mod scan;
+mod slice;
+mod snap;
+#[cfg(feature = "alloc")]
+mod source;
+#[cfg(feature = "alloc")]
+mod source_map;
+mod span;
+#[cfg(feature = "alloc")]
+mod span_map;
+#[cfg(feature = "alloc")]
+mod span_set;
+mod spanned;
+mod stats;
+mod tabs;
+mod trailing;
+mod utf8;
+mod whitespace;
+mod writer;
+
+pub use self::ansi::{AnsiCharPositions, AnsiPositionsExt, AnsiToken};
+pub use self::bytes::{BytePositions, BytePositionsExt, SubColumnBytePositions};
+pub use self::convert::TryFromPositionError;
+pub use self::counted::CountedCharPositions;
+pub use self::counter::{CheckedCharPositions, OverflowPolicy, PositionOverflowError};
+pub use self::external::{ExternalPositionsExt, Indexing};
+pub use self::find::{CharOccurrences, FindPositionsExt};
+pub use self::lazy::{LazyCharPositions, LazyPos, LazyPositionsExt};
+pub use self::lines::{LineCharPositions, LinePositionsExt};
+pub use self::newlines::{
+    LineTerminatorsExt, NormalizedCharPositions, NormalizedNewlinesExt, SplitLineTerminators,
+    UnicodeCharPositions, UnicodeLineBreaksExt,
+};
+pub use self::parse::ParseLineColError;
+pub use self::relative::RelativePos;
+pub use self::resolver::SpanResolver;
+pub use self::rev::{RevCharPositions, RevPositionsExt};
+pub use self::slice::{CharSlicePositions, CharSlicePositionsExt};
+pub use self::snap::{Bias, SnapPositionsExt};
+pub use self::span::{Span, SpanLines};
+pub use self::spanned::Spanned;
+pub use self::stats::{PositionStats, PositionStatsExt};
+pub use self::tabs::{ExpandTabsExt, ExpandedTabs};
+pub use self::trailing::{TrailingNewline, TrailingNewlineExt};
+pub use self::utf8::{CheckedUtf8CharPositions, CheckedUtf8Ext, InvalidUtf8};
+pub use self::whitespace::{MixedIndent, TrailingWhitespace, WhitespacePositionsExt};
+pub use self::writer::PositionTrackingWriter;
+
+#[cfg(feature = "alloc")]
+pub use self::anchor::{Anchor, AnchorSet};
+#[cfg(feature = "async")]
+pub use self::async_reader::AsyncCharPositionsReader;
+#[cfg(feature = "alloc")]
+pub use self::decoder::{DecodedChars, PushDecoder, ScannerState, Utf8Error};
+#[cfg(feature = "alloc")]
+pub use self::edit::{apply_edits, map_through_edits, resume_after_edit, TextEdit};
+#[cfg(feature = "alloc")]
+pub use self::interner::{SpanId, SpanInterner};
+#[cfg(feature = "alloc")]
+pub use self::line_index::LineIndex;
+#[cfg(feature = "alloc")]
+pub use self::mapper::PositionMapper;
+#[cfg(feature = "alloc")]
+pub use self::positioned_str::PositionedStr;
+#[cfg(feature = "std")]
+pub use self::reader::{CharPositionsReader, PositionedLines};
+#[cfg(feature = "alloc")]
+pub use self::source::SourceFile;
+#[cfg(feature = "alloc")]
+pub use self::source_map::{FileId, FileSpan, SourceMap};
+#[cfg(feature = "alloc")]
+pub use self::span_map::{SpanMap, SpanMapping};
+#[cfg(feature = "alloc")]
+pub use self::span_set::SpanSet;
+
+use core::fmt;
 use core::iter::FusedIterator;
 use core::marker::PhantomData;
 use core::ops::Range;
 
 use char_ranges::{CharRanges, CharRangesExt};
 
+use self::counter::LineColCounter;
+
 pub trait CharPositionsExt {
     /// Returns an iterator over [`char`]s and their positions.
     ///
@@ -137,6 +320,36 @@ pub trait CharPositionsExt {
     fn char_positions<T>(&self) -> CharPositions<'_, T>
     where
         LineColByteRange: Into<T>;
+
+    /// Returns an iterator over [`char`]s and their positions, which skips
+    /// tracking lines and columns if `T` does not use them, i.e. if
+    /// [`T::LINE_COL`](CharPosition::LINE_COL) is `false`.
+    ///
+    /// For e.g. [`usize`] and [`ByteRange`], this is as fast as iterating
+    /// the [`char`]s and their byte ranges alone. Otherwise, this is the
+    /// same as [`char_positions()`](Self::char_positions).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{ByteRange, CharPositionsExt};
+    ///
+    /// let ranges = "a🦀\nb"
+    ///     .char_positions_fast::<ByteRange>()
+    ///     .map(|(ByteRange(r), _)| r)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(ranges, [0..1, 1..5, 5..6, 6..7]);
+    /// ```
+    #[inline]
+    fn char_positions_fast<T>(&self) -> CharPositions<'_, T>
+    where
+        T: CharPosition,
+        LineColByteRange: Into<T>,
+    {
+        let mut iter = self.char_positions();
+        iter.line_col = T::LINE_COL;
+        iter
+    }
 }
 
 impl CharPositionsExt for str {
@@ -153,11 +366,17 @@ impl CharPositionsExt for str {
 ///
 /// Note: Cloning this iterator is essentially a copy.
 ///
+/// With the `compact` feature, the line and column are tracked as
+/// [`u32`], making the iterator smaller, but limiting the text to
+/// less than 4 GiB.
+///
 /// See examples in the [crate root](crate).
 #[derive(Clone, Debug)]
 pub struct CharPositions<'a, T> {
     iter: CharRanges<'a>,
-    pos: LineCol,
+    pos: LineColCounter,
+    /// Whether to track lines and columns, see [`CharPositionsExt::char_positions_fast()`].
+    line_col: bool,
     phantom: PhantomData<T>,
 }
 
@@ -166,7 +385,8 @@ impl<'a, T> CharPositions<'a, T> {
     fn new(s: &'a str) -> Self {
         Self {
             iter: s.char_ranges(),
-            pos: LineCol::START,
+            pos: LineColCounter::START,
+            line_col: true,
             phantom: PhantomData,
         }
     }
@@ -176,6 +396,114 @@ impl<'a, T> CharPositions<'a, T> {
     pub fn as_str(&self) -> &'a str {
         self.iter.as_str()
     }
+
+    /// Sets how the line and column are handled if they exceed the range
+    /// of their counters, which defaults to [`OverflowPolicy::Saturate`].
+    ///
+    /// This can only happen with the `compact` feature, for text of
+    /// 4 GiB or more.
+    #[inline]
+    pub fn overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.pos.policy = policy;
+        self
+    }
+
+    /// Returns an iterator which produces a [`PositionOverflowError`]
+    /// for the first [`char`] whose line or column exceeds the range of
+    /// their counters, after which it ends.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{CharPositionsExt, LineCol};
+    ///
+    /// let positions = "a\n🦀"
+    ///     .char_positions::<LineCol>()
+    ///     .checked()
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(positions, [(LineCol(1, 1), 'a'), (LineCol(1, 2), '\n'), (LineCol(2, 1), '🦀')]);
+    /// ```
+    #[inline]
+    pub fn checked(self) -> CheckedCharPositions<'a, T> {
+        CheckedCharPositions {
+            iter: self,
+            done: false,
+        }
+    }
+
+    /// Counts the remaining [`char`]s up front, returning an iterator
+    /// which implements [`ExactSizeIterator`], e.g. for preallocating
+    /// or reporting progress.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{CharPositionsExt, LineCol};
+    ///
+    /// let mut iter = "Hello 👋\n🦀🦀".char_positions::<LineCol>().with_len();
+    /// assert_eq!(iter.len(), 10);
+    ///
+    /// iter.next();
+    /// assert_eq!(iter.len(), 9);
+    /// assert_eq!(iter.size_hint(), (9, Some(9)));
+    /// ```
+    #[inline]
+    pub fn with_len(self) -> CountedCharPositions<'a, T> {
+        let len = self.as_str().chars().count();
+        CountedCharPositions { iter: self, len }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> CharPositions<'_, T>
+where
+    LineColByteRange: Into<T>,
+{
+    /// Appends up to `max` [`char`]s and their positions to `buf`,
+    /// returning the number of [`char`]s appended.
+    ///
+    /// Produces the same items as `buf.extend(iter.by_ref().take(max))`,
+    /// but reserves space up front and decodes the positions in a
+    /// single loop, which is faster when filling large buffers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{CharPositionsExt, LineCol};
+    ///
+    /// let mut iter = "Hello 👋\n🦀🦀".char_positions::<LineCol>();
+    /// let mut buf = Vec::new();
+    ///
+    /// assert_eq!(iter.fill(&mut buf, 8), 8);
+    /// assert_eq!(buf.last(), Some(&(LineCol(1, 8), '\n')));
+    ///
+    /// assert_eq!(iter.fill(&mut buf, 8), 2);
+    /// assert_eq!(buf.last(), Some(&(LineCol(2, 2), '🦀')));
+    ///
+    /// assert_eq!(iter.fill(&mut buf, 8), 0);
+    /// assert_eq!(buf.len(), 10);
+    /// ```
+    pub fn fill(&mut self, buf: &mut alloc::vec::Vec<(T, char)>, max: usize) -> usize {
+        buf.reserve(max.min(self.as_str().len()));
+
+        let mut pos = self.pos;
+        let mut n = 0;
+        while n < max {
+            let Some((r, c)) = self.iter.next() else {
+                break;
+            };
+            buf.push((pos.at(r).into(), c));
+            if self.line_col {
+                pos.advance(c);
+            }
+            n += 1;
+        }
+        self.pos = pos;
+
+        n
+    }
 }
 
 impl<T> Iterator for CharPositions<'_, T>
@@ -186,47 +514,201 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         let (r, c) = self.iter.next()?;
-        let pos = LineColByteRange(self.pos.0, self.pos.1, r);
+        let pos = self.pos.at(r);
+        if self.line_col {
+            self.pos.advance(c);
+        }
+        Some((pos.into(), c))
+    }
 
-        match c {
-            '\n' => {
-                self.pos.0 += 1;
-                self.pos.1 = 1;
-            }
-            _ => {
-                self.pos.1 += 1;
+    #[inline]
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let (mut pos, line_col) = (self.pos, self.line_col);
+        self.iter.fold(init, |acc, (r, c)| {
+            let item = (pos.at(r).into(), c);
+            if line_col {
+                pos.advance(c);
             }
-        }
+            f(acc, item)
+        })
+    }
+}
 
-        Some((pos.into(), c))
+/// Types produced by [`CharPositionsExt::char_positions_fast()`].
+///
+/// [`LINE_COL`](CharPosition::LINE_COL) allows skipping tracking lines
+/// and columns for types which only consist of byte positions, e.g.
+/// [`usize`] and [`ByteRange`]. Custom types only need an empty `impl`,
+/// unless they never use the line or column.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{CharPosition, CharPositionsExt, LineColByteRange};
+///
+/// struct Offset(usize);
+///
+/// impl From<LineColByteRange> for Offset {
+///     fn from(pos: LineColByteRange) -> Self {
+///         Self(pos.2.start)
+///     }
+/// }
+///
+/// impl CharPosition for Offset {
+///     const LINE_COL: bool = false;
+/// }
+///
+/// let offsets = "a🦀b"
+///     .char_positions_fast::<Offset>()
+///     .map(|(Offset(i), _)| i)
+///     .collect::<Vec<_>>();
+/// assert_eq!(offsets, [0, 1, 5]);
+/// ```
+pub trait CharPosition {
+    /// Whether the type uses the line or column of [`LineColByteRange`].
+    ///
+    /// If `false`, then the line and column are unspecified.
+    const LINE_COL: bool = true;
+
+    /// Converts `pos` into `Self` without consuming it, such that e.g.
+    /// tuples can convert each element without cloning `pos`.
+    ///
+    /// Defaults to converting a clone of `pos`.
+    #[inline]
+    fn from_position(pos: &LineColByteRange) -> Self
+    where
+        Self: Sized,
+        LineColByteRange: Into<Self>,
+    {
+        pos.clone().into()
     }
 }
 
+macro_rules! impl_char_position {
+    ($($ty:ty => $line_col:literal, |$pos:ident| $from:expr),* $(,)?) => {
+        $(
+            impl CharPosition for $ty {
+                const LINE_COL: bool = $line_col;
+
+                #[inline]
+                fn from_position($pos: &LineColByteRange) -> Self {
+                    $from
+                }
+            }
+        )*
+    };
+}
+
+impl_char_position!(
+    Line => true, |pos| Line(pos.0),
+    Col => true, |pos| Col(pos.1),
+    ByteStart => false, |pos| ByteStart(pos.2.start),
+    ByteEnd => false, |pos| ByteEnd(pos.2.end),
+    ByteRange => false, |pos| ByteRange(pos.2.start..pos.2.end),
+    LineCol => true, |pos| LineCol(pos.0, pos.1),
+    LineColByte => true, |pos| LineColByte(pos.0, pos.1, pos.2.start),
+    LineColByteRange => true, |pos| LineColByteRange(pos.0, pos.1, pos.2.start..pos.2.end),
+    usize => false, |pos| pos.2.start,
+    Range<usize> => false, |pos| pos.2.start..pos.2.end,
+);
+
+macro_rules! impl_char_position_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> CharPosition for ($($name,)+)
+        where
+            $($name: CharPosition, LineColByteRange: Into<$name>,)+
+        {
+            const LINE_COL: bool = $($name::LINE_COL)||+;
+
+            #[inline]
+            fn from_position(pos: &LineColByteRange) -> Self {
+                ($($name::from_position(pos),)+)
+            }
+        }
+    };
+}
+
+impl_char_position_tuple!(A);
+impl_char_position_tuple!(A, B);
+impl_char_position_tuple!(A, B, C);
+impl_char_position_tuple!(A, B, C, D);
+impl_char_position_tuple!(A, B, C, D, E);
+impl_char_position_tuple!(A, B, C, D, E, F);
+
 impl<T> FusedIterator for CharPositions<'_, T> where Self: Iterator {}
 
 /// `Line(line)`
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
 pub struct Line(
     /// 1-indexed line.
     pub usize,
 );
 
 /// `Col(col)`
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
 pub struct Col(
     /// 1-indexed column.
     pub usize,
 );
 
 /// `ByteStart(byte_start)`
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
 pub struct ByteStart(
     /// The start (inclusive) byte positions.
     pub usize,
 );
 
+/// Alias for [`ByteStart`], reading naturally alongside [`Line`] and [`Col`].
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{Byte, CharPositionsExt, Line, LineColByte};
+///
+/// let text = "a\n🦀b";
+///
+/// let mut iter = text.char_positions::<(Line, Byte)>();
+/// assert_eq!(iter.nth(3), Some(((Line(2), Byte(6)), 'b')));
+///
+/// let mut iter = text.char_positions::<(LineColByte,)>();
+/// assert_eq!(iter.nth(3), Some(((LineColByte(2, 2, 6),), 'b')));
+/// ```
+#[doc(inline)]
+pub use self::ByteStart as Byte;
+
 /// `ByteEnd(byte_end)`
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
 pub struct ByteEnd(
     /// The end (exclusive) byte position.
     pub usize,
@@ -234,13 +716,30 @@ pub struct ByteEnd(
 
 /// `ByteRange(byte_start..byte_end)`
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
 pub struct ByteRange(
     /// The start (inclusive) and end (exclusive) byte positions.
     pub Range<usize>,
 );
 
 /// `LineCol(line, col)`
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+///
+/// Positions are ordered by their line, then by their column.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "(usize, usize)"))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
 pub struct LineCol(
     /// 1-indexed line.
     pub usize,
@@ -251,6 +750,39 @@ pub struct LineCol(
 impl LineCol {
     const START: Self = Self(1, 1);
 
+    /// Returns `None` if `line` or `col` is `0`, as both are 1-indexed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::LineCol;
+    ///
+    /// assert_eq!(LineCol::new(2, 5), Some(LineCol(2, 5)));
+    /// assert_eq!(LineCol::new(0, 5), None);
+    /// assert_eq!(LineCol::new(2, 0), None);
+    /// ```
+    #[inline]
+    pub const fn new(line: usize, col: usize) -> Option<Self> {
+        if (line == 0) || (col == 0) {
+            return None;
+        }
+        Some(Self(line, col))
+    }
+
+    /// Advances the position past `c`.
+    #[inline]
+    fn advance(&mut self, c: char) {
+        match c {
+            '\n' => {
+                self.0 += 1;
+                self.1 = 1;
+            }
+            _ => {
+                self.1 += 1;
+            }
+        }
+    }
+
     #[inline]
     pub const fn line(&self) -> usize {
         self.0
@@ -260,10 +792,59 @@ impl LineCol {
     pub const fn column(&self) -> usize {
         self.1
     }
+
+    /// Returns `self` with its line replaced by `line`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{Col, Line, LineCol};
+    ///
+    /// let pos = LineCol(12, 5);
+    ///
+    /// let Line(line) = Line(pos.line()) + 2;
+    /// assert_eq!(pos.with_line(line), LineCol(14, 5));
+    ///
+    /// let Col(col) = Col(pos.column()).saturating_sub(10);
+    /// assert_eq!(pos.with_col(col), LineCol(12, 1));
+    ///
+    /// assert_eq!(Col(pos.column()).checked_sub(5), None);
+    /// ```
+    #[inline]
+    pub const fn with_line(mut self, line: usize) -> Self {
+        self.0 = line;
+        self
+    }
+
+    /// Returns `self` with its column replaced by `col`.
+    #[inline]
+    pub const fn with_col(mut self, col: usize) -> Self {
+        self.1 = col;
+        self
+    }
+}
+
+impl fmt::Display for LineCol {
+    /// Formats the position as `line:col`, which can
+    /// be parsed again using [`LineCol::from_str()`].
+    ///
+    /// [`LineCol::from_str()`]: core::str::FromStr::from_str
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.0, self.1)
+    }
 }
 
 /// `LineColByte(line, col, byte_start)`
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "(usize, usize, usize)"))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
 pub struct LineColByte(
     /// 1-indexed line.
     pub usize,
@@ -274,6 +855,32 @@ pub struct LineColByte(
 );
 
 impl LineColByte {
+    /// Returns `None` if `line` or `col` is `0`, as both are 1-indexed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::LineColByte;
+    ///
+    /// assert_eq!(LineColByte::new(2, 5, 16), Some(LineColByte(2, 5, 16)));
+    /// assert_eq!(LineColByte::new(0, 5, 16), None);
+    /// ```
+    #[inline]
+    pub const fn new(line: usize, col: usize, byte: usize) -> Option<Self> {
+        match LineCol::new(line, col) {
+            Some(_) => Some(Self(line, col, byte)),
+            None => None,
+        }
+    }
+
+    /// Advances the position past `c`.
+    #[inline]
+    fn advance(&mut self, c: char) {
+        let mut pos = LineCol(self.0, self.1);
+        pos.advance(c);
+        *self = Self(pos.0, pos.1, self.2 + c.len_utf8());
+    }
+
     #[inline]
     pub const fn line(&self) -> usize {
         self.0
@@ -290,10 +897,46 @@ impl LineColByte {
     pub const fn byte_start(&self) -> usize {
         self.2
     }
+
+    /// Advances the position past `s`, i.e. to the position
+    /// of whatever follows `s`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::LineColByte;
+    ///
+    /// let mut pos = LineColByte(1, 1, 0);
+    ///
+    /// pos.advance_str("Hello 👋");
+    /// assert_eq!(pos, LineColByte(1, 8, 10));
+    ///
+    /// pos.advance_str("\nWorld");
+    /// assert_eq!(pos, LineColByte(2, 6, 16));
+    /// ```
+    pub fn advance_str(&mut self, s: &str) {
+        self.2 += s.len();
+        match scan::rfind_newline(s) {
+            Some(i) => {
+                self.0 += scan::count_newlines(s);
+                self.1 = s[(i + 1)..].chars().count() + 1;
+            }
+            None => {
+                self.1 += s.chars().count();
+            }
+        }
+    }
 }
 
 /// `LineColByteRange(line, col, byte_start..byte_end)`
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
 pub struct LineColByteRange(
     /// 1-indexed line.
     pub usize,
@@ -332,6 +975,54 @@ impl LineColByteRange {
     }
 }
 
+/// The unit columns are counted in.
+///
+/// Columns are otherwise always counted in [`char`]s. However, other tools
+/// might count columns in bytes, or in UTF-16 code units, e.g. JavaScript
+/// and the [Language Server Protocol].
+///
+/// [Language Server Protocol]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#positionEncodingKind
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ColumnUnit {
+    /// Columns are counted in [`char`]s, i.e. Unicode scalar values,
+    /// also known as UTF-32 code units.
+    #[default]
+    Char,
+    /// Columns are counted in UTF-8 code units, i.e. bytes.
+    Utf8,
+    /// Columns are counted in UTF-16 code units.
+    Utf16,
+}
+
+impl ColumnUnit {
+    /// Returns the number of units `c` is counted as.
+    #[inline]
+    pub const fn len_of(self, c: char) -> usize {
+        match self {
+            Self::Char => 1,
+            Self::Utf8 => c.len_utf8(),
+            Self::Utf16 => c.len_utf16(),
+        }
+    }
+
+    /// Returns the number of units in `s`.
+    #[inline]
+    pub fn len_of_str(self, s: &str) -> usize {
+        match self {
+            Self::Char => s.chars().count(),
+            Self::Utf8 => s.len(),
+            Self::Utf16 => s.chars().map(char::len_utf16).sum(),
+        }
+    }
+}
+
 impl From<LineCol> for Line {
     #[inline]
     fn from(pos: LineCol) -> Self {
@@ -0,0 +1,212 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{scan, ColumnUnit, LineCol, LineColByte};
+
+/// An index of the start byte position of every line in a text,
+/// allowing byte positions to be resolved into lines and columns
+/// without iterating the whole text.
+///
+/// Like [`CharPositions`](crate::CharPositions), only `'\n'` is
+/// treated as a line break, and the `'\n'` belongs to the line it
+/// terminates.
+///
+/// The index does not store the text itself, so methods which need to
+/// count [`char`]s take the text the index was built from.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{LineCol, LineIndex};
+///
+/// let text = "Hello 👋\nWorld 🌏\n🦀🦀";
+/// let index = LineIndex::new(text);
+///
+/// assert_eq!(index.line_count(), 3);
+/// assert_eq!(index.line_range(2), Some(11..22));
+/// assert_eq!(index.line_col(text, 17), Some(LineCol(2, 7)));
+/// assert_eq!(index.byte_of(text, LineCol(3, 2)), Some(26));
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct LineIndex {
+    /// The start byte position of each line.
+    line_starts: Vec<usize>,
+    /// The length of the text in bytes.
+    len: usize,
+}
+
+impl LineIndex {
+    /// Builds the index for `text`.
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = Vec::new();
+        line_starts.push(0);
+        line_starts.extend(scan::newlines(text).map(|i| i + 1));
+        Self {
+            line_starts,
+            len: text.len(),
+        }
+    }
+
+    /// Returns the number of lines.
+    ///
+    /// An empty text has 1 line, and a text ending with
+    /// `'\n'` has an empty last line.
+    #[inline]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the length in bytes of the indexed text.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the indexed text is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the start byte position of every line.
+    #[inline]
+    pub fn line_starts(&self) -> &[usize] {
+        &self.line_starts
+    }
+
+    /// Returns the start (inclusive) byte position of the 1-indexed `line`.
+    #[inline]
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        let i = line.checked_sub(1)?;
+        self.line_starts.get(i).copied()
+    }
+
+    /// Returns the end (exclusive) byte position of the 1-indexed `line`,
+    /// including its `'\n'` if any.
+    #[inline]
+    pub fn line_end(&self, line: usize) -> Option<usize> {
+        if line == 0 || line > self.line_starts.len() {
+            return None;
+        }
+        Some(self.line_starts.get(line).copied().unwrap_or(self.len))
+    }
+
+    /// Returns the byte range of the 1-indexed `line`,
+    /// including its `'\n'` if any.
+    #[inline]
+    pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        Some(self.line_start(line)?..self.line_end(line)?)
+    }
+
+    /// Returns the 1-indexed line containing the byte position `byte`.
+    ///
+    /// The end of the text, i.e. `byte == len`, is part of the last line.
+    pub fn line_of(&self, byte: usize) -> Option<usize> {
+        if byte > self.len {
+            return None;
+        }
+        let line = match self.line_starts.binary_search(&byte) {
+            Ok(i) => i + 1,
+            Err(i) => i,
+        };
+        Some(line)
+    }
+
+    /// Returns the line and column of the byte position `byte` in `text`.
+    ///
+    /// Returns `None` if `byte` is out of bounds or not on a [`char`] boundary.
+    ///
+    /// `text` must be the text the index was built from.
+    pub fn line_col(&self, text: &str, byte: usize) -> Option<LineCol> {
+        let line = self.line_of(byte)?;
+        let start = self.line_starts[line - 1];
+        let col = text.get(start..byte)?.chars().count() + 1;
+        Some(LineCol(line, col))
+    }
+
+    /// Returns the line, column, and byte position of the byte
+    /// position `byte` in `text`.
+    ///
+    /// Returns `None` if `byte` is out of bounds or not on a [`char`] boundary.
+    ///
+    /// `text` must be the text the index was built from.
+    #[inline]
+    pub fn line_col_byte(&self, text: &str, byte: usize) -> Option<LineColByte> {
+        let LineCol(line, col) = self.line_col(text, byte)?;
+        Some(LineColByte(line, col, byte))
+    }
+
+    /// Returns the byte position of `pos` in `text`.
+    ///
+    /// The column may point one past the last [`char`] of the last line,
+    /// i.e. the end of the text.
+    /// Returns `None` if the line or column is out of bounds.
+    ///
+    /// `text` must be the text the index was built from.
+    pub fn byte_of(&self, text: &str, pos: LineCol) -> Option<usize> {
+        let LineCol(line, col) = pos;
+        let range = self.line_range(line)?;
+        let col = col.checked_sub(1)?;
+        let line = &text[range.clone()];
+        match line.char_indices().nth(col) {
+            Some((i, _)) => Some(range.start + i),
+            None if !line.ends_with('\n') && col == line.chars().count() => Some(range.end),
+            None => None,
+        }
+    }
+
+    /// Returns the line and column of the byte position `byte` in `text`,
+    /// with the column counted in `unit`s.
+    ///
+    /// Returns `None` if `byte` is out of bounds or not on a [`char`] boundary.
+    ///
+    /// `text` must be the text the index was built from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{ColumnUnit, LineCol, LineIndex};
+    ///
+    /// let text = "Hello\n🦀 World";
+    /// let index = LineIndex::new(text);
+    ///
+    /// assert_eq!(index.line_col_with_unit(text, 10, ColumnUnit::Char), Some(LineCol(2, 2)));
+    /// assert_eq!(index.line_col_with_unit(text, 10, ColumnUnit::Utf8), Some(LineCol(2, 5)));
+    /// assert_eq!(index.line_col_with_unit(text, 10, ColumnUnit::Utf16), Some(LineCol(2, 3)));
+    ///
+    /// assert_eq!(index.byte_of_with_unit(text, LineCol(2, 3), ColumnUnit::Utf16), Some(10));
+    /// assert_eq!(index.byte_of_with_unit(text, LineCol(2, 2), ColumnUnit::Utf16), None);
+    /// ```
+    pub fn line_col_with_unit(&self, text: &str, byte: usize, unit: ColumnUnit) -> Option<LineCol> {
+        let line = self.line_of(byte)?;
+        let start = self.line_starts[line - 1];
+        let col = unit.len_of_str(text.get(start..byte)?) + 1;
+        Some(LineCol(line, col))
+    }
+
+    /// Returns the byte position of `pos` in `text`, with the column
+    /// counted in `unit`s.
+    ///
+    /// Returns `None` if the line or column is out of bounds,
+    /// or if the column is within a [`char`].
+    ///
+    /// `text` must be the text the index was built from.
+    pub fn byte_of_with_unit(&self, text: &str, pos: LineCol, unit: ColumnUnit) -> Option<usize> {
+        let LineCol(line, col) = pos;
+        let range = self.line_range(line)?;
+        let col = col.checked_sub(1)?;
+        let line = &text[range.clone()];
+
+        let mut units = 0;
+        for (i, c) in line.char_indices() {
+            if units == col {
+                return Some(range.start + i);
+            } else if units > col {
+                return None;
+            }
+            units += unit.len_of(c);
+        }
+
+        (!line.ends_with('\n') && (units == col)).then_some(range.end)
+    }
+}
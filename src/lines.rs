@@ -0,0 +1,111 @@
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::{
+    ByteRange, CharPositions, CharPositionsExt, Line, LineColByte, LineColByteRange,
+    LineTerminatorsExt,
+};
+
+pub trait LinePositionsExt {
+    /// Calls `f` for each line of the text, with its line number, byte range,
+    /// content, and an iterator over the [`char`]s of the content and their
+    /// positions in the whole text.
+    ///
+    /// Lines are split like [`str::lines()`], i.e. the content, byte range,
+    /// and [`char`]s exclude the line terminator, `"\n"` or `"\r\n"`, and
+    /// a trailing line terminator does not produce an empty last line.
+    ///
+    /// Nothing is allocated, and the iterator borrows the text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{ByteRange, Line, LineCol, LinePositionsExt};
+    ///
+    /// let text = "let 🦀;\r\n\nx = 1;\n";
+    ///
+    /// let mut lines = Vec::new();
+    /// text.for_each_line_with_positions::<LineCol, _>(|line, range, content, chars| {
+    ///     let last = chars.last();
+    ///     lines.push((line, range, content, last));
+    /// });
+    ///
+    /// assert_eq!(
+    ///     lines,
+    ///     [
+    ///         (Line(1), ByteRange(0..9), "let 🦀;", Some((LineCol(1, 6), ';'))),
+    ///         (Line(2), ByteRange(11..11), "", None),
+    ///         (Line(3), ByteRange(12..18), "x = 1;", Some((LineCol(3, 6), ';'))),
+    ///     ],
+    /// );
+    /// ```
+    fn for_each_line_with_positions<'a, T, F>(&'a self, f: F)
+    where
+        LineColByteRange: Into<T>,
+        F: FnMut(Line, ByteRange, &'a str, LineCharPositions<'a, T>);
+}
+
+impl LinePositionsExt for str {
+    fn for_each_line_with_positions<'a, T, F>(&'a self, mut f: F)
+    where
+        LineColByteRange: Into<T>,
+        F: FnMut(Line, ByteRange, &'a str, LineCharPositions<'a, T>),
+    {
+        for (content, _terminator, line, ByteRange(range)) in self.split_line_terminators() {
+            let range = range.start..(range.start + content.len());
+            let start = LineColByte(line.0, 1, range.start);
+            f(
+                line,
+                ByteRange(range),
+                content,
+                LineCharPositions::new(content, start),
+            );
+        }
+    }
+}
+
+/// An iterator over the [`char`]s of a line and their positions.
+///
+/// See [`LinePositionsExt::for_each_line_with_positions()`].
+#[derive(Clone, Debug)]
+pub struct LineCharPositions<'a, T> {
+    iter: CharPositions<'a, LineColByteRange>,
+    start: LineColByte,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T> LineCharPositions<'a, T> {
+    /// Returns an iterator over the [`char`]s of `line`, where
+    /// `start` is the position of the start of the line.
+    #[inline]
+    pub(crate) fn new(line: &'a str, start: LineColByte) -> Self {
+        Self {
+            iter: line.char_positions::<LineColByteRange>(),
+            start,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.iter.as_str()
+    }
+}
+
+impl<T> Iterator for LineCharPositions<'_, T>
+where
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (LineColByteRange(_, col, r), c) = self.iter.next()?;
+        let LineColByte(line, _, offset) = self.start;
+        let pos = LineColByteRange(line, col, (r.start + offset)..(r.end + offset));
+        Some((pos.into(), c))
+    }
+}
+
+impl<T> FusedIterator for LineCharPositions<'_, T> where Self: Iterator {}
@@ -0,0 +1,160 @@
+//! Formatting of locations, e.g. `path:line:col`, as understood by editors
+//! and terminals for jumping to a position in a file.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::location::{Location, LocationStyle};
+//! use char_positions::LineCol;
+//!
+//! let loc = Location::new("src/main.rs", LineCol(12, 5));
+//! assert_eq!(loc.to_string(), "src/main.rs:12:5");
+//!
+//! let loc = loc.style(LocationStyle::Rustc);
+//! assert_eq!(loc.to_string(), "--> src/main.rs:12:5");
+//!
+//! let loc = loc.style(LocationStyle::Msvc);
+//! assert_eq!(loc.to_string(), "src/main.rs(12,5)");
+//! ```
+
+use core::fmt;
+
+use crate::LineCol;
+#[cfg(feature = "alloc")]
+use crate::SourceFile;
+
+/// The format of a [`Location`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub enum LocationStyle {
+    /// `path:line:col`, as used by grep, GCC, and most tools.
+    #[default]
+    Grep,
+    /// `--> path:line:col`, as used by rustc.
+    Rustc,
+    /// `path(line,col)`, as used by MSVC.
+    Msvc,
+}
+
+/// A path along with a line and column, which can be formatted
+/// using [`Display`](fmt::Display) in a given [`LocationStyle`].
+///
+/// See examples in the [module](self) documentation.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Location<'a> {
+    path: &'a str,
+    pos: LineCol,
+    style: LocationStyle,
+    #[cfg(feature = "hyperlinks")]
+    hyperlink: bool,
+}
+
+impl<'a> Location<'a> {
+    #[inline]
+    pub const fn new(path: &'a str, pos: LineCol) -> Self {
+        Self {
+            path,
+            pos,
+            style: LocationStyle::Grep,
+            #[cfg(feature = "hyperlinks")]
+            hyperlink: false,
+        }
+    }
+
+    /// Sets the format. Defaults to [`LocationStyle::Grep`].
+    #[inline]
+    pub const fn style(mut self, style: LocationStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets whether the location is wrapped in an OSC 8 terminal hyperlink,
+    /// linking to the file. Defaults to `false`.
+    ///
+    /// The path should be absolute, as it is linked as is.
+    ///
+    /// Requires the `hyperlinks` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::location::Location;
+    /// use char_positions::LineCol;
+    ///
+    /// let loc = Location::new("/home/me/my project/main.rs", LineCol(3, 1)).hyperlink(true);
+    /// assert_eq!(
+    ///     loc.to_string(),
+    ///     "\x1b]8;;file:///home/me/my%20project/main.rs\x1b\\/home/me/my project/main.rs:3:1\x1b]8;;\x1b\\",
+    /// );
+    /// ```
+    #[cfg(feature = "hyperlinks")]
+    #[inline]
+    pub const fn hyperlink(mut self, hyperlink: bool) -> Self {
+        self.hyperlink = hyperlink;
+        self
+    }
+
+    #[inline]
+    pub const fn path(&self) -> &'a str {
+        self.path
+    }
+
+    #[inline]
+    pub const fn line_col(&self) -> LineCol {
+        self.pos
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Location<'a> {
+    /// Returns the location of `pos` in `file`, using the name of `file` as the path.
+    #[inline]
+    pub fn of(file: &'a SourceFile<'_>, pos: LineCol) -> Self {
+        Self::new(file.name(), pos)
+    }
+}
+
+impl fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "hyperlinks")]
+        if self.hyperlink {
+            f.write_str("\x1b]8;;file://")?;
+            if !self.path.starts_with(['/', '\\']) {
+                f.write_str("/")?;
+            }
+            write_percent_encoded(f, self.path)?;
+            f.write_str("\x1b\\")?;
+        }
+
+        let LineCol(line, col) = self.pos;
+        match self.style {
+            LocationStyle::Grep => write!(f, "{}:{line}:{col}", self.path)?,
+            LocationStyle::Rustc => write!(f, "--> {}:{line}:{col}", self.path)?,
+            LocationStyle::Msvc => write!(f, "{}({line},{col})", self.path)?,
+        }
+
+        #[cfg(feature = "hyperlinks")]
+        if self.hyperlink {
+            f.write_str("\x1b]8;;\x1b\\")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes `path` percent-encoded for use in a `file://` URL,
+/// using `/` as the separator.
+#[cfg(feature = "hyperlinks")]
+fn write_percent_encoded(f: &mut fmt::Formatter<'_>, path: &str) -> fmt::Result {
+    use fmt::Write;
+
+    for b in path.bytes() {
+        match b {
+            b'\\' => f.write_char('/')?,
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                f.write_char(b as char)?
+            }
+            _ => write!(f, "%{b:02X}")?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,200 @@
+//! Integration with [`lsp_types`], enabled by the `lsp-types` feature.
+//!
+//! Positions in the [Language Server Protocol] are 0-indexed, and columns are
+//! counted in the [position encoding] negotiated between the client and server,
+//! UTF-16 code units by default. The functions in this module convert
+//! between byte positions and LSP positions given a [`ColumnUnit`], see
+//! [`column_unit()`] for converting a [`PositionEncodingKind`].
+//!
+//! Additionally, [`LineCol`] and [`Span`] can be converted into [`Position`]
+//! and [`Range`] directly, when the negotiated encoding is UTF-32, i.e.
+//! columns are counted in [`char`]s.
+//!
+//! [Language Server Protocol]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#position
+//! [position encoding]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#positionEncodingKind
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{lsp, ColumnUnit, SourceFile};
+//! use lsp_types::Position;
+//!
+//! let file = SourceFile::new("main.rs", "fn main() {\n    🦀 = x;\n}\n");
+//!
+//! // The `x`
+//! assert_eq!(lsp::position(&file, 23, ColumnUnit::Utf16), Some(Position::new(1, 9)));
+//! assert_eq!(lsp::position(&file, 23, ColumnUnit::Char), Some(Position::new(1, 8)));
+//!
+//! assert_eq!(lsp::byte_of(&file, Position::new(1, 9), ColumnUnit::Utf16), Some(23));
+//! // Columns past the end of the line are clamped
+//! assert_eq!(lsp::byte_of(&file, Position::new(1, 99), ColumnUnit::Utf16), Some(25));
+//!
+//! // Including before a "\r\n"
+//! let file = SourceFile::new("main.rs", "x;\r\ny;\r\n");
+//! assert_eq!(lsp::byte_of(&file, Position::new(0, 99), ColumnUnit::Utf16), Some(2));
+//! ```
+
+use core::fmt;
+
+use ::lsp_types::{Position, PositionEncodingKind, Range};
+
+use crate::{ColumnUnit, LineCol, SourceFile, Span};
+
+/// Returns the [`ColumnUnit`] of the position encoding `kind`,
+/// or `None` if it is not a known encoding.
+pub fn column_unit(kind: &PositionEncodingKind) -> Option<ColumnUnit> {
+    if *kind == PositionEncodingKind::UTF8 {
+        Some(ColumnUnit::Utf8)
+    } else if *kind == PositionEncodingKind::UTF16 {
+        Some(ColumnUnit::Utf16)
+    } else if *kind == PositionEncodingKind::UTF32 {
+        Some(ColumnUnit::Char)
+    } else {
+        None
+    }
+}
+
+impl From<ColumnUnit> for PositionEncodingKind {
+    #[inline]
+    fn from(unit: ColumnUnit) -> Self {
+        match unit {
+            ColumnUnit::Char => Self::UTF32,
+            ColumnUnit::Utf8 => Self::UTF8,
+            ColumnUnit::Utf16 => Self::UTF16,
+        }
+    }
+}
+
+/// Returns the LSP position of the byte position `byte` in `file`.
+///
+/// Returns `None` if `byte` is out of bounds, not on a [`char`] boundary,
+/// or the line or column does not fit in a [`u32`].
+pub fn position(file: &SourceFile<'_>, byte: usize, unit: ColumnUnit) -> Option<Position> {
+    let pos = file
+        .line_index()
+        .line_col_with_unit(file.text(), byte, unit)?;
+    Position::try_from(pos).ok()
+}
+
+/// Returns the LSP range of `span` in `file`.
+///
+/// See [`position()`].
+#[inline]
+pub fn range(file: &SourceFile<'_>, span: &Span, unit: ColumnUnit) -> Option<Range> {
+    let start = position(file, span.byte_start(), unit)?;
+    let end = position(file, span.byte_end(), unit)?;
+    Some(Range::new(start, end))
+}
+
+/// Returns the byte position of the LSP position `pos` in `file`.
+///
+/// As per the specification, columns past the end of the line are clamped
+/// to the end of the line, i.e. before its `'\n'` or `"\r\n"`.
+///
+/// Returns `None` if the line is out of bounds, or the column is within a [`char`].
+pub fn byte_of(file: &SourceFile<'_>, pos: Position, unit: ColumnUnit) -> Option<usize> {
+    let LineCol(line, col) = LineCol::from(pos);
+    let range = file.line_index().line_range(line)?;
+
+    let text = &file.text()[range.clone()];
+    let text = text.strip_suffix('\n').unwrap_or(text);
+    let text = text.strip_suffix('\r').unwrap_or(text);
+
+    if (pos.character as usize) > unit.len_of_str(text) {
+        Some(range.start + text.len())
+    } else {
+        file.line_index()
+            .byte_of_with_unit(file.text(), LineCol(line, col), unit)
+    }
+}
+
+/// Returns the [`Span`] of the LSP range `range` in `file`.
+///
+/// See [`byte_of()`].
+#[inline]
+pub fn span_of(file: &SourceFile<'_>, range: Range, unit: ColumnUnit) -> Option<Span> {
+    let start = byte_of(file, range.start, unit)?;
+    let end = byte_of(file, range.end, unit)?;
+    file.span(start..end)
+}
+
+/// An error returned when converting a [`LineCol`] or [`Span`] into
+/// a [`Position`] or [`Range`].
+///
+/// # Example
+///
+/// ```
+/// use char_positions::LineCol;
+/// use lsp_types::Position;
+///
+/// assert_eq!(Position::try_from(LineCol(12, 5)), Ok(Position::new(11, 4)));
+/// assert!(Position::try_from(LineCol(0, 5)).is_err());
+/// assert!(Position::try_from(LineCol(12, usize::MAX)).is_err());
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct TryFromLineColError {
+    kind: ErrorKind,
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+enum ErrorKind {
+    Zero,
+    Overflow,
+}
+
+impl TryFromLineColError {
+    #[inline]
+    const fn new(kind: ErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for TryFromLineColError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.kind {
+            ErrorKind::Zero => "line and column must be greater than 0",
+            ErrorKind::Overflow => "line or column out of range for an LSP position",
+        })
+    }
+}
+
+impl std::error::Error for TryFromLineColError {}
+
+/// 1-indexed to 0-indexed.
+#[inline]
+fn to_zero_indexed(n: usize) -> Result<u32, TryFromLineColError> {
+    let n = n
+        .checked_sub(1)
+        .ok_or(TryFromLineColError::new(ErrorKind::Zero))?;
+    u32::try_from(n).map_err(|_| TryFromLineColError::new(ErrorKind::Overflow))
+}
+
+/// Assumes the UTF-32 position encoding, i.e. columns counted in [`char`]s.
+impl TryFrom<LineCol> for Position {
+    type Error = TryFromLineColError;
+
+    #[inline]
+    fn try_from(LineCol(line, col): LineCol) -> Result<Self, Self::Error> {
+        Ok(Self::new(to_zero_indexed(line)?, to_zero_indexed(col)?))
+    }
+}
+
+/// Assumes the UTF-32 position encoding, i.e. columns counted in [`char`]s.
+impl From<Position> for LineCol {
+    #[inline]
+    fn from(pos: Position) -> Self {
+        Self((pos.line as usize) + 1, (pos.character as usize) + 1)
+    }
+}
+
+/// Assumes the UTF-32 position encoding, i.e. columns counted in [`char`]s.
+impl TryFrom<Span> for Range {
+    type Error = TryFromLineColError;
+
+    #[inline]
+    fn try_from(span: Span) -> Result<Self, Self::Error> {
+        let start = Position::try_from(span.start_line_col())?;
+        let end = Position::try_from(span.end_line_col())?;
+        Ok(Self::new(start, end))
+    }
+}
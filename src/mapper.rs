@@ -0,0 +1,250 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{Bias, LineCol, LineColByte, SourceFile};
+
+/// A changed region between an old and a new version of a text.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+struct Hunk {
+    /// The byte range in the old text.
+    old: Range<usize>,
+    /// The byte range in the new text.
+    new: Range<usize>,
+}
+
+/// Maps byte positions between an old and a new version of a text,
+/// e.g. as produced by [`apply_edits()`](crate::apply_edits).
+///
+/// Positions outside the changed regions map exactly. Positions at
+/// the start of or within a changed region map to the start of the
+/// corresponding region if [`Bias::Left`], and to the end if
+/// [`Bias::Right`].
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{apply_edits, Bias, LineCol, SourceFile, TextEdit};
+///
+/// let old = "Hello World\n🦀🦀";
+/// let (new, mapper) = apply_edits(old, &[
+///     TextEdit::new(0..5, "Hi"),
+///     TextEdit::insert(12, "👋 "),
+/// ]);
+/// assert_eq!(new, "Hi World\n👋 🦀🦀");
+///
+/// assert_eq!(mapper.old_to_new(6, Bias::Left), 3);
+/// assert_eq!(mapper.old_to_new(12, Bias::Left), 9);
+/// assert_eq!(mapper.old_to_new(12, Bias::Right), 14);
+/// assert_eq!(mapper.new_to_old(14, Bias::Left), 12);
+///
+/// let old = SourceFile::new("old.txt", old);
+/// let new = SourceFile::new("new.txt", new);
+/// let pos = mapper.old_to_new_line_col(&old, &new, LineCol(2, 2), Bias::Left);
+/// assert_eq!(pos.map(LineCol::from), Some(LineCol(2, 4)));
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Default, Debug)]
+pub struct PositionMapper {
+    /// Sorted, non-overlapping changed regions.
+    hunks: Vec<Hunk>,
+}
+
+impl PositionMapper {
+    /// Returns a mapper where the old and new text are identical.
+    #[inline]
+    pub fn identity() -> Self {
+        Self { hunks: Vec::new() }
+    }
+
+    /// Returns a mapper from the differences between the `old` and `new` text.
+    ///
+    /// The common prefix and suffix are matched, and everything in between
+    /// is treated as a single changed region. See `from_myers_diff()`,
+    /// enabled by the `similar` feature, for a mapper where unchanged text
+    /// in between changes also maps exactly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{Bias, PositionMapper};
+    ///
+    /// let old = "let x = 1;\nlet y = 2;\n";
+    /// let new = "let x = 1;\n\nlet y = 2;\n";
+    ///
+    /// let mapper = PositionMapper::from_diff(old, new);
+    /// assert_eq!(mapper.old_to_new(0, Bias::Left), 0);
+    /// assert_eq!(mapper.old_to_new(15, Bias::Left), 16);
+    /// ```
+    pub fn from_diff(old: &str, new: &str) -> Self {
+        let prefix = old
+            .char_indices()
+            .zip(new.chars())
+            .find(|&((_, a), b)| a != b)
+            .map_or(old.len().min(new.len()), |((i, _), _)| i);
+        let (old_rest, new_rest) = (&old[prefix..], &new[prefix..]);
+
+        let suffix = old_rest
+            .char_indices()
+            .rev()
+            .zip(new_rest.chars().rev())
+            .find(|&((_, a), b)| a != b)
+            .map_or(old_rest.len().min(new_rest.len()), |((i, a), _)| {
+                old_rest.len() - i - a.len_utf8()
+            });
+
+        let mut mapper = Self::identity();
+        if (prefix + suffix != old.len()) || (prefix + suffix != new.len()) {
+            mapper.push(prefix..(old.len() - suffix), prefix..(new.len() - suffix));
+        }
+        mapper
+    }
+
+    /// Returns a mapper from a [Myers diff] of the [`char`]s of the `old`
+    /// and `new` text, such that unchanged text in between changes also
+    /// maps exactly, unlike [`from_diff()`](Self::from_diff).
+    ///
+    /// Requires the `similar` feature.
+    ///
+    /// [Myers diff]: https://docs.rs/similar/*/similar/algorithms/myers/index.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{Bias, PositionMapper};
+    ///
+    /// let old = "a = 1\nb = 2\nc = 3\n";
+    /// let new = "a = 10\nb = 2\nc = 30\n";
+    ///
+    /// let mapper = PositionMapper::from_myers_diff(old, new);
+    /// assert_eq!(mapper.changes().len(), 2);
+    /// // `b` is in between the changes
+    /// assert_eq!(mapper.old_to_new(6, Bias::Left), 7);
+    ///
+    /// let mapper = PositionMapper::from_diff(old, new);
+    /// assert_eq!(mapper.changes().len(), 1);
+    /// assert_eq!(mapper.old_to_new(6, Bias::Left), 5);
+    /// ```
+    #[cfg(feature = "similar")]
+    pub fn from_myers_diff(old: &str, new: &str) -> Self {
+        use similar::{Algorithm, DiffTag};
+
+        fn char_starts(s: &str) -> (Vec<char>, Vec<usize>) {
+            let (chars, mut starts): (Vec<_>, Vec<_>) =
+                s.char_indices().map(|(i, c)| (c, i)).unzip();
+            starts.push(s.len());
+            (chars, starts)
+        }
+
+        let (old_chars, old_starts) = char_starts(old);
+        let (new_chars, new_starts) = char_starts(new);
+
+        let ops = similar::capture_diff_slices(Algorithm::Myers, &old_chars, &new_chars);
+
+        let mut mapper = Self::identity();
+        for op in ops {
+            let (tag, old_range, new_range) = op.as_tag_tuple();
+            if tag == DiffTag::Equal {
+                continue;
+            }
+
+            let old_range = old_starts[old_range.start]..old_starts[old_range.end];
+            let new_range = new_starts[new_range.start]..new_starts[new_range.end];
+
+            // Merge adjacent changes, e.g. a delete followed by an insert
+            match mapper.hunks.last_mut() {
+                Some(last)
+                    if (last.old.end == old_range.start) && (last.new.end == new_range.start) =>
+                {
+                    last.old.end = old_range.end;
+                    last.new.end = new_range.end;
+                }
+                _ => mapper.push(old_range, new_range),
+            }
+        }
+        mapper
+    }
+
+    /// Records that `old` in the old text was changed into `new`
+    /// in the new text.
+    ///
+    /// Hunks must be pushed in order, and must not overlap.
+    #[inline]
+    pub(crate) fn push(&mut self, old: Range<usize>, new: Range<usize>) {
+        if let Some(last) = self.hunks.last() {
+            debug_assert!((last.old.end <= old.start) && (last.new.end <= new.start));
+        }
+        self.hunks.push(Hunk { old, new });
+    }
+
+    /// Returns an iterator over the changed regions, as
+    /// `(old_range, new_range)` byte ranges.
+    #[inline]
+    pub fn changes(&self) -> impl ExactSizeIterator<Item = (Range<usize>, Range<usize>)> + '_ {
+        self.hunks
+            .iter()
+            .map(|hunk| (hunk.old.clone(), hunk.new.clone()))
+    }
+
+    /// Maps the byte position `byte` in the old text, to the
+    /// corresponding byte position in the new text.
+    #[inline]
+    pub fn old_to_new(&self, byte: usize, bias: Bias) -> usize {
+        map(&self.hunks, byte, bias, |hunk| (&hunk.old, &hunk.new))
+    }
+
+    /// Maps the byte position `byte` in the new text, to the
+    /// corresponding byte position in the old text.
+    #[inline]
+    pub fn new_to_old(&self, byte: usize, bias: Bias) -> usize {
+        map(&self.hunks, byte, bias, |hunk| (&hunk.new, &hunk.old))
+    }
+
+    /// Maps `pos` in the `old` text, to the corresponding
+    /// position in the `new` text.
+    pub fn old_to_new_line_col(
+        &self,
+        old: &SourceFile<'_>,
+        new: &SourceFile<'_>,
+        pos: LineCol,
+        bias: Bias,
+    ) -> Option<LineColByte> {
+        let byte = old.byte_of(pos)?;
+        new.position(self.old_to_new(byte, bias))
+    }
+
+    /// Maps `pos` in the `new` text, to the corresponding
+    /// position in the `old` text.
+    pub fn new_to_old_line_col(
+        &self,
+        old: &SourceFile<'_>,
+        new: &SourceFile<'_>,
+        pos: LineCol,
+        bias: Bias,
+    ) -> Option<LineColByte> {
+        let byte = new.byte_of(pos)?;
+        old.position(self.new_to_old(byte, bias))
+    }
+}
+
+fn map(
+    hunks: &[Hunk],
+    byte: usize,
+    bias: Bias,
+    f: impl Fn(&Hunk) -> (&Range<usize>, &Range<usize>),
+) -> usize {
+    // Number of hunks entirely before `byte`
+    let i = hunks.partition_point(|hunk| {
+        let (from, _to) = f(hunk);
+        (from.end < byte) || ((from.end == byte) && (from.start != from.end))
+    });
+
+    match hunks.get(i).map(&f) {
+        Some((from, to)) if from.start <= byte => match bias {
+            Bias::Left => to.start,
+            Bias::Right => to.end,
+        },
+        _ => match i.checked_sub(1).map(|i| f(&hunks[i])) {
+            Some((from, to)) => byte - from.end + to.end,
+            None => byte,
+        },
+    }
+}
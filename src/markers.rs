@@ -0,0 +1,361 @@
+//! Locating merge conflict markers, i.e. `<<<<<<<`, `=======`, and
+//! `>>>>>>>`, and unified diff hunk headers, i.e. `@@ -1,2 +1,3 @@`.
+//!
+//! Markers are only recognized at the start of a line. The span of a
+//! marker covers its whole line, excluding the line break, i.e. `\n`
+//! or `\r\n`.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::markers::conflicts;
+//! use char_positions::LineCol;
+//!
+//! let text = "\
+//! fn main() {
+//! <<<<<<< HEAD
+//!     let x = 🦀;
+//! =======
+//!     let x = 42;
+//!     let y = x;
+//! >>>>>>> feature
+//! }
+//! ";
+//!
+//! let conflict = conflicts(text).next().unwrap();
+//! assert_eq!(conflict.start.start_line_col(), LineCol(2, 1));
+//! assert_eq!(&text[conflict.start.byte_range()], "<<<<<<< HEAD");
+//! assert_eq!(conflict.ours_lines(), 3..4);
+//! assert_eq!(conflict.theirs_lines(), 5..7);
+//! assert_eq!(conflict.lines(), 2..8);
+//! ```
+
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{LineColByte, Span};
+
+/// Returns an iterator over the merge conflicts in `text`.
+///
+/// Both the 2-way style, and the 3-way `diff3` style with a `|||||||`
+/// marker, are recognized. Incomplete conflicts are skipped.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::markers::conflicts;
+///
+/// let text = "\
+/// let x=🦀;
+/// <<<<<<<🦀
+/// =======🦀
+/// >>>>>>>🦀
+/// ";
+/// assert_eq!(conflicts(text).count(), 0);
+///
+/// let text = "<<<<<<< HEAD\r\nours\r\n=======\r\ntheirs\r\n>>>>>>> feature\r\n";
+/// let conflict = conflicts(text).next().unwrap();
+/// assert_eq!(&text[conflict.start.byte_range()], "<<<<<<< HEAD");
+/// assert_eq!(&text[conflict.end.byte_range()], ">>>>>>> feature");
+/// assert_eq!(conflict.lines(), 1..6);
+/// ```
+#[inline]
+pub fn conflicts(text: &str) -> Conflicts<'_> {
+    Conflicts {
+        lines: MarkerLines::new(text),
+    }
+}
+
+/// Returns an iterator over the unified diff hunk headers in `text`.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::markers::hunk_headers;
+/// use char_positions::LineCol;
+///
+/// let patch = "\
+/// --- a/main.rs
+/// +++ b/main.rs
+/// @@ -1,2 +1,3 @@ fn main() {
+///  fn main() {
+/// +    let x = 🦀;
+///  }
+/// @@ -10 +11,0 @@
+/// -// TODO
+/// --- a/lib.rs
+/// +++ b/lib.rs
+/// @@ -1 +1 @@
+/// -pub fn f() {}
+/// +pub fn f() -> i32 { 42 }
+/// ";
+///
+/// let mut headers = hunk_headers(patch);
+///
+/// let header = headers.next().unwrap();
+/// assert_eq!(header.span.start_line_col(), LineCol(3, 1));
+/// assert_eq!(header.old_lines, 1..3);
+/// assert_eq!(header.new_lines, 1..4);
+/// assert_eq!(header.body_lines, 4..7);
+///
+/// let header = headers.next().unwrap();
+/// assert_eq!(header.old_lines, 10..11);
+/// assert_eq!(header.new_lines, 12..12);
+/// assert_eq!(header.body_lines, 8..9);
+///
+/// let header = headers.next().unwrap();
+/// assert_eq!(header.span.start_line_col(), LineCol(11, 1));
+/// assert_eq!(header.body_lines, 12..14);
+///
+/// assert!(headers.next().is_none());
+///
+/// let patch = format!("@@ -1 +{},2 @@\n", usize::MAX);
+/// assert_eq!(hunk_headers(&patch).count(), 0);
+/// ```
+#[inline]
+pub fn hunk_headers(text: &str) -> HunkHeaders<'_> {
+    HunkHeaders {
+        lines: MarkerLines::new(text),
+    }
+}
+
+/// A merge conflict, delimited by conflict markers.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Conflict {
+    /// The span of the `<<<<<<<` marker.
+    pub start: Span,
+    /// The span of the `|||||||` marker, if any.
+    pub base: Option<Span>,
+    /// The span of the `=======` marker.
+    pub separator: Span,
+    /// The span of the `>>>>>>>` marker.
+    pub end: Span,
+}
+
+impl Conflict {
+    /// Returns the 1-indexed lines of the whole conflict,
+    /// including the markers.
+    #[inline]
+    pub fn lines(&self) -> Range<usize> {
+        self.start.start.0..(self.end.start.0 + 1)
+    }
+
+    /// Returns the 1-indexed lines between the `<<<<<<<` marker and the
+    /// `|||||||` or `=======` marker, i.e. "our" side of the conflict.
+    #[inline]
+    pub fn ours_lines(&self) -> Range<usize> {
+        let end = self.base.unwrap_or(self.separator);
+        (self.start.start.0 + 1)..end.start.0
+    }
+
+    /// Returns the 1-indexed lines between the `|||||||` marker and
+    /// the `=======` marker, i.e. the common ancestor, if any.
+    #[inline]
+    pub fn base_lines(&self) -> Option<Range<usize>> {
+        let base = self.base?;
+        Some((base.start.0 + 1)..self.separator.start.0)
+    }
+
+    /// Returns the 1-indexed lines between the `=======` marker and the
+    /// `>>>>>>>` marker, i.e. "their" side of the conflict.
+    #[inline]
+    pub fn theirs_lines(&self) -> Range<usize> {
+        (self.separator.start.0 + 1)..self.end.start.0
+    }
+}
+
+/// A unified diff hunk header, e.g. `@@ -1,2 +1,3 @@`.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct HunkHeader {
+    /// The span of the header.
+    pub span: Span,
+    /// The 1-indexed lines of the hunk in the old file.
+    pub old_lines: Range<usize>,
+    /// The 1-indexed lines of the hunk in the new file.
+    pub new_lines: Range<usize>,
+    /// The 1-indexed lines of the hunk body in the text,
+    /// i.e. the lines following the header, up to the number
+    /// of old and new lines given by the header.
+    pub body_lines: Range<usize>,
+}
+
+/// An iterator over merge conflicts.
+///
+/// See [`conflicts()`].
+#[derive(Clone, Debug)]
+pub struct Conflicts<'a> {
+    lines: MarkerLines<'a>,
+}
+
+impl Iterator for Conflicts<'_> {
+    type Item = Conflict;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut start = None;
+        let mut base = None;
+        let mut separator = None;
+        for (span, line) in self.lines.by_ref() {
+            if is_marker(line, b'<') {
+                start = Some(span);
+                base = None;
+                separator = None;
+            } else if start.is_none() {
+                continue;
+            } else if separator.is_none() && is_marker(line, b'|') {
+                base = Some(span);
+            } else if separator.is_none() && is_marker(line, b'=') {
+                separator = Some(span);
+            } else if is_marker(line, b'>') {
+                if let (Some(start), Some(separator)) = (start, separator) {
+                    return Some(Conflict {
+                        start,
+                        base,
+                        separator,
+                        end: span,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for Conflicts<'_> {}
+
+/// An iterator over unified diff hunk headers.
+///
+/// See [`hunk_headers()`].
+#[derive(Clone, Debug)]
+pub struct HunkHeaders<'a> {
+    lines: MarkerLines<'a>,
+}
+
+impl Iterator for HunkHeaders<'_> {
+    type Item = HunkHeader;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (span, line) = self.lines.next()?;
+            let Some((old_lines, new_lines)) = parse_hunk_header(line) else {
+                continue;
+            };
+
+            // The body consists of the following context, removed, and
+            // added lines, and "\ No newline at end of file", bounded by
+            // the line counts of the header, such that e.g. the `---` and
+            // `+++` lines of the next file in the diff are excluded
+            let (mut old, mut new) = (old_lines.len(), new_lines.len());
+            let body_start = span.start.0 + 1;
+            let mut body_end = body_start;
+            while let Some(line) = self.lines.peek() {
+                match line.as_bytes().first() {
+                    Some(b' ') if (old > 0) && (new > 0) => {
+                        old -= 1;
+                        new -= 1;
+                    }
+                    Some(b'-') if old > 0 => old -= 1,
+                    Some(b'+') if new > 0 => new -= 1,
+                    Some(b'\\') => {}
+                    _ => break,
+                }
+                self.lines.next();
+                body_end += 1;
+            }
+
+            return Some(HunkHeader {
+                span,
+                old_lines,
+                new_lines,
+                body_lines: body_start..body_end,
+            });
+        }
+    }
+}
+
+impl FusedIterator for HunkHeaders<'_> {}
+
+/// Returns `true` if `line` is a conflict marker of 7 `c`s, followed
+/// by nothing or by a space.
+fn is_marker(line: &str, c: u8) -> bool {
+    let line = line.as_bytes();
+    line.get(..7).is_some_and(|b| b.iter().all(|&b| b == c))
+        && matches!(line.get(7), None | Some(b' '))
+}
+
+/// Parses `@@ -l[,s] +l[,s] @@`, returning the old and new lines.
+fn parse_hunk_header(line: &str) -> Option<(Range<usize>, Range<usize>)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (old, rest) = rest.split_once(" +")?;
+    let (new, _) = rest.split_once(" @@")?;
+    Some((parse_hunk_range(old)?, parse_hunk_range(new)?))
+}
+
+/// Parses `l[,s]`, where `s` defaults to 1.
+///
+/// Returns `None` if the range overflows, as the header is malformed.
+fn parse_hunk_range(s: &str) -> Option<Range<usize>> {
+    let (start, len) = match s.split_once(',') {
+        Some((start, len)) => (start.parse::<usize>().ok()?, len.parse::<usize>().ok()?),
+        None => (s.parse::<usize>().ok()?, 1),
+    };
+    // An empty range is located after the given line, e.g. `+11,0` before line 12
+    let start = if len == 0 {
+        start.checked_add(1)?
+    } else {
+        start
+    };
+    Some(start..start.checked_add(len)?)
+}
+
+/// An iterator over the lines of a text, excluding line breaks,
+/// and their spans.
+#[derive(Clone, Debug)]
+struct MarkerLines<'a> {
+    text: &'a str,
+    /// The position of the start of the next line.
+    pos: LineColByte,
+}
+
+impl<'a> MarkerLines<'a> {
+    #[inline]
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            pos: LineColByte(1, 1, 0),
+        }
+    }
+
+    /// Returns the next line, excluding its line break.
+    ///
+    /// The empty line following a trailing line break is excluded.
+    fn peek(&self) -> Option<&'a str> {
+        let line = self.peek_raw()?;
+        Some(line.strip_suffix('\r').unwrap_or(line))
+    }
+
+    /// Returns the next line, excluding its `\n`, but including any `\r`.
+    fn peek_raw(&self) -> Option<&'a str> {
+        let rest = self
+            .text
+            .get(self.pos.2..)
+            .filter(|rest| !rest.is_empty())?;
+        Some(rest.split('\n').next().unwrap_or(rest))
+    }
+}
+
+impl<'a> Iterator for MarkerLines<'a> {
+    type Item = (Span, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let raw = self.peek_raw()?;
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+
+        let start = self.pos;
+        let mut end = start;
+        end.advance_str(line);
+
+        self.pos = LineColByte(start.0 + 1, 1, start.2 + raw.len() + 1);
+
+        Some((Span::new(start, end), line))
+    }
+}
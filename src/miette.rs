@@ -0,0 +1,86 @@
+//! Integration with [`miette`], enabled by the `miette` feature.
+//!
+//! Implements [`SourceCode`] for [`SourceFile`], such that the file's
+//! name is included in reports, and converts between [`Span`] and
+//! [`SourceSpan`].
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{miette::to_span, LineCol, SourceFile};
+//! use miette::{SourceCode, SourceSpan};
+//!
+//! let file = SourceFile::new("main.rs", "fn main() {\n    x\n}\n");
+//!
+//! let span: SourceSpan = file.span(16..17).unwrap().into();
+//! assert_eq!(span, SourceSpan::from(16..17));
+//!
+//! let contents = file.read_span(&span, 0, 0).unwrap();
+//! assert_eq!(contents.name(), Some("main.rs"));
+//!
+//! let span = to_span(&file, span).unwrap();
+//! assert_eq!(span.start_line_col(), LineCol(2, 5));
+//! ```
+
+use ::miette::{MietteError, MietteSpanContents, SourceCode, SourceSpan, SpanContents};
+use alloc::boxed::Box;
+use alloc::string::ToString;
+
+use crate::{ByteRange, FileSpan, SourceFile, Span};
+
+/// Returns the [`Span`] of `span` in `file`.
+///
+/// Returns `None` if `span` is out of bounds or not on [`char`] boundaries.
+#[inline]
+pub fn to_span(file: &SourceFile<'_>, span: SourceSpan) -> Option<Span> {
+    file.span(span.offset()..(span.offset() + span.len()))
+}
+
+impl SourceCode for SourceFile<'_> {
+    fn read_span<'a>(
+        &'a self,
+        span: &SourceSpan,
+        context_lines_before: usize,
+        context_lines_after: usize,
+    ) -> Result<Box<dyn SpanContents<'a> + 'a>, MietteError> {
+        let contents = self
+            .text()
+            .read_span(span, context_lines_before, context_lines_after)?;
+        Ok(Box::new(MietteSpanContents::new_named(
+            self.name().to_string(),
+            contents.data(),
+            *contents.span(),
+            contents.line(),
+            contents.column(),
+            contents.line_count(),
+        )))
+    }
+}
+
+impl From<Span> for SourceSpan {
+    #[inline]
+    fn from(span: Span) -> Self {
+        span.byte_range().into()
+    }
+}
+
+impl From<FileSpan> for SourceSpan {
+    #[inline]
+    fn from(span: FileSpan) -> Self {
+        span.span.into()
+    }
+}
+
+impl From<ByteRange> for SourceSpan {
+    #[inline]
+    fn from(ByteRange(range): ByteRange) -> Self {
+        range.into()
+    }
+}
+
+impl From<SourceSpan> for ByteRange {
+    #[inline]
+    fn from(span: SourceSpan) -> Self {
+        Self(span.offset()..(span.offset() + span.len()))
+    }
+}
@@ -0,0 +1,333 @@
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::{scan, ByteRange, Line, LineCol, LineColByteRange};
+
+pub trait NormalizedNewlinesExt {
+    /// Returns an iterator over [`char`]s and their positions, as if
+    /// every `"\r\n"` and lone `'\r'` were replaced by `'\n'`.
+    ///
+    /// The byte ranges are those of the original text, i.e. the `'\n'`
+    /// produced for a `"\r\n"` has a byte range covering both bytes.
+    /// Lines and columns are those of the normalized text, such that a
+    /// lone `'\r'` also counts as a line break.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineColByteRange, NormalizedNewlinesExt};
+    ///
+    /// let text = "a\r\nb\rc\n🦀";
+    ///
+    /// let normalized = text
+    ///     .normalized_char_positions::<LineColByteRange>()
+    ///     .map(|(LineColByteRange(line, col, range), c)| (line, col, range, c))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     normalized,
+    ///     [
+    ///         (1, 1, 0..1, 'a'),
+    ///         (1, 2, 1..3, '\n'),
+    ///         (2, 1, 3..4, 'b'),
+    ///         (2, 2, 4..5, '\n'),
+    ///         (3, 1, 5..6, 'c'),
+    ///         (3, 2, 6..7, '\n'),
+    ///         (4, 1, 7..11, '🦀'),
+    ///     ],
+    /// );
+    /// ```
+    fn normalized_char_positions<T>(&self) -> NormalizedCharPositions<'_, T>
+    where
+        LineColByteRange: Into<T>;
+}
+
+impl NormalizedNewlinesExt for str {
+    #[inline]
+    fn normalized_char_positions<T>(&self) -> NormalizedCharPositions<'_, T>
+    where
+        LineColByteRange: Into<T>,
+    {
+        NormalizedCharPositions {
+            text: self,
+            byte: 0,
+            pos: LineCol::START,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// An iterator over [`char`]s and their positions, with line endings
+/// normalized to `'\n'`.
+///
+/// See [`NormalizedNewlinesExt::normalized_char_positions()`].
+#[derive(Clone, Debug)]
+pub struct NormalizedCharPositions<'a, T> {
+    text: &'a str,
+    byte: usize,
+    pos: LineCol,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T> NormalizedCharPositions<'a, T> {
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.byte..]
+    }
+}
+
+impl<T> Iterator for NormalizedCharPositions<'_, T>
+where
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.as_str();
+        let (c, len) = match rest.chars().next()? {
+            '\r' if rest[1..].starts_with('\n') => ('\n', 2),
+            '\r' => ('\n', 1),
+            c => (c, c.len_utf8()),
+        };
+
+        let start = self.byte;
+        self.byte += len;
+        let pos = LineColByteRange(self.pos.0, self.pos.1, start..self.byte);
+        self.pos.advance(c);
+
+        Some((pos.into(), c))
+    }
+}
+
+impl<T> FusedIterator for NormalizedCharPositions<'_, T> where Self: Iterator {}
+
+pub trait LineTerminatorsExt {
+    /// Returns an iterator over the lines of the text, split into their
+    /// content and their line terminator, i.e. `"\n"` or `"\r\n"`, along
+    /// with their line number and byte range.
+    ///
+    /// The byte range covers the whole line, including the terminator,
+    /// such that concatenating the content and terminator of every line
+    /// reproduces the text exactly. The last line has no terminator, unless
+    /// the text ends with one, in which case there is no empty last line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{ByteRange, Line, LineTerminatorsExt};
+    ///
+    /// let text = "a\r\n🦀\n\nb";
+    ///
+    /// let lines = text.split_line_terminators().collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     lines,
+    ///     [
+    ///         ("a", Some("\r\n"), Line(1), ByteRange(0..3)),
+    ///         ("🦀", Some("\n"), Line(2), ByteRange(3..8)),
+    ///         ("", Some("\n"), Line(3), ByteRange(8..9)),
+    ///         ("b", None, Line(4), ByteRange(9..10)),
+    ///     ],
+    /// );
+    ///
+    /// let text2 = lines
+    ///     .iter()
+    ///     .flat_map(|&(content, terminator, _, _)| [content, terminator.unwrap_or("")])
+    ///     .collect::<String>();
+    /// assert_eq!(text2, text);
+    /// ```
+    fn split_line_terminators(&self) -> SplitLineTerminators<'_>;
+}
+
+impl LineTerminatorsExt for str {
+    #[inline]
+    fn split_line_terminators(&self) -> SplitLineTerminators<'_> {
+        SplitLineTerminators {
+            text: self,
+            byte: 0,
+            line: 1,
+        }
+    }
+}
+
+/// An iterator over lines and their line terminators.
+///
+/// See [`LineTerminatorsExt::split_line_terminators()`].
+#[derive(Clone, Debug)]
+pub struct SplitLineTerminators<'a> {
+    text: &'a str,
+    byte: usize,
+    line: usize,
+}
+
+impl<'a> SplitLineTerminators<'a> {
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.byte..]
+    }
+}
+
+impl<'a> Iterator for SplitLineTerminators<'a> {
+    type Item = (&'a str, Option<&'a str>, Line, ByteRange);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.as_str();
+        if rest.is_empty() {
+            return None;
+        }
+
+        let (content, terminator) = match scan::newlines(rest).next() {
+            Some(i) if rest[..i].ends_with('\r') => (&rest[..(i - 1)], Some(&rest[(i - 1)..=i])),
+            Some(i) => (&rest[..i], Some(&rest[i..=i])),
+            None => (rest, None),
+        };
+        let len = content.len() + terminator.map_or(0, str::len);
+
+        let start = self.byte;
+        self.byte += len;
+        let line = Line(self.line);
+        self.line += 1;
+
+        Some((content, terminator, line, ByteRange(start..self.byte)))
+    }
+}
+
+impl FusedIterator for SplitLineTerminators<'_> {}
+
+pub trait UnicodeLineBreaksExt {
+    /// Returns an iterator over [`char`]s and their positions, where lines
+    /// are broken following the Unicode newline guidelines, i.e. the
+    /// mandatory breaks of [UAX #14], instead of only at `'\n'`.
+    ///
+    /// This matches the line numbering of e.g. Java's `\R` and Python's
+    /// `str.splitlines()`, except that Python also breaks at `'\x1C'`,
+    /// `'\x1D'`, and `'\x1E'`.
+    ///
+    /// Lines are broken after each of:
+    ///
+    /// | `char` | Name |
+    /// |:---|:---|
+    /// | `'\n'` | Line Feed (LF) |
+    /// | `'\u{B}'` | Vertical Tab (VT) |
+    /// | `'\u{C}'` | Form Feed (FF) |
+    /// | `'\r'` | Carriage Return (CR), unless followed by `'\n'` |
+    /// | `'\u{85}'` | Next Line (NEL) |
+    /// | `'\u{2028}'` | Line Separator (LS) |
+    /// | `'\u{2029}'` | Paragraph Separator (PS) |
+    ///
+    /// Such that `"\r\n"` is a single line break, where the `'\r'`
+    /// and `'\n'` are both part of the line they end.
+    ///
+    /// [UAX #14]: https://www.unicode.org/reports/tr14/#BK
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineCol, UnicodeLineBreaksExt};
+    ///
+    /// let text = "a\nb\u{B}c\u{C}d\re\r\nf\u{85}g\u{2028}h\u{2029}i";
+    ///
+    /// let positions = text
+    ///     .unicode_char_positions::<LineCol>()
+    ///     .map(|(LineCol(line, col), c)| (line, col, c))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     positions,
+    ///     [
+    ///         (1, 1, 'a'),
+    ///         (1, 2, '\n'),
+    ///         (2, 1, 'b'),
+    ///         (2, 2, '\u{B}'),
+    ///         (3, 1, 'c'),
+    ///         (3, 2, '\u{C}'),
+    ///         (4, 1, 'd'),
+    ///         (4, 2, '\r'),
+    ///         (5, 1, 'e'),
+    ///         (5, 2, '\r'),
+    ///         (5, 3, '\n'),
+    ///         (6, 1, 'f'),
+    ///         (6, 2, '\u{85}'),
+    ///         (7, 1, 'g'),
+    ///         (7, 2, '\u{2028}'),
+    ///         (8, 1, 'h'),
+    ///         (8, 2, '\u{2029}'),
+    ///         (9, 1, 'i'),
+    ///     ],
+    /// );
+    ///
+    /// // Other control and space characters do not break lines
+    /// let text = "a\t\u{1C}\u{A0}\u{200B}b\r\r\n";
+    /// let lines = text
+    ///     .unicode_char_positions::<LineCol>()
+    ///     .map(|(LineCol(line, _), _)| line)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(lines, [1, 1, 1, 1, 1, 1, 1, 2, 2]);
+    /// ```
+    fn unicode_char_positions<T>(&self) -> UnicodeCharPositions<'_, T>
+    where
+        LineColByteRange: Into<T>;
+}
+
+impl UnicodeLineBreaksExt for str {
+    #[inline]
+    fn unicode_char_positions<T>(&self) -> UnicodeCharPositions<'_, T>
+    where
+        LineColByteRange: Into<T>,
+    {
+        UnicodeCharPositions {
+            text: self,
+            byte: 0,
+            pos: LineCol::START,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// An iterator over [`char`]s and their positions, with lines broken
+/// following the Unicode newline guidelines.
+///
+/// See [`UnicodeLineBreaksExt::unicode_char_positions()`].
+#[derive(Clone, Debug)]
+pub struct UnicodeCharPositions<'a, T> {
+    text: &'a str,
+    byte: usize,
+    pos: LineCol,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T> UnicodeCharPositions<'a, T> {
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.byte..]
+    }
+}
+
+impl<T> Iterator for UnicodeCharPositions<'_, T>
+where
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.as_str();
+        let c = rest.chars().next()?;
+
+        let start = self.byte;
+        self.byte += c.len_utf8();
+        let pos = LineColByteRange(self.pos.0, self.pos.1, start..self.byte);
+
+        let line_break = match c {
+            '\n' | '\u{B}' | '\u{C}' | '\u{85}' | '\u{2028}' | '\u{2029}' => true,
+            '\r' => !rest[1..].starts_with('\n'),
+            _ => false,
+        };
+        self.pos.advance(if line_break { '\n' } else { c });
+
+        Some((pos.into(), c))
+    }
+}
+
+impl<T> FusedIterator for UnicodeCharPositions<'_, T> where Self: Iterator {}
@@ -0,0 +1,198 @@
+//! Integration with [`nom`], enabled by the `nom` feature.
+//!
+//! [`PositionedInput`] wraps a `&str` and implements [`nom`]'s input traits,
+//! while tracking the line, column, and byte position of the remaining
+//! input, with columns counted in [`char`]s like the rest of this crate.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{nom::PositionedInput, LineCol, LineColByte};
+//! use nom::bytes::complete::{tag, take_until};
+//! use nom::character::complete::multispace0;
+//! use nom::{IResult, Parser};
+//!
+//! fn hello(input: PositionedInput<'_>) -> IResult<PositionedInput<'_>, PositionedInput<'_>> {
+//!     let (input, _) = take_until("Hello").parse(input)?;
+//!     tag("Hello").parse(input)
+//! }
+//!
+//! let input = PositionedInput::new("🦀🦀\n  Hello World");
+//! let (rest, hello) = hello(input).unwrap();
+//!
+//! assert_eq!(hello.fragment(), &"Hello");
+//! assert_eq!(hello.position(), LineColByte(2, 3, 11));
+//!
+//! let span = hello.span();
+//! assert_eq!(span.end_line_col(), LineCol(2, 8));
+//!
+//! let (rest, _) = multispace0::<_, ()>(rest).unwrap();
+//! assert_eq!(rest.position(), LineColByte(2, 9, 17));
+//! ```
+
+use core::fmt;
+use core::str::{CharIndices, Chars, FromStr};
+
+use ::nom::{AsBytes, Compare, CompareResult, FindSubstring, Input, Needed, Offset, ParseTo};
+
+use crate::{LineCol, LineColByte, Span};
+
+/// A `&str` input for [`nom`] parsers, which tracks the line,
+/// column, and byte position of the remaining input.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct PositionedInput<'a> {
+    fragment: &'a str,
+    pos: LineColByte,
+}
+
+impl<'a> PositionedInput<'a> {
+    /// Returns the input for the whole `text`, starting at line 1, column 1.
+    #[inline]
+    pub const fn new(text: &'a str) -> Self {
+        Self::with_position(text, LineColByte(1, 1, 0))
+    }
+
+    /// Returns the input for `fragment`, starting at `pos`.
+    #[inline]
+    pub const fn with_position(fragment: &'a str, pos: LineColByte) -> Self {
+        Self { fragment, pos }
+    }
+
+    /// Returns the remaining input.
+    #[inline]
+    pub const fn fragment(&self) -> &&'a str {
+        &self.fragment
+    }
+
+    /// Returns the position of the start of the remaining input.
+    #[inline]
+    pub const fn position(&self) -> LineColByte {
+        self.pos
+    }
+
+    /// Returns the line and column of the start of the remaining input.
+    #[inline]
+    pub const fn line_col(&self) -> LineCol {
+        LineCol(self.pos.0, self.pos.1)
+    }
+
+    /// Returns the [`Span`] of the remaining input.
+    #[inline]
+    pub fn span(&self) -> Span {
+        let mut end = self.pos;
+        end.advance_str(self.fragment);
+        Span::new(self.pos, end)
+    }
+
+    /// Returns the [`Span`] from the start of `self`
+    /// to the start of `rest`.
+    ///
+    /// `rest` must be a remainder of `self`.
+    #[inline]
+    pub fn span_to(&self, rest: &Self) -> Span {
+        Span::new(self.pos, rest.pos)
+    }
+
+    /// Returns the input with the first `index` bytes removed.
+    fn skip(&self, index: usize) -> Self {
+        let mut pos = self.pos;
+        pos.advance_str(&self.fragment[..index]);
+        Self::with_position(&self.fragment[index..], pos)
+    }
+}
+
+impl<'a> Input for PositionedInput<'a> {
+    type Item = char;
+    type Iter = Chars<'a>;
+    type IterIndices = CharIndices<'a>;
+
+    #[inline]
+    fn input_len(&self) -> usize {
+        self.fragment.len()
+    }
+
+    #[inline]
+    fn take(&self, index: usize) -> Self {
+        Self::with_position(&self.fragment[..index], self.pos)
+    }
+
+    #[inline]
+    fn take_from(&self, index: usize) -> Self {
+        self.skip(index)
+    }
+
+    #[inline]
+    fn take_split(&self, index: usize) -> (Self, Self) {
+        (self.take_from(index), self.take(index))
+    }
+
+    #[inline]
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        self.fragment.position(predicate)
+    }
+
+    #[inline]
+    fn iter_elements(&self) -> Self::Iter {
+        self.fragment.chars()
+    }
+
+    #[inline]
+    fn iter_indices(&self) -> Self::IterIndices {
+        self.fragment.char_indices()
+    }
+
+    #[inline]
+    fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+        self.fragment.slice_index(count)
+    }
+}
+
+impl<'b> Compare<&'b str> for PositionedInput<'_> {
+    #[inline]
+    fn compare(&self, t: &'b str) -> CompareResult {
+        self.fragment.compare(t)
+    }
+
+    #[inline]
+    fn compare_no_case(&self, t: &'b str) -> CompareResult {
+        self.fragment.compare_no_case(t)
+    }
+}
+
+impl<'b> FindSubstring<&'b str> for PositionedInput<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'b str) -> Option<usize> {
+        self.fragment.find_substring(substr)
+    }
+}
+
+impl Offset for PositionedInput<'_> {
+    #[inline]
+    fn offset(&self, second: &Self) -> usize {
+        second.pos.2 - self.pos.2
+    }
+}
+
+impl AsBytes for PositionedInput<'_> {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self.fragment.as_bytes()
+    }
+}
+
+impl<R: FromStr> ParseTo<R> for PositionedInput<'_> {
+    #[inline]
+    fn parse_to(&self) -> Option<R> {
+        self.fragment.parse_to()
+    }
+}
+
+impl fmt::Display for PositionedInput<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fragment.fmt(f)
+    }
+}
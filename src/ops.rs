@@ -0,0 +1,83 @@
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+
+use crate::{Col, Line};
+
+macro_rules! impl_ops {
+    ($ty:ident, $name:literal) => {
+        impl $ty {
+            /// Returns `self + n`, or `None` on overflow.
+            #[inline]
+            pub const fn checked_add(self, n: usize) -> Option<Self> {
+                match self.0.checked_add(n) {
+                    Some(x) => Some(Self(x)),
+                    None => None,
+                }
+            }
+
+            #[doc = concat!("Returns `self - n`, or `None` if the ", $name, " would be less than 1.")]
+            #[inline]
+            pub const fn checked_sub(self, n: usize) -> Option<Self> {
+                match self.0.checked_sub(n) {
+                    Some(x) if x >= 1 => Some(Self(x)),
+                    _ => None,
+                }
+            }
+
+            /// Returns `self + n`, saturating at `usize::MAX`.
+            #[inline]
+            pub const fn saturating_add(self, n: usize) -> Self {
+                Self(self.0.saturating_add(n))
+            }
+
+            /// Returns `self - n`, saturating at 1.
+            #[inline]
+            pub const fn saturating_sub(self, n: usize) -> Self {
+                match self.checked_sub(n) {
+                    Some(x) => x,
+                    None => Self(1),
+                }
+            }
+        }
+
+        impl Add<usize> for $ty {
+            type Output = Self;
+
+            /// # Panics
+            ///
+            /// Panics on overflow.
+            #[inline]
+            fn add(self, n: usize) -> Self {
+                self.checked_add(n).expect(concat!($name, " overflowed"))
+            }
+        }
+
+        impl Sub<usize> for $ty {
+            type Output = Self;
+
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if the ", $name, " would be less than 1.")]
+            #[inline]
+            fn sub(self, n: usize) -> Self {
+                self.checked_sub(n).expect(concat!($name, " must be greater than 0"))
+            }
+        }
+
+        impl AddAssign<usize> for $ty {
+            #[inline]
+            fn add_assign(&mut self, n: usize) {
+                *self = *self + n;
+            }
+        }
+
+        impl SubAssign<usize> for $ty {
+            #[inline]
+            fn sub_assign(&mut self, n: usize) {
+                *self = *self - n;
+            }
+        }
+    };
+}
+
+impl_ops!(Line, "line");
+impl_ops!(Col, "column");
@@ -0,0 +1,142 @@
+use core::fmt;
+use core::ops::Range;
+use core::str::FromStr;
+
+use crate::{LineCol, LineColByte, Span};
+
+/// An error which can be returned when parsing a [`LineCol`] or [`Span`],
+/// see [`LineCol::from_str()`], [`LineCol::parse_range()`],
+/// and [`Span::from_str()`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct ParseLineColError {
+    kind: ErrorKind,
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+enum ErrorKind {
+    Invalid,
+    Zero,
+    Inverted,
+}
+
+impl ParseLineColError {
+    #[inline]
+    const fn new(kind: ErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for ParseLineColError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.kind {
+            ErrorKind::Invalid => "invalid line and column, expected `line:col`",
+            ErrorKind::Zero => "line and column must be greater than 0",
+            ErrorKind::Inverted => "end of range is before its start",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseLineColError {}
+
+impl FromStr for LineCol {
+    type Err = ParseLineColError;
+
+    /// Parses `line:col`, `line.col`, or `(line,col)`,
+    /// where both `line` and `col` are 1-indexed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::LineCol;
+    ///
+    /// assert_eq!("12:5".parse(), Ok(LineCol(12, 5)));
+    /// assert_eq!("12.5".parse(), Ok(LineCol(12, 5)));
+    /// assert_eq!("(12, 5)".parse(), Ok(LineCol(12, 5)));
+    ///
+    /// assert!("12".parse::<LineCol>().is_err());
+    /// assert!("0:5".parse::<LineCol>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (line, col) = match s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+            Some(s) => s
+                .split_once(',')
+                .map(|(line, col)| (line.trim(), col.trim())),
+            None => s.split_once(':').or_else(|| s.split_once('.')),
+        }
+        .ok_or(ParseLineColError::new(ErrorKind::Invalid))?;
+
+        Ok(LineCol(parse_one_indexed(line)?, parse_one_indexed(col)?))
+    }
+}
+
+impl LineCol {
+    /// Parses `start-end`, where `start` and `end` are in any of
+    /// the formats accepted by [`LineCol::from_str()`].
+    ///
+    /// Returns an error if `end` is before `start`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::LineCol;
+    ///
+    /// assert_eq!(LineCol::parse_range("12:5-14:2"), Ok(LineCol(12, 5)..LineCol(14, 2)));
+    /// assert_eq!(LineCol::parse_range("(12,5)-(12,5)"), Ok(LineCol(12, 5)..LineCol(12, 5)));
+    ///
+    /// assert!(LineCol::parse_range("14:2-12:5").is_err());
+    /// ```
+    pub fn parse_range(s: &str) -> Result<Range<Self>, ParseLineColError> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or(ParseLineColError::new(ErrorKind::Invalid))?;
+        let start: Self = start.parse()?;
+        let end: Self = end.parse()?;
+
+        if end < start {
+            return Err(ParseLineColError::new(ErrorKind::Inverted));
+        }
+        Ok(start..end)
+    }
+}
+
+impl FromStr for Span {
+    type Err = ParseLineColError;
+
+    /// Parses `start-end` as [`LineCol::parse_range()`], i.e. the format
+    /// produced by [`Span`]'s `Display` implementation.
+    ///
+    /// The text is unknown, so the byte positions of the span are `0`.
+    /// Use e.g. `SourceFile::byte_of()` to resolve them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineCol, LineColByte, Span};
+    ///
+    /// let span = Span::new(LineColByte(12, 5, 0), LineColByte(14, 2, 0));
+    /// assert_eq!(span.to_string().parse(), Ok(span));
+    ///
+    /// let span: Span = "(12,5)-(14,2)".parse().unwrap();
+    /// assert_eq!(span.start_line_col(), LineCol(12, 5));
+    /// assert_eq!(span.end_line_col(), LineCol(14, 2));
+    /// assert_eq!(span.byte_range(), 0..0);
+    ///
+    /// assert!("14:2-12:5".parse::<Span>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Range { start, end } = LineCol::parse_range(s)?;
+        Ok(Span::new(
+            LineColByte(start.0, start.1, 0),
+            LineColByte(end.0, end.1, 0),
+        ))
+    }
+}
+
+fn parse_one_indexed(s: &str) -> Result<usize, ParseLineColError> {
+    match s.parse() {
+        Ok(0) => Err(ParseLineColError::new(ErrorKind::Zero)),
+        Ok(n) => Ok(n),
+        Err(_) => Err(ParseLineColError::new(ErrorKind::Invalid)),
+    }
+}
@@ -0,0 +1,106 @@
+use core::cell::OnceCell;
+use core::ops::{Index, Range};
+
+use crate::{LineCol, LineIndex};
+
+/// A `&str` which can be indexed by lines and columns.
+///
+/// The [`LineIndex`] is built lazily, the first time it is needed.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{LineCol, PositionedStr};
+///
+/// let src = PositionedStr::new("Hello 👋\nlet 🦀 = 1;\n");
+///
+/// assert_eq!(&src[LineCol(2, 5)], "🦀");
+/// assert_eq!(&src[LineCol(2, 1)..LineCol(2, 6)], "let 🦀");
+///
+/// assert_eq!(src.get(LineCol(3, 1)), None);
+/// assert_eq!(src.get_range(LineCol(2, 9)..LineCol(3, 1)), Some("1;\n"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PositionedStr<'a> {
+    text: &'a str,
+    index: OnceCell<LineIndex>,
+}
+
+impl<'a> PositionedStr<'a> {
+    #[inline]
+    pub const fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            index: OnceCell::new(),
+        }
+    }
+
+    #[inline]
+    pub const fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the [`LineIndex`] of the text, building it if needed.
+    #[inline]
+    pub fn line_index(&self) -> &LineIndex {
+        self.index.get_or_init(|| LineIndex::new(self.text))
+    }
+
+    /// Returns the byte position of `pos`.
+    ///
+    /// See [`LineIndex::byte_of()`].
+    #[inline]
+    pub fn byte_of(&self, pos: LineCol) -> Option<usize> {
+        self.line_index().byte_of(self.text, pos)
+    }
+
+    /// Returns the [`char`] at `pos` as a `&str`.
+    ///
+    /// Returns `None` if `pos` is out of bounds.
+    pub fn get(&self, pos: LineCol) -> Option<&'a str> {
+        let start = self.byte_of(pos)?;
+        let c = self.text[start..].chars().next()?;
+        Some(&self.text[start..(start + c.len_utf8())])
+    }
+
+    /// Returns the text between `range.start` (inclusive)
+    /// and `range.end` (exclusive).
+    ///
+    /// Returns `None` if `range` is inverted or out of bounds.
+    pub fn get_range(&self, range: Range<LineCol>) -> Option<&'a str> {
+        let start = self.byte_of(range.start)?;
+        let end = self.byte_of(range.end)?;
+        self.text.get(start..end)
+    }
+}
+
+impl Index<LineCol> for PositionedStr<'_> {
+    type Output = str;
+
+    /// # Panics
+    ///
+    /// Panics if `pos` is out of bounds.
+    #[inline]
+    fn index(&self, pos: LineCol) -> &str {
+        match self.get(pos) {
+            Some(s) => s,
+            None => panic!("position {pos} is out of bounds"),
+        }
+    }
+}
+
+impl Index<Range<LineCol>> for PositionedStr<'_> {
+    type Output = str;
+
+    /// # Panics
+    ///
+    /// Panics if `range` is inverted or out of bounds.
+    #[inline]
+    fn index(&self, range: Range<LineCol>) -> &str {
+        let (start, end) = (range.start, range.end);
+        match self.get_range(range) {
+            Some(s) => s,
+            None => panic!("range {start}..{end} is out of bounds"),
+        }
+    }
+}
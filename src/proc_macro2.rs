@@ -0,0 +1,60 @@
+//! Integration with [`proc_macro2`], enabled by the `proc-macro2` feature.
+//!
+//! [`LineColumn`] has a 1-indexed line like [`LineCol`], but a 0-indexed
+//! column, while both count columns in [`char`]s.
+//!
+//! Resolving a [`proc_macro2::Span`] requires its `span-locations` feature,
+//! which is enabled by this crate's `proc-macro2` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{proc_macro2::resolve_span, LineCol, SourceFile};
+//! use proc_macro2::{LineColumn, TokenStream, TokenTree};
+//!
+//! assert_eq!(LineCol::from(LineColumn { line: 1, column: 0 }), LineCol(1, 1));
+//!
+//! let file = SourceFile::new("main.rs", "fn main() {\n    \"🦀\";\n}\n");
+//! let tokens: TokenStream = file.text().parse().unwrap();
+//!
+//! let Some(TokenTree::Group(body)) = tokens.into_iter().nth(3) else {
+//!     unreachable!();
+//! };
+//!
+//! let span = resolve_span(&file, body.span()).unwrap();
+//! assert_eq!(span.start_line_col(), LineCol(1, 11));
+//! assert_eq!(span.end_line_col(), LineCol(3, 2));
+//! assert_eq!(span.byte_range(), 10..25);
+//! ```
+
+use ::proc_macro2::LineColumn;
+
+use crate::{LineCol, SourceFile, Span};
+
+impl From<LineColumn> for LineCol {
+    #[inline]
+    fn from(pos: LineColumn) -> Self {
+        Self(pos.line, pos.column + 1)
+    }
+}
+
+impl From<LineCol> for LineColumn {
+    #[inline]
+    fn from(LineCol(line, col): LineCol) -> Self {
+        Self {
+            line,
+            column: col.saturating_sub(1),
+        }
+    }
+}
+
+/// Returns the [`Span`] of `span` in `file`.
+///
+/// `file` must contain the text `span` was parsed from.
+///
+/// Returns `None` if `span` is out of bounds.
+pub fn resolve_span(file: &SourceFile<'_>, span: ::proc_macro2::Span) -> Option<Span> {
+    let start = file.byte_of(span.start().into())?;
+    let end = file.byte_of(span.end().into())?;
+    file.span(start..end)
+}
@@ -0,0 +1,49 @@
+//! Integration with [`proptest`], enabled by the `proptest` feature.
+//!
+//! [`strategy_for()`] and [`span_strategy_for()`] only produce positions
+//! that exist in a given text, unlike e.g. generating arbitrary integers.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{proptest::span_strategy_for, SpanResolver};
+//! use proptest::prelude::*;
+//!
+//! let text = "Hello 👋\nWorld 🌏\n";
+//!
+//! proptest!(|(span in span_strategy_for(text))| {
+//!     let mut resolver = SpanResolver::new(text);
+//!     prop_assert_eq!(resolver.span(span.byte_range()), Some(span));
+//! });
+//! ```
+
+use std::vec::Vec;
+
+use ::proptest::sample::{select, Select};
+use ::proptest::strategy::Strategy;
+
+use crate::{CharPositionsExt, LineColByte, Span};
+
+/// Returns a strategy producing the positions in `text`, i.e. the
+/// position of any of its [`char`]s, or the end of `text`.
+pub fn strategy_for(text: &str) -> Select<LineColByte> {
+    select(positions(text))
+}
+
+/// Returns a strategy producing [`Span`]s in `text`, which may be empty.
+///
+/// See [`strategy_for()`].
+pub fn span_strategy_for(text: &str) -> impl Strategy<Value = Span> {
+    let positions = positions(text);
+    (select(positions.clone()), select(positions)).prop_map(|(a, b)| Span::new(a.min(b), a.max(b)))
+}
+
+fn positions(text: &str) -> Vec<LineColByte> {
+    let mut end = LineColByte(1, 1, 0);
+    end.advance_str(text);
+
+    text.char_positions::<LineColByte>()
+        .map(|(pos, _c)| pos)
+        .chain([end])
+        .collect()
+}
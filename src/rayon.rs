@@ -0,0 +1,134 @@
+//! Integration with [`rayon`], enabled by the `rayon` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::rayon::ParPositionsExt;
+//! use char_positions::LineColByteRange;
+//! use rayon::prelude::*;
+//!
+//! let text = "Hello 👋\nWorld 🌏\n🦀🦀";
+//!
+//! let lines = text
+//!     .par_lines_with_positions()
+//!     .map(|line| (line.start().line(), line.start().byte_start(), line.as_str()))
+//!     .collect::<Vec<_>>();
+//! assert_eq!(
+//!     lines,
+//!     [(1, 0, "Hello 👋\n"), (2, 11, "World 🌏\n"), (3, 22, "🦀🦀")],
+//! );
+//!
+//! let crabs = text
+//!     .par_char_positions::<LineColByteRange>()
+//!     .filter(|(_, c)| *c == '🦀')
+//!     .map(|(LineColByteRange(line, col, range), _)| (line, col, range))
+//!     .collect::<Vec<_>>();
+//! assert_eq!(crabs, [(3, 1, 22..26), (3, 2, 26..30)]);
+//! ```
+
+use alloc::vec::Vec;
+use core::iter;
+
+use ::rayon::iter::{FlatMapIter, IntoParallelIterator, ParallelIterator};
+use ::rayon::vec::IntoIter;
+
+pub use crate::LineCharPositions;
+use crate::{scan, LineColByte, LineColByteRange};
+
+/// A parallel iterator over the lines of a text and their positions.
+///
+/// See [`ParPositionsExt::par_lines_with_positions()`].
+pub type ParLines<'a> = IntoIter<PositionedLine<'a>>;
+
+/// A parallel iterator over the [`char`]s of a text and their positions.
+///
+/// See [`ParPositionsExt::par_char_positions()`].
+pub type ParCharPositions<'a, T> =
+    FlatMapIter<ParLines<'a>, fn(PositionedLine<'a>) -> LineCharPositions<'a, T>>;
+
+pub trait ParPositionsExt {
+    /// Returns a parallel iterator over the lines of the text, each
+    /// including its trailing `'\n'`, if any, along with the position
+    /// of the start of the line.
+    ///
+    /// The line breaks are found up front, after which the lines can
+    /// be processed in parallel. An empty text has no lines, and a text
+    /// ending with `'\n'` has no empty last line.
+    fn par_lines_with_positions(&self) -> ParLines<'_>;
+
+    /// Returns a parallel iterator over [`char`]s and their positions,
+    /// where each line is processed in parallel.
+    ///
+    /// See [`CharPositionsExt::char_positions()`].
+    fn par_char_positions<T>(&self) -> ParCharPositions<'_, T>
+    where
+        T: Send,
+        LineColByteRange: Into<T>;
+}
+
+impl ParPositionsExt for str {
+    fn par_lines_with_positions(&self) -> ParLines<'_> {
+        let mut starts = iter::once(0)
+            .chain(scan::newlines(self).map(|i| i + 1))
+            .collect::<Vec<_>>();
+        if starts.last() == Some(&self.len()) {
+            starts.pop();
+        }
+
+        let lines = starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                let end = starts.get(i + 1).copied().unwrap_or(self.len());
+                PositionedLine {
+                    start: LineColByte(i + 1, 1, start),
+                    text: &self[start..end],
+                }
+            })
+            .collect::<Vec<_>>();
+        lines.into_par_iter()
+    }
+
+    #[inline]
+    fn par_char_positions<T>(&self) -> ParCharPositions<'_, T>
+    where
+        T: Send,
+        LineColByteRange: Into<T>,
+    {
+        self.par_lines_with_positions()
+            .flat_map_iter(PositionedLine::char_positions)
+    }
+}
+
+/// A line of text and the position of its start.
+///
+/// See [`ParPositionsExt::par_lines_with_positions()`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct PositionedLine<'a> {
+    start: LineColByte,
+    text: &'a str,
+}
+
+impl<'a> PositionedLine<'a> {
+    /// Returns the position of the start of the line.
+    #[inline]
+    pub fn start(&self) -> LineColByte {
+        self.start
+    }
+
+    /// Returns the line, including its trailing `'\n'`, if any.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns an iterator over the [`char`]s of the line and
+    /// their absolute positions in the whole text.
+    #[inline]
+    pub fn char_positions<T>(self) -> LineCharPositions<'a, T>
+    where
+        LineColByteRange: Into<T>,
+    {
+        LineCharPositions::new(self.text, self.start)
+    }
+}
@@ -0,0 +1,288 @@
+use std::boxed::Box;
+use std::io::{self, BufRead, ErrorKind, Read};
+use std::string::String;
+use std::vec;
+use std::vec::Vec;
+
+use crate::utf8::{self, Decoded};
+use crate::{ByteRange, Line, LineColByte};
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// An iterator over [`char`]s and their positions, incrementally decoded
+/// from UTF-8 read from any [`Read`], without reading everything into memory.
+///
+/// Byte positions are relative to the start of the stream. `char`s split
+/// across reads are handled transparently.
+///
+/// Invalid UTF-8 produces an [`ErrorKind::InvalidData`] error, after which
+/// iteration can continue past the invalid bytes. Each invalid sequence
+/// counts as a single column, see [`CheckedUtf8Ext::char_positions_checked()`].
+///
+/// Reads are buffered internally, so wrapping `reader` in a
+/// [`BufReader`](std::io::BufReader) is unnecessary.
+///
+/// [`CheckedUtf8Ext::char_positions_checked()`]: crate::CheckedUtf8Ext::char_positions_checked
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{CharPositionsReader, LineColByte};
+///
+/// let reader = "Hello 👋\nWorld 🌏\n".as_bytes();
+///
+/// let mut iter = CharPositionsReader::new(reader)
+///     .map(Result::unwrap)
+///     .skip(6);
+///
+/// assert_eq!(iter.next(), Some((LineColByte(1, 7, 6), '👋')));
+/// assert_eq!(iter.next(), Some((LineColByte(1, 8, 10), '\n')));
+/// assert_eq!(iter.next(), Some((LineColByte(2, 1, 11), 'W')));
+/// ```
+///
+/// Invalid UTF-8:
+///
+/// ```
+/// use char_positions::{CharPositionsReader, LineColByte};
+///
+/// let reader = &b"a\xFFb\xF0\x9F"[..];
+/// let mut iter = CharPositionsReader::with_capacity(1, reader);
+///
+/// assert_eq!(iter.next().unwrap().unwrap(), (LineColByte(1, 1, 0), 'a'));
+/// assert!(iter.next().unwrap().is_err());
+/// assert_eq!(iter.next().unwrap().unwrap(), (LineColByte(1, 3, 2), 'b'));
+/// assert!(iter.next().unwrap().is_err());
+/// assert!(iter.next().is_none());
+/// ```
+#[derive(Debug)]
+pub struct CharPositionsReader<R> {
+    reader: R,
+    buf: DecodeBuffer,
+}
+
+impl<R: Read> CharPositionsReader<R> {
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, reader)
+    }
+
+    /// Creates a new `CharPositionsReader` with a buffer
+    /// of at least `capacity` bytes.
+    #[inline]
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        Self {
+            reader,
+            buf: DecodeBuffer::with_capacity(capacity),
+        }
+    }
+}
+
+impl<R> CharPositionsReader<R> {
+    /// Returns the position of the next [`char`].
+    #[inline]
+    pub fn position(&self) -> LineColByte {
+        self.buf.pos
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Any buffered, but not yet iterated, bytes are lost.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for CharPositionsReader<R> {
+    type Item = io::Result<(LineColByte, char)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.buf.decode_next() {
+                return item;
+            }
+
+            match self.reader.read(self.buf.spare()) {
+                Ok(n) => self.buf.filled(n),
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// An iterator over the lines of a [`BufRead`], along with their 1-indexed
+/// line number, and their byte range relative to the start of the stream.
+///
+/// Like [`BufRead::lines()`], the lines do not include their `"\n"` or
+/// `"\r\n"`, and neither does the byte range. Only one line is held
+/// in memory at a time.
+///
+/// A line that is not valid UTF-8 produces an [`ErrorKind::InvalidData`]
+/// error, after which iteration can continue with the next line.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{ByteRange, Line, PositionedLines};
+///
+/// let reader = "Hello 👋\r\nWorld 🌏\n🦀🦀".as_bytes();
+///
+/// let mut lines = PositionedLines::new(reader).map(Result::unwrap);
+///
+/// assert_eq!(lines.next(), Some((Line(1), ByteRange(0..10), "Hello 👋".to_owned())));
+/// assert_eq!(lines.next(), Some((Line(2), ByteRange(12..22), "World 🌏".to_owned())));
+/// assert_eq!(lines.next(), Some((Line(3), ByteRange(23..31), "🦀🦀".to_owned())));
+/// assert_eq!(lines.next(), None);
+/// ```
+#[derive(Debug)]
+pub struct PositionedLines<R> {
+    reader: R,
+    /// The line number of the last line.
+    line: usize,
+    /// The start byte position of the next line.
+    byte: usize,
+}
+
+impl<R: BufRead> PositionedLines<R> {
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 0,
+            byte: 0,
+        }
+    }
+}
+
+impl<R> PositionedLines<R> {
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> Iterator for PositionedLines<R> {
+    type Item = io::Result<(Line, ByteRange, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buf = Vec::new();
+        match self.reader.read_until(b'\n', &mut buf) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(err) => return Some(Err(err)),
+        }
+
+        let start = self.byte;
+        self.byte += buf.len();
+        self.line += 1;
+
+        if buf.ends_with(b"\n") {
+            buf.pop();
+            if buf.ends_with(b"\r") {
+                buf.pop();
+            }
+        }
+        let end = start + buf.len();
+
+        match String::from_utf8(buf) {
+            Ok(text) => Some(Ok((Line(self.line), ByteRange(start..end), text))),
+            Err(_) => Some(Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            ))),
+        }
+    }
+}
+
+/// A buffer of bytes read, but not yet decoded.
+#[derive(Debug)]
+pub(crate) struct DecodeBuffer {
+    buf: Box<[u8]>,
+    /// The start of the undecoded bytes in `buf`.
+    start: usize,
+    /// The end of the undecoded bytes in `buf`.
+    end: usize,
+    eof: bool,
+    /// The position of the next [`char`].
+    pub(crate) pos: LineColByte,
+}
+
+impl DecodeBuffer {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        // Must at least fit any `char`
+        let capacity = capacity.max(4);
+        Self {
+            buf: vec![0; capacity].into_boxed_slice(),
+            start: 0,
+            end: 0,
+            eof: false,
+            pos: LineColByte(1, 1, 0),
+        }
+    }
+
+    /// Decodes the next [`char`].
+    ///
+    /// Returns `None` if more bytes must be read first,
+    /// see [`spare()`](Self::spare).
+    pub(crate) fn decode_next(&mut self) -> Option<Option<io::Result<(LineColByte, char)>>> {
+        match utf8::decode(&self.buf[self.start..self.end]) {
+            Decoded::Char(c) => {
+                let pos = self.pos;
+                self.start += c.len_utf8();
+                self.pos.advance(c);
+                Some(Some(Ok((pos, c))))
+            }
+            Decoded::Invalid(n) => Some(Some(Err(
+                self.skip_invalid(n, "stream did not contain valid UTF-8")
+            ))),
+            Decoded::Incomplete if self.eof => {
+                let n = self.end - self.start;
+                if n == 0 {
+                    return Some(None);
+                }
+                Some(Some(Err(self.skip_invalid(
+                    n,
+                    "stream ended with an incomplete UTF-8 sequence",
+                ))))
+            }
+            Decoded::Incomplete => None,
+        }
+    }
+
+    /// Returns the unused part of the buffer to read into,
+    /// followed by [`filled()`](Self::filled).
+    pub(crate) fn spare(&mut self) -> &mut [u8] {
+        self.buf.copy_within(self.start..self.end, 0);
+        self.end -= self.start;
+        self.start = 0;
+        &mut self.buf[self.end..]
+    }
+
+    /// Marks `n` bytes of [`spare()`](Self::spare) as read,
+    /// where `0` means the end of the stream.
+    pub(crate) fn filled(&mut self, n: usize) {
+        if n == 0 {
+            self.eof = true;
+        }
+        self.end += n;
+    }
+
+    /// Consumes `n` invalid bytes, counting as a single column.
+    fn skip_invalid(&mut self, n: usize, msg: &'static str) -> io::Error {
+        self.start += n;
+        self.pos.1 += 1;
+        self.pos.2 += n;
+        io::Error::new(ErrorKind::InvalidData, msg)
+    }
+}
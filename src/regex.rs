@@ -0,0 +1,170 @@
+//! Integration with [`regex`], enabled by the `regex` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{regex::RegexPositionsExt, LineCol};
+//! use regex::Regex;
+//!
+//! let text = "fn main() {\n    let 🦀 = 42;\n    let x = 7;\n}\n";
+//! let re = Regex::new(r"let (\S+) = (\d+)").unwrap();
+//!
+//! let mut iter = text.regex_positions(&re);
+//! let (span, m) = iter.next().unwrap();
+//! assert_eq!(m.as_str(), "let 🦀 = 42");
+//! assert_eq!(span.start_line_col(), LineCol(2, 5));
+//! assert_eq!(span.end_line_col(), LineCol(2, 15));
+//!
+//! let caps = text.regex_captures_positions(&re).nth(1).unwrap();
+//! let (span, m) = caps.get(2).unwrap();
+//! assert_eq!(m.as_str(), "7");
+//! assert_eq!(span.start_line_col(), LineCol(3, 13));
+//! ```
+
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+use ::regex::{CaptureMatches, Captures, Match, Matches, Regex};
+
+use crate::{Span, SpanResolver};
+
+pub trait RegexPositionsExt {
+    /// Returns an iterator over all non-overlapping matches of `re`,
+    /// and their [`Span`]s.
+    ///
+    /// The positions are resolved in a single forward pass over the text.
+    fn regex_positions<'r, 'h>(&'h self, re: &'r Regex) -> RegexPositions<'r, 'h>;
+
+    /// Returns an iterator over the capture groups of all non-overlapping
+    /// matches of `re`, and their [`Span`]s.
+    ///
+    /// The positions are resolved in a single forward pass over the text,
+    /// by resolving the starts and ends of the groups of each match in order.
+    fn regex_captures_positions<'r, 'h>(&'h self, re: &'r Regex) -> CapturesPositions<'r, 'h>;
+}
+
+impl RegexPositionsExt for str {
+    #[inline]
+    fn regex_positions<'r, 'h>(&'h self, re: &'r Regex) -> RegexPositions<'r, 'h> {
+        RegexPositions {
+            matches: re.find_iter(self),
+            resolver: SpanResolver::new(self),
+        }
+    }
+
+    #[inline]
+    fn regex_captures_positions<'r, 'h>(&'h self, re: &'r Regex) -> CapturesPositions<'r, 'h> {
+        CapturesPositions {
+            captures: re.captures_iter(self),
+            resolver: SpanResolver::new(self),
+        }
+    }
+}
+
+/// An iterator over regex matches and their [`Span`]s.
+///
+/// See [`RegexPositionsExt::regex_positions()`].
+#[derive(Debug)]
+pub struct RegexPositions<'r, 'h> {
+    matches: Matches<'r, 'h>,
+    resolver: SpanResolver<'h>,
+}
+
+impl<'h> Iterator for RegexPositions<'_, 'h> {
+    type Item = (Span, Match<'h>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let m = self.matches.next()?;
+        let span = self.resolver.span(m.range())?;
+        Some((span, m))
+    }
+}
+
+impl FusedIterator for RegexPositions<'_, '_> {}
+
+/// An iterator over the capture groups of regex matches and their [`Span`]s.
+///
+/// See [`RegexPositionsExt::regex_captures_positions()`].
+#[derive(Debug)]
+pub struct CapturesPositions<'r, 'h> {
+    captures: CaptureMatches<'r, 'h>,
+    resolver: SpanResolver<'h>,
+}
+
+impl<'h> Iterator for CapturesPositions<'_, 'h> {
+    type Item = CapturePositions<'h>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let captures = self.captures.next()?;
+
+        // Groups can start before the previous group ends, so resolve
+        // all starts and ends in order, to only ever scan forward
+        let mut bounds = captures
+            .iter()
+            .enumerate()
+            .filter_map(|(i, m)| Some((i, m?.range())))
+            .flat_map(|(i, r)| [(r.start, i, false), (r.end, i, true)])
+            .collect::<Vec<_>>();
+        bounds.sort_unstable();
+
+        let mut starts = alloc::vec![None; captures.len()];
+        let mut spans = alloc::vec![None; captures.len()];
+        for (byte, i, is_end) in bounds {
+            let pos = self.resolver.position(byte);
+            if is_end {
+                spans[i] = starts[i].zip(pos).map(|(start, end)| Span::new(start, end));
+            } else {
+                starts[i] = pos;
+            }
+        }
+
+        Some(CapturePositions { captures, spans })
+    }
+}
+
+impl FusedIterator for CapturesPositions<'_, '_> {}
+
+/// The capture groups of a single regex match and their [`Span`]s.
+#[derive(Debug)]
+pub struct CapturePositions<'h> {
+    captures: Captures<'h>,
+    spans: Vec<Option<Span>>,
+}
+
+impl<'h> CapturePositions<'h> {
+    /// Returns the capture group at index `i`, where index `0`
+    /// is the whole match.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<(Span, Match<'h>)> {
+        let span = (*self.spans.get(i)?)?;
+        Some((span, self.captures.get(i)?))
+    }
+
+    /// Returns the capture group named `name`.
+    pub fn name(&self, name: &str) -> Option<(Span, Match<'h>)> {
+        let m = self.captures.name(name)?;
+        let span = self
+            .spans
+            .iter()
+            .flatten()
+            .find(|span| span.byte_range() == m.range())?;
+        Some((*span, m))
+    }
+
+    /// Returns the number of capture groups, including the whole match.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Always `false`, as the whole match is always present.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    #[inline]
+    pub fn captures(&self) -> &Captures<'h> {
+        &self.captures
+    }
+}
@@ -0,0 +1,104 @@
+use core::ops::{Add, AddAssign};
+
+use crate::{scan, LineCol, LineColByte};
+
+/// `RelativePos { lines, cols, bytes }`
+///
+/// The distance between two positions, e.g. the extent of some text.
+///
+/// Adding a `RelativePos` to a position follows the same rules as
+/// advancing past text: if `lines` is 0, then `cols` is added to the
+/// column. Otherwise, `lines` is added to the line, and the column is
+/// reset, such that `cols` is the number of columns from the start of
+/// the new line.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{LineCol, LineColByte, RelativePos};
+///
+/// let rel = RelativePos::of("🦀\nHello");
+/// assert_eq!(rel, RelativePos { lines: 1, cols: 5, bytes: 10 });
+///
+/// assert_eq!(LineCol(3, 7) + rel, LineCol(4, 6));
+/// assert_eq!(LineColByte(3, 7, 20) + rel, LineColByte(4, 6, 30));
+///
+/// let start = LineColByte(3, 7, 20);
+/// let end = LineColByte(4, 6, 30);
+/// assert_eq!(RelativePos::between(start, end), Some(rel));
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub struct RelativePos {
+    /// The number of lines.
+    pub lines: usize,
+    /// The number of columns, from the start of the last line
+    /// if `lines` is not 0.
+    pub cols: usize,
+    /// The number of bytes.
+    pub bytes: usize,
+}
+
+impl RelativePos {
+    /// Returns the extent of `text`, i.e. the position at the end
+    /// of `text` relative to its start.
+    pub fn of(text: &str) -> Self {
+        let (lines, last_line) = match scan::rfind_newline(text) {
+            Some(i) => (scan::count_newlines(text), &text[(i + 1)..]),
+            None => (0, text),
+        };
+        Self {
+            lines,
+            cols: last_line.chars().count(),
+            bytes: text.len(),
+        }
+    }
+
+    /// Returns the position of `end` relative to `start`, such that
+    /// `start + RelativePos::between(start, end) == end`.
+    ///
+    /// Returns `None` if `end` is before `start`.
+    pub fn between(start: LineColByte, end: LineColByte) -> Option<Self> {
+        let bytes = end.2.checked_sub(start.2)?;
+        let (lines, cols) = match end.0.checked_sub(start.0)? {
+            0 => (0, end.1.checked_sub(start.1)?),
+            lines => (lines, end.1.checked_sub(1)?),
+        };
+        Some(Self { lines, cols, bytes })
+    }
+}
+
+impl Add<RelativePos> for LineCol {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rel: RelativePos) -> Self {
+        match rel.lines {
+            0 => Self(self.0, self.1 + rel.cols),
+            lines => Self(self.0 + lines, 1 + rel.cols),
+        }
+    }
+}
+
+impl Add<RelativePos> for LineColByte {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rel: RelativePos) -> Self {
+        let LineCol(line, col) = LineCol(self.0, self.1) + rel;
+        Self(line, col, self.2 + rel.bytes)
+    }
+}
+
+impl AddAssign<RelativePos> for LineCol {
+    #[inline]
+    fn add_assign(&mut self, rel: RelativePos) {
+        *self = *self + rel;
+    }
+}
+
+impl AddAssign<RelativePos> for LineColByte {
+    #[inline]
+    fn add_assign(&mut self, rel: RelativePos) {
+        *self = *self + rel;
+    }
+}
@@ -0,0 +1,870 @@
+//! Rendering of rustc-style annotated snippets of source code.
+//!
+//! Output can optionally be colored using [anstyle](https://docs.rs/anstyle),
+//! which requires the `anstyle` feature.
+//!
+//! Carets are aligned by display width, with tabs expanded. With the
+//! `unicode-width` feature, wide [`char`]s, e.g. CJK, count as 2 columns,
+//! and zero width `char`s as 0, using [unicode-width](https://docs.rs/unicode-width).
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::render::{render, Label};
+//! use char_positions::SpanResolver;
+//!
+//! let source = "fn main() {\n    let x = 🦀;\n}\n";
+//! let span = SpanResolver::new(source).span(20..21).unwrap();
+//!
+//! let out = render(source, &[Label::new(span, "unused variable")]);
+//! assert_eq!(
+//!     out,
+//!     "  |\n\
+//!      2 |     let x = 🦀;\n\
+//!      \x20 |         ^ unused variable\n",
+//! );
+//! ```
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::ops::Range;
+
+#[cfg(feature = "anstyle")]
+use ::anstyle::{AnsiColor, Color, Style};
+
+use crate::{LineIndex, Span};
+
+/// `Label { span, message }`
+///
+/// A [`Span`] annotated with a message.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Label<'a> {
+    pub span: Span,
+    pub message: Cow<'a, str>,
+}
+
+impl<'a> Label<'a> {
+    #[inline]
+    pub fn new(span: Span, message: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// The severity of a [`Diagnostic`].
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default, Debug)]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+impl Severity {
+    /// Returns the severity in lowercase, e.g. `"error"`.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Note => "note",
+            Self::Help => "help",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A diagnostic, i.e. a message with a [`Severity`], an optional code,
+/// a primary label, secondary labels, and notes.
+///
+/// Render it with [`Renderer::render_diagnostic()`], or pass its
+/// [`labels()`](Self::labels) to the other outputs, e.g. JSON or SARIF.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::render::{Diagnostic, Severity};
+/// use char_positions::SpanResolver;
+///
+/// let source = "let x = 🦀;";
+/// let span = SpanResolver::new(source).span(4..5).unwrap();
+///
+/// let diagnostic = Diagnostic::warning("unused variable: `x`")
+///     .primary(span, "help: prefix it with an underscore: `_x`");
+///
+/// assert_eq!(diagnostic.severity, Severity::Warning);
+/// assert_eq!(diagnostic.labels().count(), 1);
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Diagnostic<'a> {
+    pub severity: Severity,
+    pub code: Option<Cow<'a, str>>,
+    pub message: Cow<'a, str>,
+    pub primary: Option<Label<'a>>,
+    pub secondary: Vec<Label<'a>>,
+    pub notes: Vec<Cow<'a, str>>,
+}
+
+impl<'a> Diagnostic<'a> {
+    #[inline]
+    pub fn new(severity: Severity, message: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            severity,
+            code: None,
+            message: message.into(),
+            primary: None,
+            secondary: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    /// Returns a diagnostic with [`Severity::Error`].
+    #[inline]
+    pub fn error(message: impl Into<Cow<'a, str>>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    /// Returns a diagnostic with [`Severity::Warning`].
+    #[inline]
+    pub fn warning(message: impl Into<Cow<'a, str>>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    /// Sets the code, e.g. `"E0425"`.
+    #[inline]
+    pub fn code(mut self, code: impl Into<Cow<'a, str>>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Sets the primary label, i.e. where the diagnostic occurred.
+    #[inline]
+    pub fn primary(mut self, span: Span, message: impl Into<Cow<'a, str>>) -> Self {
+        self.primary = Some(Label::new(span, message));
+        self
+    }
+
+    /// Adds a secondary label.
+    #[inline]
+    pub fn label(mut self, span: Span, message: impl Into<Cow<'a, str>>) -> Self {
+        self.secondary.push(Label::new(span, message));
+        self
+    }
+
+    /// Adds a note.
+    #[inline]
+    pub fn note(mut self, note: impl Into<Cow<'a, str>>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Returns an iterator over the primary label, if any,
+    /// followed by the secondary labels.
+    #[inline]
+    pub fn labels(&self) -> impl Iterator<Item = &Label<'a>> {
+        self.primary.iter().chain(&self.secondary)
+    }
+}
+
+/// Renders the lines of `source` covered by `labels`, with line numbers in
+/// a gutter, and each label underlined by `^^^` carets followed by its message.
+///
+/// Labels spanning multiple lines are drawn in a margin left of the source,
+/// connecting the first and last [`char`] of the label. Overlapping labels
+/// on the same line share a row of carets, with their messages stacked
+/// below, from right to left.
+///
+/// Labels are ordered by their spans, so the output
+/// does not depend on the order of `labels`.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::render::{render, Label};
+/// use char_positions::SpanResolver;
+///
+/// let source = "fn main() {\n    println!(\"{}\", x);\n}\n";
+/// let mut resolver = SpanResolver::new(source);
+///
+/// let labels = [
+///     Label::new(resolver.span(10..36).unwrap(), "function body"),
+///     Label::new(resolver.span(16..33).unwrap(), "in this macro"),
+///     Label::new(resolver.span(31..32).unwrap(), "not found"),
+/// ];
+///
+/// let expected = [
+///     "  |",
+///     "1 |   fn main() {",
+///     "  |  ___________^",
+///     "2 | |     println!(\"{}\", x);",
+///     "  | |     ^^^^^^^^^^^^^^^^^",
+///     "  | |     |              |",
+///     "  | |     |              not found",
+///     "  | |     in this macro",
+///     "3 | | }",
+///     "  | |_^ function body",
+/// ];
+/// assert_eq!(render(source, &labels), expected.join("\n") + "\n");
+/// ```
+///
+/// See [`Renderer`] for more options.
+///
+/// # Panics
+///
+/// Panics if any span is out of bounds of `source`.
+#[inline]
+pub fn render(source: &str, labels: &[Label<'_>]) -> String {
+    Renderer::new().render(source, labels)
+}
+
+/// Options for rendering annotated snippets.
+///
+/// See [`render()`] for details on the output.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::render::{Label, Renderer};
+/// use char_positions::SourceFile;
+///
+/// let file = SourceFile::new("main.rs", "fn main() {\n    let a = 1;\n    let b = 2;\n\n    let c = 3;\n    let d = 4;\n}\n");
+///
+/// let labels = [
+///     Label::new(file.span(20..21).unwrap(), "first"),
+///     Label::new(file.span(66..67).unwrap(), "second"),
+/// ];
+///
+/// let expected = [
+///     "  |",
+///     "1 | fn main() {",
+///     "2 |     let a = 1;",
+///     "  |         ^ first",
+///     "3 |     let b = 2;",
+///     "...",
+///     "5 |     let c = 3;",
+///     "6 |     let d = 4;",
+///     "  |         ^ second",
+///     "7 | }",
+/// ];
+///
+/// let out = Renderer::new().context_lines(1).render(file.text(), &labels);
+/// assert_eq!(out, expected.join("\n") + "\n");
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Renderer {
+    context_lines: usize,
+    tab_width: usize,
+    #[cfg(feature = "anstyle")]
+    styles: Styles,
+}
+
+impl Default for Renderer {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            context_lines: 0,
+            tab_width: 4,
+            #[cfg(feature = "anstyle")]
+            styles: Styles::plain(),
+        }
+    }
+
+    /// Sets the styles used when rendering. Defaults to [`Styles::plain()`].
+    ///
+    /// Requires the `anstyle` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::render::{Label, Renderer, Styles};
+    /// use char_positions::SpanResolver;
+    ///
+    /// let source = "let x = 🦀;";
+    /// let span = SpanResolver::new(source).span(4..5).unwrap();
+    ///
+    /// let out = Renderer::new()
+    ///     .styles(Styles::colored())
+    ///     .render(source, &[Label::new(span, "unused variable")]);
+    /// assert!(out.contains("\x1b["));
+    /// ```
+    #[cfg(feature = "anstyle")]
+    #[inline]
+    pub const fn styles(mut self, styles: Styles) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Sets the number of lines of context to include before and
+    /// after each annotated line. Defaults to `0`.
+    ///
+    /// Lines that are not rendered are elided, marked by `...`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::render::{Label, Renderer};
+    /// use char_positions::SpanResolver;
+    ///
+    /// let source = "a\nb\nc\n";
+    /// let span = SpanResolver::new(source).span(2..3).unwrap();
+    ///
+    /// // Include every line
+    /// let out = Renderer::new()
+    ///     .context_lines(usize::MAX)
+    ///     .render(source, &[Label::new(span, "here")]);
+    /// assert_eq!(out, "  |\n1 | a\n2 | b\n  | ^ here\n3 | c\n");
+    /// ```
+    #[inline]
+    pub const fn context_lines(mut self, lines: usize) -> Self {
+        self.context_lines = lines;
+        self
+    }
+
+    /// Sets the number of columns between tab stops. Defaults to `4`.
+    ///
+    /// Tabs in the source are expanded to spaces, so the carets
+    /// line up with the text regardless of the terminal's tab width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::render::{Label, Renderer};
+    /// use char_positions::SpanResolver;
+    ///
+    /// let source = "\tlet\tx = 1;";
+    /// let span = SpanResolver::new(source).span(5..6).unwrap();
+    ///
+    /// let expected = [
+    ///     "  |",
+    ///     "1 |     let x = 1;",
+    ///     "  |         ^ unused",
+    /// ];
+    ///
+    /// let out = Renderer::new()
+    ///     .tab_width(4)
+    ///     .render(source, &[Label::new(span, "unused")]);
+    /// assert_eq!(out, expected.join("\n") + "\n");
+    /// ```
+    #[inline]
+    pub const fn tab_width(mut self, width: usize) -> Self {
+        self.tab_width = width;
+        self
+    }
+
+    /// Renders the lines of `source` covered by `labels`.
+    ///
+    /// See [`render()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if any span is out of bounds of `source`.
+    pub fn render(&self, source: &str, labels: &[Label<'_>]) -> String {
+        self.render_with_gutter(source, labels).0
+    }
+
+    /// Renders `diagnostic` in the style of rustc, i.e. a `severity[code]: message`
+    /// header, followed by the [rendered](Self::render) labels, and its notes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::render::{Diagnostic, Renderer};
+    /// use char_positions::SourceFile;
+    ///
+    /// let file = SourceFile::new("main.rs", "fn main() {\n    let x = y;\n}\n");
+    ///
+    /// let diagnostic = Diagnostic::error("cannot find value `y` in this scope")
+    ///     .code("E0425")
+    ///     .primary(file.span(24..25).unwrap(), "not found in this scope")
+    ///     .label(file.span(20..21).unwrap(), "while assigning this")
+    ///     .note("`y` must be declared before use");
+    ///
+    /// let expected = [
+    ///     "error[E0425]: cannot find value `y` in this scope",
+    ///     "  |",
+    ///     "2 |     let x = y;",
+    ///     "  |         ^   ^ not found in this scope",
+    ///     "  |         |",
+    ///     "  |         while assigning this",
+    ///     "  = note: `y` must be declared before use",
+    /// ];
+    /// let out = Renderer::new().render_diagnostic(file.text(), &diagnostic);
+    /// assert_eq!(out, expected.join("\n") + "\n");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if any span is out of bounds of `source`.
+    pub fn render_diagnostic(&self, source: &str, diagnostic: &Diagnostic<'_>) -> String {
+        let mut out = String::new();
+        _ = write!(out, "{}", diagnostic.severity);
+        if let Some(code) = &diagnostic.code {
+            _ = write!(out, "[{code}]");
+        }
+        _ = writeln!(out, ": {}", diagnostic.message);
+
+        let labels = diagnostic.labels().cloned().collect::<Vec<_>>();
+        let mut gutter = 0;
+        if !labels.is_empty() {
+            let (snippet, width) = self.render_with_gutter(source, &labels);
+            out.push_str(&snippet);
+            gutter = width + 1;
+        }
+
+        for note in &diagnostic.notes {
+            _ = writeln!(out, "{:gutter$}= note: {note}", "");
+        }
+        out
+    }
+
+    /// Returns the rendered `labels`, and the width of the line numbers in the gutter.
+    fn render_with_gutter(&self, source: &str, labels: &[Label<'_>]) -> (String, usize) {
+        let index = LineIndex::new(source);
+
+        let mut labels = labels
+            .iter()
+            .map(|label| Annotation::new(source, &index, label, self.tab_width))
+            .collect::<Vec<_>>();
+        labels.sort_by_key(|a| (a.first, a.last));
+
+        let (multi, single): (Vec<_>, Vec<_>) =
+            labels.into_iter().partition(|a| a.first.0 != a.last.0);
+
+        let mut lines = BTreeSet::new();
+        lines.extend(single.iter().map(|a| a.first.0));
+        for a in &multi {
+            lines.extend(a.first.0..=a.last.0);
+        }
+        if self.context_lines > 0 {
+            // Exclude the empty line following a trailing line break
+            let line_count = index.line_count() - usize::from(source.ends_with('\n'));
+            let context = lines
+                .iter()
+                .flat_map(|&line| {
+                    let first = line.saturating_sub(self.context_lines).max(1);
+                    let last = line
+                        .saturating_add(self.context_lines)
+                        .min(line_count.max(line));
+                    first..=last
+                })
+                .collect::<Vec<_>>();
+            lines.extend(context);
+        }
+
+        let gutter = lines.last().map_or(1, |&line: &usize| {
+            line.checked_ilog10().unwrap_or(0) as usize + 1
+        });
+
+        let mut out = Output {
+            out: String::new(),
+            gutter,
+            renderer: self,
+        };
+        out.row(Row::margin(&[]), None);
+
+        let mut prev = None;
+        for &line in &lines {
+            if prev.is_some_and(|prev| line > prev + 1) {
+                out.paint(Part::Gutter, "...");
+                out.out.push('\n');
+            }
+            prev = Some(line);
+
+            let mut active = multi
+                .iter()
+                .map(|a| (a.first.0 < line) && (line <= a.last.0))
+                .collect::<Vec<_>>();
+
+            let mut row = Row::margin(&active);
+            let text = expand_tabs(line_text(source, &index, line), self.tab_width);
+            row.put_str(0, &text, Part::Source);
+            out.row(row, Some(line));
+
+            let labels = single
+                .iter()
+                .filter(|a| a.first.0 == line)
+                .collect::<Vec<_>>();
+            write_single_line(&mut out, &active, &labels);
+
+            for (track, a) in multi.iter().enumerate() {
+                if a.first.0 == line {
+                    let mut row = Row::margin(&active);
+                    let caret = row.text_index(a.start);
+                    row.fill((track + 1)..caret, '_');
+                    row.put(caret, '^');
+                    out.row(row, None);
+                    active[track] = true;
+                }
+            }
+
+            for (track, a) in multi.iter().enumerate() {
+                if a.last.0 == line {
+                    let mut row = Row::margin(&active);
+                    let caret = row.text_index(a.end - 1);
+                    row.fill((track + 1)..caret, '_');
+                    row.put(caret, '^');
+                    if !a.message.is_empty() {
+                        row.put_str_at(caret + 2, a.message, Part::Message);
+                    }
+                    out.row(row, None);
+                    active[track] = false;
+                }
+            }
+        }
+
+        (out.out, gutter)
+    }
+}
+
+/// Writes the rows of carets and messages for the single line `labels`.
+fn write_single_line(out: &mut Output<'_>, active: &[bool], labels: &[&Annotation<'_>]) {
+    let Some(last) = labels.last() else {
+        return;
+    };
+
+    let mut row = Row::margin(active);
+    for a in labels {
+        row.fill_text(a.start..a.end, '^');
+    }
+
+    // The rightmost label gets its message inline,
+    // unless other labels extend further right
+    let end = labels.iter().map(|a| a.end).max().unwrap_or(0);
+    let inline = (last.end == end) && !last.message.is_empty();
+    if inline {
+        row.put_str(end + 1, last.message, Part::Message);
+    }
+    out.row(row, None);
+
+    let stacked = labels[..(labels.len() - usize::from(inline))]
+        .iter()
+        .filter(|a| !a.message.is_empty())
+        .collect::<Vec<_>>();
+    if stacked.is_empty() {
+        return;
+    }
+
+    let mut row = Row::margin(active);
+    for a in &stacked {
+        row.put_text(a.start, '|');
+    }
+    out.row(row, None);
+
+    for (i, a) in stacked.iter().enumerate().rev() {
+        let mut row = Row::margin(active);
+        for a in &stacked[..i] {
+            row.put_text(a.start, '|');
+        }
+        row.put_str(a.start, a.message, Part::Message);
+        out.row(row, None);
+    }
+}
+
+/// A [`Label`] resolved into the line and column of
+/// its first and last [`char`], both inclusive.
+struct Annotation<'a> {
+    first: (usize, usize),
+    last: (usize, usize),
+    /// The display column of the first `char`, on the first line.
+    start: usize,
+    /// The display column following the last `char`, on the last line.
+    end: usize,
+    message: &'a str,
+}
+
+impl<'a> Annotation<'a> {
+    fn new(source: &str, index: &LineIndex, label: &'a Label<'_>, tab_width: usize) -> Self {
+        let Span { start, end } = label.span;
+        let first = (start.0, start.1);
+        let last = if label.span.is_empty() {
+            first
+        } else if end.1 > 1 {
+            (end.0, end.1 - 1)
+        } else {
+            // The span ends with a `'\n'`
+            let line = end.0 - 1;
+            (line, line_text(source, index, line).chars().count() + 1)
+        };
+        let start = display_range(line_text(source, index, first.0), first.1, tab_width).start;
+        let end = display_range(line_text(source, index, last.0), last.1, tab_width).end;
+        Self {
+            first,
+            last,
+            start,
+            end,
+            message: &label.message,
+        }
+    }
+}
+
+/// The [`Style`]s of each part of rendered snippets, enabled by the `anstyle` feature.
+///
+/// See [`Renderer::styles()`].
+#[cfg(feature = "anstyle")]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub struct Styles {
+    /// The line numbers, `|` separators, and `...` elision markers.
+    pub gutter: Style,
+    /// The source text.
+    pub source: Style,
+    /// The `^^^` carets, and the lines connecting them to messages.
+    pub underline: Style,
+    /// The label messages.
+    pub message: Style,
+}
+
+#[cfg(feature = "anstyle")]
+impl Styles {
+    /// No styling, i.e. plain text.
+    #[inline]
+    pub const fn plain() -> Self {
+        Self {
+            gutter: Style::new(),
+            source: Style::new(),
+            underline: Style::new(),
+            message: Style::new(),
+        }
+    }
+
+    /// Colors similar to rustc, i.e. a bold blue gutter,
+    /// and bold red underlines and messages.
+    #[inline]
+    pub const fn colored() -> Self {
+        let blue = Style::new()
+            .fg_color(Some(Color::Ansi(AnsiColor::BrightBlue)))
+            .bold();
+        let red = Style::new()
+            .fg_color(Some(Color::Ansi(AnsiColor::BrightRed)))
+            .bold();
+        Self {
+            gutter: blue,
+            source: Style::new(),
+            underline: red,
+            message: red,
+        }
+    }
+
+    #[inline]
+    const fn get(&self, part: Part) -> Style {
+        match part {
+            Part::Gutter => self.gutter,
+            Part::Source => self.source,
+            Part::Underline => self.underline,
+            Part::Message => self.message,
+        }
+    }
+}
+
+/// The parts of the output, which can be styled differently.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Part {
+    /// The line numbers, `|` separators, and `...` elision markers.
+    Gutter,
+    /// The source text.
+    Source,
+    /// The `^^^` carets, and the lines connecting them to messages.
+    Underline,
+    /// The label messages.
+    Message,
+}
+
+/// A row of output following the gutter, consisting of the margin
+/// for multi line labels, followed by the text.
+struct Row {
+    chars: Vec<(char, Part)>,
+    /// The index in `chars` of the text.
+    text: usize,
+}
+
+impl Row {
+    /// Creates a row with `|` in the margin for every `active` multi line label.
+    fn margin(active: &[bool]) -> Self {
+        let mut chars = active
+            .iter()
+            .map(|&active| {
+                if active {
+                    ('|', Part::Underline)
+                } else {
+                    (' ', Part::Source)
+                }
+            })
+            .collect::<Vec<_>>();
+        if !chars.is_empty() {
+            chars.push((' ', Part::Source));
+        }
+        let text = chars.len();
+        Self { chars, text }
+    }
+
+    /// Returns the index in the row of the 0-indexed column `col` of the text.
+    #[inline]
+    fn text_index(&self, col: usize) -> usize {
+        self.text + col
+    }
+
+    /// Puts the underline `c` at `i`.
+    fn put(&mut self, i: usize, c: char) {
+        if self.chars.len() <= i {
+            self.chars.resize(i + 1, (' ', Part::Source));
+        }
+        self.chars[i] = (c, Part::Underline);
+    }
+
+    fn fill(&mut self, range: Range<usize>, c: char) {
+        for i in range {
+            self.put(i, c);
+        }
+    }
+
+    /// Puts `s` at `i`, replacing anything after it.
+    fn put_str_at(&mut self, i: usize, s: &str, part: Part) {
+        self.chars.truncate(i);
+        self.chars.resize(i, (' ', Part::Source));
+        self.chars.extend(s.chars().map(|c| (c, part)));
+    }
+
+    #[inline]
+    fn put_text(&mut self, col: usize, c: char) {
+        self.put(self.text_index(col), c);
+    }
+
+    #[inline]
+    fn fill_text(&mut self, cols: Range<usize>, c: char) {
+        let cols = cols.start..cols.end.max(cols.start + 1);
+        self.fill(self.text_index(cols.start)..self.text_index(cols.end), c);
+    }
+
+    #[inline]
+    fn put_str(&mut self, col: usize, s: &str, part: Part) {
+        self.put_str_at(self.text_index(col), s, part);
+    }
+}
+
+/// The output being rendered.
+struct Output<'r> {
+    out: String,
+    /// The width of the line numbers.
+    gutter: usize,
+    renderer: &'r Renderer,
+}
+
+impl Output<'_> {
+    /// Writes the gutter, followed by the `row`.
+    fn row(&mut self, row: Row, line: Option<usize>) {
+        let mut gutter = String::new();
+        let width = self.gutter;
+        match line {
+            Some(line) => _ = write!(gutter, "{line:>width$} |"),
+            None => _ = write!(gutter, "{:width$} |", ""),
+        }
+        self.paint(Part::Gutter, &gutter);
+
+        let len = row
+            .chars
+            .iter()
+            .rposition(|&(c, _)| c != ' ')
+            .map_or(0, |i| i + 1);
+        if len > 0 {
+            self.out.push(' ');
+        }
+
+        let mut chars = &row.chars[..len];
+        while let Some(&(_, part)) = chars.first() {
+            let n = chars.iter().take_while(|&&(_, p)| p == part).count();
+            let text = chars[..n].iter().map(|&(c, _)| c).collect::<String>();
+            self.paint(part, &text);
+            chars = &chars[n..];
+        }
+
+        self.out.push('\n');
+    }
+
+    /// Writes `text` styled as `part`.
+    fn paint(&mut self, part: Part, text: &str) {
+        #[cfg(feature = "anstyle")]
+        {
+            let style = self.renderer.styles.get(part);
+            _ = write!(self.out, "{}{text}{}", style.render(), style.render_reset());
+        }
+        #[cfg(not(feature = "anstyle"))]
+        {
+            _ = (self.renderer, part);
+            self.out.push_str(text);
+        }
+    }
+}
+
+/// Returns the display columns of the [`char`] at the 1-indexed `col` of `text`.
+///
+/// Columns past the end of `text` are 1 wide.
+fn display_range(text: &str, col: usize, tab_width: usize) -> Range<usize> {
+    let mut offset = 0;
+    let mut chars = text.chars();
+    for _ in 1..col {
+        match chars.next() {
+            Some(c) => offset += char_width(c, offset, tab_width),
+            None => offset += 1,
+        }
+    }
+    let width = chars.next().map_or(1, |c| char_width(c, offset, tab_width));
+    offset..(offset + width)
+}
+
+/// Returns `text` with tabs replaced by spaces up to the next tab stop.
+fn expand_tabs(text: &str, tab_width: usize) -> Cow<'_, str> {
+    if !text.contains('\t') {
+        return Cow::Borrowed(text);
+    }
+    let mut expanded = String::with_capacity(text.len());
+    let mut offset = 0;
+    for c in text.chars() {
+        let width = char_width(c, offset, tab_width);
+        match c {
+            '\t' => expanded.extend(core::iter::repeat_n(' ', width)),
+            _ => expanded.push(c),
+        }
+        offset += width;
+    }
+    Cow::Owned(expanded)
+}
+
+/// Returns the display width of `c` at the display column `offset`.
+///
+/// Without the `unicode-width` feature, all other [`char`]s are 1 wide.
+fn char_width(c: char, offset: usize, tab_width: usize) -> usize {
+    match c {
+        '\t' => {
+            let tab_width = tab_width.max(1);
+            tab_width - (offset % tab_width)
+        }
+        #[cfg(feature = "unicode-width")]
+        _ => ::unicode_width::UnicodeWidthChar::width(c).unwrap_or(0),
+        #[cfg(not(feature = "unicode-width"))]
+        _ => 1,
+    }
+}
+
+/// Returns the 1-indexed `line`, excluding its line break.
+fn line_text<'a>(source: &'a str, index: &LineIndex, line: usize) -> &'a str {
+    let range = index.line_range(line).expect("span out of bounds");
+    source[range].trim_end_matches(['\n', '\r'])
+}
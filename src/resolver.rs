@@ -0,0 +1,104 @@
+use core::ops::Range;
+
+use crate::{scan, LineColByte, Span};
+
+/// Resolves byte positions and byte ranges into lines and columns,
+/// e.g. the `Range<usize>` spans produced by [logos], [regex], or
+/// hand written lexers.
+///
+/// The resolver remembers the last resolved position, and only scans the
+/// text in between. As such, resolving monotonically increasing positions,
+/// e.g. the spans of tokens in order, is amortized O(1). Resolving a position
+/// before the last resolved position rescans from the start of its line, or
+/// from the start of the text if it is on an earlier line.
+///
+/// For random access see [`LineIndex`](crate::LineIndex), which requires the
+/// `alloc` feature.
+///
+/// [logos]: https://docs.rs/logos
+/// [regex]: https://docs.rs/regex
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{LineColByte, SpanResolver};
+///
+/// let text = "let 🦀 = 1;\nlet x = 🦀;";
+/// let mut resolver = SpanResolver::new(text);
+///
+/// let tokens = [0..3, 4..8, 9..10, 11..12, 12..13, 14..17];
+/// let spans = tokens
+///     .into_iter()
+///     .map(|range| resolver.span(range).unwrap())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(spans[1].start, LineColByte(1, 5, 4));
+/// assert_eq!(spans[1].end, LineColByte(1, 6, 8));
+/// assert_eq!(spans[5].start, LineColByte(2, 1, 14));
+///
+/// assert_eq!(resolver.position(9), Some(LineColByte(1, 7, 9)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpanResolver<'a> {
+    text: &'a str,
+    /// The last resolved position.
+    pos: LineColByte,
+    /// The start byte position of the line of `pos`.
+    line_start: usize,
+}
+
+impl<'a> SpanResolver<'a> {
+    #[inline]
+    pub const fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            pos: LineColByte(1, 1, 0),
+            line_start: 0,
+        }
+    }
+
+    #[inline]
+    pub const fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the line, column, and byte position of the byte position `byte`.
+    ///
+    /// Returns `None` if `byte` is out of bounds or not on a [`char`] boundary.
+    pub fn position(&mut self, byte: usize) -> Option<LineColByte> {
+        if !self.text.is_char_boundary(byte) {
+            return None;
+        }
+
+        if byte < self.pos.2 {
+            if byte >= self.line_start {
+                self.pos = LineColByte(self.pos.0, 1, self.line_start);
+            } else {
+                self.pos = LineColByte(1, 1, 0);
+                self.line_start = 0;
+            }
+        }
+
+        let s = &self.text[self.pos.2..byte];
+        if let Some(i) = scan::rfind_newline(s) {
+            self.line_start = self.pos.2 + i + 1;
+        }
+        self.pos.advance_str(s);
+
+        Some(self.pos)
+    }
+
+    /// Returns the [`Span`] of the byte `range`.
+    ///
+    /// Returns `None` if `range` is inverted, out of bounds, or not
+    /// on [`char`] boundaries.
+    #[inline]
+    pub fn span(&mut self, range: Range<usize>) -> Option<Span> {
+        if range.start > range.end {
+            return None;
+        }
+        let start = self.position(range.start)?;
+        let end = self.position(range.end)?;
+        Some(Span::new(start, end))
+    }
+}
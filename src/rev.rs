@@ -0,0 +1,106 @@
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::{scan, LineColByteRange};
+
+pub trait RevPositionsExt {
+    /// Returns an iterator over the [`char`]s before the byte position
+    /// `offset` in reverse, along with their positions in the whole text.
+    ///
+    /// The line and column of `offset` are computed up front, from the
+    /// number of line breaks before it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is out of bounds, or not on a [`char`] boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineCol, RevPositionsExt};
+    ///
+    /// let text = "fn main() {\n    dbg!(🦀, x";
+    /// let cursor = text.len();
+    ///
+    /// let (pos, _) = text
+    ///     .char_positions_rev_from::<LineCol>(cursor)
+    ///     .find(|&(_, c)| c == '(')
+    ///     .unwrap();
+    /// assert_eq!(pos, LineCol(2, 9));
+    ///
+    /// let (pos, _) = text
+    ///     .char_positions_rev_from::<LineCol>(cursor)
+    ///     .find(|&(_, c)| c == '{')
+    ///     .unwrap();
+    /// assert_eq!(pos, LineCol(1, 11));
+    /// ```
+    fn char_positions_rev_from<T>(&self, offset: usize) -> RevCharPositions<'_, T>
+    where
+        LineColByteRange: Into<T>;
+}
+
+impl RevPositionsExt for str {
+    fn char_positions_rev_from<T>(&self, offset: usize) -> RevCharPositions<'_, T>
+    where
+        LineColByteRange: Into<T>,
+    {
+        let prefix = &self[..offset];
+        let line_start = scan::rfind_newline(prefix).map_or(0, |i| i + 1);
+        RevCharPositions {
+            text: self,
+            end: offset,
+            line: scan::count_newlines(prefix) + 1,
+            col: prefix[line_start..].chars().count() + 1,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// An iterator over [`char`]s and their positions in reverse.
+///
+/// See [`RevPositionsExt::char_positions_rev_from()`].
+#[derive(Clone, Debug)]
+pub struct RevCharPositions<'a, T> {
+    text: &'a str,
+    /// The end byte position of the next [`char`].
+    end: usize,
+    /// The line and column of `end`.
+    line: usize,
+    col: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T> RevCharPositions<'a, T> {
+    /// Returns the remaining substring, i.e. the text before the next [`char`].
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[..self.end]
+    }
+}
+
+impl<T> Iterator for RevCharPositions<'_, T>
+where
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.as_str().chars().next_back()?;
+        let start = self.end - c.len_utf8();
+
+        if c == '\n' {
+            let line_start = scan::rfind_newline(&self.text[..start]).map_or(0, |i| i + 1);
+            self.line -= 1;
+            self.col = self.text[line_start..start].chars().count() + 1;
+        } else {
+            self.col -= 1;
+        }
+
+        let pos = LineColByteRange(self.line, self.col, start..self.end);
+        self.end = start;
+
+        Some((pos.into(), c))
+    }
+}
+
+impl<T> FusedIterator for RevCharPositions<'_, T> where Self: Iterator {}
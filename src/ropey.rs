@@ -0,0 +1,42 @@
+//! Integration with [`ropey`], enabled by the `ropey` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{ropey::char_positions, LineColByteRange};
+//! use ropey::Rope;
+//!
+//! let rope = Rope::from_str("Hello 👋\nWorld 🌏\n🦀🦀");
+//!
+//! let mut iter = char_positions::<LineColByteRange>(rope.slice(..))
+//!     .map(|(LineColByteRange(line, col, range), c)| (line, col, range, c))
+//!     .skip(6);
+//!
+//! assert_eq!(iter.next(), Some((1, 7, 6..10, '👋')));
+//! assert_eq!(iter.next(), Some((1, 8, 10..11, '\n')));
+//! assert_eq!(iter.next(), Some((2, 1, 11..12, 'W')));
+//! ```
+
+use ::ropey::iter::Chunks;
+use ::ropey::RopeSlice;
+
+use crate::chunks::{self, ChunkedCharPositions};
+use crate::LineColByteRange;
+
+/// An iterator over the [`char`]s of a [`RopeSlice`] and their positions.
+///
+/// See [`char_positions()`].
+pub type RopeCharPositions<'a, T> = ChunkedCharPositions<'a, Chunks<'a>, T>;
+
+/// Returns an iterator over the [`char`]s of `slice` and their positions.
+///
+/// The byte positions are relative to the start of `slice`.
+///
+/// See [`CharPositionsExt::char_positions()`](crate::CharPositionsExt::char_positions).
+#[inline]
+pub fn char_positions<T>(slice: RopeSlice<'_>) -> RopeCharPositions<'_, T>
+where
+    LineColByteRange: Into<T>,
+{
+    chunks::char_positions(slice.chunks())
+}
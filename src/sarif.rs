@@ -0,0 +1,217 @@
+//! Conversion of labeled spans into [SARIF] 2.1 results, enabled by the `sarif` feature.
+//!
+//! Only the `results` of a run are produced, i.e. a JSON array of
+//! [`result`] objects, to be embedded in a complete SARIF log.
+//!
+//! SARIF columns default to counting UTF-16 code units, i.e. the default
+//! `columnKind` of `"utf16CodeUnits"`, so columns are converted accordingly.
+//! Lines and columns are 1-indexed, and `endColumn` is exclusive.
+//!
+//! [SARIF]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+//! [`result`]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/os/sarif-v2.1.0-os.html#_Toc34317638
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::render::Label;
+//! use char_positions::sarif::to_sarif_results;
+//! use char_positions::SourceFile;
+//!
+//! let file = SourceFile::new("src/main.rs", "let s = \"🦀\"; x");
+//! let label = Label::new(file.span(16..17).unwrap(), "unknown variable `x`");
+//!
+//! // `x` is at column 14 in `char`s, but at column 15 in UTF-16 code units
+//! let results = to_sarif_results([(&file, "E0425", &label)]);
+//! assert_eq!(
+//!     results,
+//!     concat!(
+//!         r#"[{"ruleId":"E0425","message":{"text":"unknown variable `x`"},"#,
+//!         r#""locations":[{"physicalLocation":{"artifactLocation":{"uri":"src/main.rs"},"#,
+//!         r#""region":{"startLine":1,"startColumn":15,"endLine":1,"endColumn":16,"byteOffset":16,"byteLength":1}}}]}]"#,
+//!     ),
+//! );
+//! ```
+
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::json::write_str;
+use crate::render::{Diagnostic, Label, Severity};
+use crate::{ColumnUnit, SourceFile, Span};
+
+/// Converts `findings`, i.e. the file, rule id, and label of each finding,
+/// into a JSON array of SARIF results.
+///
+/// The file name is used as the artifact URI, and should
+/// as such be a relative or absolute URI.
+///
+/// See the [module](self) documentation for details.
+///
+/// # Panics
+///
+/// Panics if the span of any label is out of bounds of its file.
+pub fn to_sarif_results<'a, 'b: 'a>(
+    findings: impl IntoIterator<Item = (&'a SourceFile<'b>, &'a str, &'a Label<'b>)>,
+) -> String {
+    let mut out = String::new();
+    _ = write_sarif_results(&mut out, findings);
+    out
+}
+
+/// Writes `findings` as a JSON array of SARIF results to `w`.
+///
+/// See [`to_sarif_results()`].
+///
+/// # Panics
+///
+/// Panics if the span of any label is out of bounds of its file.
+pub fn write_sarif_results<'a, 'b: 'a>(
+    w: &mut impl Write,
+    findings: impl IntoIterator<Item = (&'a SourceFile<'b>, &'a str, &'a Label<'b>)>,
+) -> fmt::Result {
+    w.write_char('[')?;
+    for (i, (file, rule_id, label)) in findings.into_iter().enumerate() {
+        if i > 0 {
+            w.write_char(',')?;
+        }
+
+        w.write_str("{\"ruleId\":")?;
+        write_str(w, rule_id)?;
+        w.write_str(",\"message\":{\"text\":")?;
+        write_str(w, &label.message)?;
+        w.write_str("},\"locations\":[{")?;
+        write_physical_location(w, file, label.span)?;
+        w.write_str("}]}")?;
+    }
+    w.write_char(']')
+}
+
+/// Converts `diagnostics`, i.e. the file and [`Diagnostic`] of each finding,
+/// into a JSON array of SARIF results.
+///
+/// The code is used as the `ruleId`, which is omitted if absent. The
+/// severity is used as the `level`, where [`Severity::Help`] maps to `"note"`.
+/// The primary label is used as the location, and the secondary labels
+/// as `relatedLocations`. The label messages are kept, while the notes
+/// are not included, as SARIF has no equivalent.
+///
+/// See [`to_sarif_results()`].
+///
+/// # Example
+///
+/// ```
+/// use char_positions::render::Diagnostic;
+/// use char_positions::sarif::diagnostics_to_sarif_results;
+/// use char_positions::SourceFile;
+///
+/// let file = SourceFile::new("src/main.rs", "let x = y;");
+/// let diagnostic = Diagnostic::error("cannot find value `y` in this scope")
+///     .code("E0425")
+///     .primary(file.span(8..9).unwrap(), "not found");
+///
+/// let results = diagnostics_to_sarif_results([(&file, &diagnostic)]);
+/// assert_eq!(
+///     results,
+///     concat!(
+///         r#"[{"ruleId":"E0425","level":"error","message":{"text":"cannot find value `y` in this scope"},"#,
+///         r#""locations":[{"physicalLocation":{"artifactLocation":{"uri":"src/main.rs"},"#,
+///         r#""region":{"startLine":1,"startColumn":9,"endLine":1,"endColumn":10,"byteOffset":8,"byteLength":1}},"#,
+///         r#""message":{"text":"not found"}}]}]"#,
+///     ),
+/// );
+/// ```
+///
+/// # Panics
+///
+/// Panics if the span of any label is out of bounds of its file.
+pub fn diagnostics_to_sarif_results<'a, 'b: 'a>(
+    diagnostics: impl IntoIterator<Item = (&'a SourceFile<'b>, &'a Diagnostic<'b>)>,
+) -> String {
+    let mut out = String::new();
+    _ = write_diagnostics_sarif_results(&mut out, diagnostics);
+    out
+}
+
+/// Writes `diagnostics` as a JSON array of SARIF results to `w`.
+///
+/// See [`diagnostics_to_sarif_results()`].
+///
+/// # Panics
+///
+/// Panics if the span of any label is out of bounds of its file.
+pub fn write_diagnostics_sarif_results<'a, 'b: 'a>(
+    w: &mut impl Write,
+    diagnostics: impl IntoIterator<Item = (&'a SourceFile<'b>, &'a Diagnostic<'b>)>,
+) -> fmt::Result {
+    w.write_char('[')?;
+    for (i, (file, diagnostic)) in diagnostics.into_iter().enumerate() {
+        if i > 0 {
+            w.write_char(',')?;
+        }
+
+        w.write_char('{')?;
+        if let Some(code) = &diagnostic.code {
+            w.write_str("\"ruleId\":")?;
+            write_str(w, code)?;
+            w.write_char(',')?;
+        }
+        let level = match diagnostic.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note | Severity::Help => "note",
+        };
+        write!(w, "\"level\":\"{level}\",\"message\":{{\"text\":")?;
+        write_str(w, &diagnostic.message)?;
+
+        w.write_str("},\"locations\":[")?;
+        if let Some(primary) = &diagnostic.primary {
+            w.write_char('{')?;
+            write_physical_location(w, file, primary.span)?;
+            w.write_str(",\"message\":{\"text\":")?;
+            write_str(w, &primary.message)?;
+            w.write_str("}}")?;
+        }
+        w.write_char(']')?;
+
+        if !diagnostic.secondary.is_empty() {
+            w.write_str(",\"relatedLocations\":[")?;
+            for (i, label) in diagnostic.secondary.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                w.write_char('{')?;
+                write_physical_location(w, file, label.span)?;
+                w.write_str(",\"message\":{\"text\":")?;
+                write_str(w, &label.message)?;
+                w.write_str("}}")?;
+            }
+            w.write_char(']')?;
+        }
+        w.write_char('}')?;
+    }
+    w.write_char(']')
+}
+
+/// Writes the `"physicalLocation"` field of `span` in `file`.
+fn write_physical_location(w: &mut impl Write, file: &SourceFile<'_>, span: Span) -> fmt::Result {
+    let (start, end) = (span.byte_start(), span.byte_end());
+    let index = file.line_index();
+    let utf16 = |byte| {
+        index
+            .line_col_with_unit(file.text(), byte, ColumnUnit::Utf16)
+            .expect("span out of bounds")
+    };
+    let (start_pos, end_pos) = (utf16(start), utf16(end));
+
+    w.write_str("\"physicalLocation\":{\"artifactLocation\":{\"uri\":")?;
+    write_str(w, file.name())?;
+    write!(
+        w,
+        "}},\"region\":{{\"startLine\":{},\"startColumn\":{},\"endLine\":{},\"endColumn\":{},\"byteOffset\":{start},\"byteLength\":{}}}}}",
+        start_pos.0,
+        start_pos.1,
+        end_pos.0,
+        end_pos.1,
+        end - start,
+    )
+}
@@ -0,0 +1,49 @@
+//! Byte level searching for line breaks, using [memchr] if the `simd`
+//! (or `memchr`) feature is enabled.
+//!
+//! [memchr]: https://docs.rs/memchr
+
+/// Returns the number of `'\n'` in `s`.
+#[cfg(feature = "memchr")]
+#[inline]
+pub(crate) fn count_newlines(s: &str) -> usize {
+    ::memchr::memchr_iter(b'\n', s.as_bytes()).count()
+}
+
+/// Returns the number of `'\n'` in `s`.
+#[cfg(not(feature = "memchr"))]
+#[inline]
+pub(crate) fn count_newlines(s: &str) -> usize {
+    s.bytes().filter(|&b| b == b'\n').count()
+}
+
+/// Returns the byte position of the last `'\n'` in `s`.
+#[cfg(feature = "memchr")]
+#[inline]
+pub(crate) fn rfind_newline(s: &str) -> Option<usize> {
+    ::memchr::memrchr(b'\n', s.as_bytes())
+}
+
+/// Returns the byte position of the last `'\n'` in `s`.
+#[cfg(not(feature = "memchr"))]
+#[inline]
+pub(crate) fn rfind_newline(s: &str) -> Option<usize> {
+    s.bytes().rposition(|b| b == b'\n')
+}
+
+/// Returns an iterator over the byte positions of every `'\n'` in `s`.
+#[cfg(feature = "memchr")]
+#[inline]
+pub(crate) fn newlines(s: &str) -> impl Iterator<Item = usize> + '_ {
+    ::memchr::memchr_iter(b'\n', s.as_bytes())
+}
+
+/// Returns an iterator over the byte positions of every `'\n'` in `s`.
+#[cfg(not(feature = "memchr"))]
+#[inline]
+pub(crate) fn newlines(s: &str) -> impl Iterator<Item = usize> + '_ {
+    s.bytes()
+        .enumerate()
+        .filter(|&(_, b)| b == b'\n')
+        .map(|(i, _)| i)
+}
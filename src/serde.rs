@@ -0,0 +1,488 @@
+//! [Serde] support, enabled by the `serde` feature.
+//!
+//! All position types implement `Serialize` and `Deserialize`. By default they
+//! are represented the same way they are constructed, i.e. tuple structs as
+//! arrays, and structs as objects. Alternatively, the modules in this module
+//! can be used with `#[serde(with = "...")]`, to represent the tuple structs
+//! as objects with named fields, with 0 or 1-indexed lines and columns.
+//!
+//! The representations are stable.
+//!
+//! | Type | Default | [`structs`] |
+//! |:---|:---|:---|
+//! | [`Line`], [`Col`], [`ByteStart`], [`ByteEnd`] | `1` | |
+//! | [`ByteRange`] | `{"start":0,"end":1}` | |
+//! | [`LineCol`] | `[1,2]` | `{"line":1,"col":2}` |
+//! | [`LineColByte`] | `[1,2,3]` | `{"line":1,"col":2,"byte":3}` |
+//! | [`LineColByteRange`] | `[1,2,{"start":3,"end":4}]` | `{"line":1,"col":2,"start":3,"end":4}` |
+//! | [`Span`] | `{"start":[1,2,3],"end":[1,3,4]}` | `{"start":{"line":1,"col":2,"byte":3},"end":{...}}` |
+//!
+//! The other modules only differ from [`structs`] in the following:
+//!
+//! | Module | Lines and columns | Column field |
+//! |:---|:---|:---|
+//! | [`structs`] | 1-indexed | `"col"` |
+//! | [`zero_based`] | 0-indexed | `"col"` |
+//! | [`character`] | 1-indexed | `"character"` |
+//! | [`zero_based_character`] | 0-indexed | `"character"` |
+//!
+//! Byte positions are always 0-indexed. When deserializing, the
+//! positions are converted back into this crate's 1-indexed positions.
+//!
+//! [Serde]: https://serde.rs
+//! [`Line`]: crate::Line
+//! [`Col`]: crate::Col
+//! [`ByteStart`]: crate::ByteStart
+//! [`ByteEnd`]: crate::ByteEnd
+//! [`ByteRange`]: crate::ByteRange
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{LineCol, LineColByte, Span};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Token {
+//!     pos: LineCol,
+//!     #[serde(with = "char_positions::serde::structs")]
+//!     span: Span,
+//!     #[serde(with = "char_positions::serde::zero_based_character")]
+//!     lsp_pos: LineCol,
+//! }
+//!
+//! let token = Token {
+//!     pos: LineCol(1, 5),
+//!     span: Span::new(LineColByte(1, 5, 4), LineColByte(1, 8, 7)),
+//!     lsp_pos: LineCol(1, 5),
+//! };
+//!
+//! let json = serde_json::to_string(&token).unwrap();
+//! assert_eq!(
+//!     json,
+//!     concat!(
+//!         r#"{"pos":[1,5],"#,
+//!         r#""span":{"start":{"line":1,"col":5,"byte":4},"end":{"line":1,"col":8,"byte":7}},"#,
+//!         r#""lsp_pos":{"line":0,"character":4}}"#,
+//!     ),
+//! );
+//! assert_eq!(serde_json::from_str::<Token>(&json).unwrap(), token);
+//! ```
+//!
+//! Deserializing validates the positions the same way as [`Span::try_new()`],
+//! regardless of the representation. Lines and columns of `0`, and spans
+//! ending before they start, are rejected.
+//!
+//! ```
+//! use char_positions::{LineCol, Span};
+//!
+//! assert!(serde_json::from_str::<LineCol>("[0,1]").is_err());
+//! assert!(serde_json::from_str::<Span>(r#"{"start":[1,3,2],"end":[1,1,0]}"#).is_err());
+//! assert!(serde_json::from_str::<Span>(r#"{"start":[1,1,0],"end":[1,3,2]}"#).is_ok());
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use ::serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use ::serde::ser::{self, SerializeStruct};
+use ::serde::{Deserializer, Serialize, Serializer};
+
+use crate::{LineCol, LineColByte, LineColByteRange, Span};
+
+/// The default representation of [`Span`], before it is validated.
+#[derive(::serde::Deserialize)]
+#[serde(rename = "Span")]
+#[doc(hidden)]
+pub struct RawSpan {
+    start: LineColByte,
+    end: LineColByte,
+}
+
+impl TryFrom<RawSpan> for Span {
+    type Error = &'static str;
+
+    #[inline]
+    fn try_from(RawSpan { start, end }: RawSpan) -> Result<Self, Self::Error> {
+        Span::try_new(start, end).ok_or("invalid span")
+    }
+}
+
+/// Types which can be represented as objects with named fields.
+///
+/// See the [module](self) documentation.
+pub trait StructForm: Sized {
+    #[doc(hidden)]
+    fn serialize_repr<S: Serializer>(&self, repr: Repr, serializer: S) -> Result<S::Ok, S::Error>;
+
+    #[doc(hidden)]
+    fn deserialize_repr<'de, D: Deserializer<'de>>(
+        repr: Repr,
+        deserializer: D,
+    ) -> Result<Self, D::Error>;
+}
+
+/// How lines and columns are represented.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug)]
+pub struct Repr {
+    zero_based: bool,
+    col: &'static str,
+}
+
+macro_rules! repr_module {
+    ($(#[$attr:meta])* $name:ident, zero_based: $zero_based:expr, col: $col:expr) => {
+        $(#[$attr])*
+        pub mod $name {
+            use ::serde::{Deserializer, Serializer};
+
+            use super::{Repr, StructForm};
+
+            const REPR: Repr = Repr {
+                zero_based: $zero_based,
+                col: $col,
+            };
+
+            #[inline]
+            pub fn serialize<T: StructForm, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+                value.serialize_repr(REPR, serializer)
+            }
+
+            #[inline]
+            pub fn deserialize<'de, T: StructForm, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+                T::deserialize_repr(REPR, deserializer)
+            }
+        }
+    };
+}
+
+repr_module!(
+    /// Represents positions as objects with 1-indexed `"line"` and `"col"` fields,
+    /// for use with `#[serde(with = "char_positions::serde::structs")]`.
+    ///
+    /// See the [module](super) documentation.
+    structs,
+    zero_based: false,
+    col: "col"
+);
+
+repr_module!(
+    /// Represents positions as objects with 0-indexed `"line"` and `"col"` fields,
+    /// for use with `#[serde(with = "char_positions::serde::zero_based")]`.
+    ///
+    /// See the [module](super) documentation.
+    zero_based,
+    zero_based: true,
+    col: "col"
+);
+
+repr_module!(
+    /// Represents positions as objects with 1-indexed `"line"` and `"character"` fields,
+    /// for use with `#[serde(with = "char_positions::serde::character")]`.
+    ///
+    /// See the [module](super) documentation.
+    character,
+    zero_based: false,
+    col: "character"
+);
+
+repr_module!(
+    /// Represents positions as objects with 0-indexed `"line"` and `"character"` fields,
+    /// for use with `#[serde(with = "char_positions::serde::zero_based_character")]`.
+    ///
+    /// This matches the shape of LSP's `Position`. However, the columns are
+    /// still counted in [`char`]s, whereas LSP defaults to UTF-16 code units.
+    ///
+    /// See the [module](super) documentation.
+    zero_based_character,
+    zero_based: true,
+    col: "character"
+);
+
+impl StructForm for LineCol {
+    fn serialize_repr<S: Serializer>(&self, repr: Repr, serializer: S) -> Result<S::Ok, S::Error> {
+        let LineCol(line, col) = *self;
+        serialize_fields(
+            serializer,
+            "LineCol",
+            repr,
+            [("line", line, true), (repr.col, col, true)],
+        )
+    }
+
+    fn deserialize_repr<'de, D: Deserializer<'de>>(
+        repr: Repr,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let [line, col] = deserialize_fields(
+            deserializer,
+            "LineCol",
+            repr,
+            ["line", repr.col],
+            [true, true],
+        )?;
+        Ok(LineCol(line, col))
+    }
+}
+
+impl StructForm for LineColByte {
+    fn serialize_repr<S: Serializer>(&self, repr: Repr, serializer: S) -> Result<S::Ok, S::Error> {
+        let LineColByte(line, col, byte) = *self;
+        serialize_fields(
+            serializer,
+            "LineColByte",
+            repr,
+            [
+                ("line", line, true),
+                (repr.col, col, true),
+                ("byte", byte, false),
+            ],
+        )
+    }
+
+    fn deserialize_repr<'de, D: Deserializer<'de>>(
+        repr: Repr,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let [line, col, byte] = deserialize_fields(
+            deserializer,
+            "LineColByte",
+            repr,
+            ["line", repr.col, "byte"],
+            [true, true, false],
+        )?;
+        Ok(LineColByte(line, col, byte))
+    }
+}
+
+impl StructForm for LineColByteRange {
+    fn serialize_repr<S: Serializer>(&self, repr: Repr, serializer: S) -> Result<S::Ok, S::Error> {
+        let LineColByteRange(line, col, ref range) = *self;
+        serialize_fields(
+            serializer,
+            "LineColByteRange",
+            repr,
+            [
+                ("line", line, true),
+                (repr.col, col, true),
+                ("start", range.start, false),
+                ("end", range.end, false),
+            ],
+        )
+    }
+
+    fn deserialize_repr<'de, D: Deserializer<'de>>(
+        repr: Repr,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let [line, col, start, end] = deserialize_fields(
+            deserializer,
+            "LineColByteRange",
+            repr,
+            ["line", repr.col, "start", "end"],
+            [true, true, false, false],
+        )?;
+        Ok(LineColByteRange(line, col, start..end))
+    }
+}
+
+impl StructForm for Span {
+    fn serialize_repr<S: Serializer>(&self, repr: Repr, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Span", 2)?;
+        s.serialize_field("start", &WithRepr(&self.start, repr))?;
+        s.serialize_field("end", &WithRepr(&self.end, repr))?;
+        s.end()
+    }
+
+    fn deserialize_repr<'de, D: Deserializer<'de>>(
+        repr: Repr,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("Span", &["start", "end"], SpanVisitor(repr))
+    }
+}
+
+/// Serializes the fields `(key, value, is 1-indexed)` as a struct.
+fn serialize_fields<S: Serializer, const N: usize>(
+    serializer: S,
+    name: &'static str,
+    repr: Repr,
+    fields: [(&'static str, usize, bool); N],
+) -> Result<S::Ok, S::Error> {
+    let mut s = serializer.serialize_struct(name, N)?;
+    for (key, value, one_indexed) in fields {
+        let value = if one_indexed && repr.zero_based {
+            value.checked_sub(1).ok_or_else(|| {
+                <S::Error as ser::Error>::custom("lines and columns must be 1-indexed")
+            })?
+        } else {
+            value
+        };
+        s.serialize_field(key, &value)?;
+    }
+    s.end()
+}
+
+/// Deserializes the fields `keys` of a struct, where the fields
+/// marked as `one_indexed` are converted into 1-indexed values.
+fn deserialize_fields<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+    name: &'static str,
+    repr: Repr,
+    keys: [&'static str; N],
+    one_indexed: [bool; N],
+) -> Result<[usize; N], D::Error> {
+    // Only used as a hint by non-self-describing formats,
+    // which only care about the number of fields
+    const FIELDS: &[&str] = &["line", "col", "start", "end"];
+
+    let mut values =
+        deserializer.deserialize_struct(name, &FIELDS[..N], FieldsVisitor { name, keys })?;
+    if repr.zero_based {
+        for (value, _) in values
+            .iter_mut()
+            .zip(one_indexed)
+            .filter(|&(_, one_indexed)| one_indexed)
+        {
+            *value = value
+                .checked_add(1)
+                .ok_or_else(|| <D::Error as de::Error>::custom("line or column overflow"))?;
+        }
+    }
+    Ok(values)
+}
+
+/// Serializes `T` using the representation `Repr`.
+struct WithRepr<'a, T>(&'a T, Repr);
+
+impl<T: StructForm> Serialize for WithRepr<'_, T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_repr(self.1, serializer)
+    }
+}
+
+/// Deserializes `T` using the representation `Repr`.
+struct ReprSeed<T>(Repr, PhantomData<T>);
+
+impl<'de, T: StructForm> DeserializeSeed<'de> for ReprSeed<T> {
+    type Value = T;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        T::deserialize_repr(self.0, deserializer)
+    }
+}
+
+struct FieldsVisitor<const N: usize> {
+    name: &'static str,
+    keys: [&'static str; N],
+}
+
+impl<'de, const N: usize> Visitor<'de> for FieldsVisitor<N> {
+    type Value = [usize; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "struct {}", self.name)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut values = [0; N];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        Ok(values)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut values = [None; N];
+        while let Some(key) = map.next_key_seed(KeySeed(&self.keys))? {
+            match key {
+                Some(i) if values[i].is_some() => {
+                    return Err(de::Error::duplicate_field(self.keys[i]))
+                }
+                Some(i) => values[i] = Some(map.next_value()?),
+                None => _ = map.next_value::<IgnoredAny>()?,
+            }
+        }
+
+        let mut out = [0; N];
+        for (i, value) in values.into_iter().enumerate() {
+            out[i] = value.ok_or_else(|| de::Error::missing_field(self.keys[i]))?;
+        }
+        Ok(out)
+    }
+}
+
+struct SpanVisitor(Repr);
+
+impl<'de> Visitor<'de> for SpanVisitor {
+    type Value = Span;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("struct Span")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Span, A::Error> {
+        let start = seq
+            .next_element_seed(ReprSeed(self.0, PhantomData))?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let end = seq
+            .next_element_seed(ReprSeed(self.0, PhantomData))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Span::try_new(start, end).ok_or_else(|| de::Error::custom("invalid span"))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Span, A::Error> {
+        const KEYS: [&str; 2] = ["start", "end"];
+
+        let mut values = [None; 2];
+        while let Some(key) = map.next_key_seed(KeySeed(&KEYS))? {
+            match key {
+                Some(i) if values[i].is_some() => return Err(de::Error::duplicate_field(KEYS[i])),
+                Some(i) => values[i] = Some(map.next_value_seed(ReprSeed(self.0, PhantomData))?),
+                None => _ = map.next_value::<IgnoredAny>()?,
+            }
+        }
+
+        let [start, end] = values;
+        let start = start.ok_or_else(|| de::Error::missing_field("start"))?;
+        let end = end.ok_or_else(|| de::Error::missing_field("end"))?;
+        Span::try_new(start, end).ok_or_else(|| de::Error::custom("invalid span"))
+    }
+}
+
+/// Deserializes a field name into its index in `keys`,
+/// or `None` for unknown fields.
+struct KeySeed<'a>(&'a [&'static str]);
+
+impl<'de> DeserializeSeed<'de> for KeySeed<'_> {
+    type Value = Option<usize>;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeySeed<'_> {
+    type Value = Option<usize>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("field identifier")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(self.0.iter().position(|&key| key == v))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(self.0.iter().position(|&key| key.as_bytes() == v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(usize::try_from(v).ok().filter(|&i| i < self.0.len()))
+    }
+}
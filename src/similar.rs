@@ -0,0 +1,109 @@
+//! Integration with [`similar`], enabled by the `similar` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::similar::diff_positions;
+//! use char_positions::LineCol;
+//!
+//! let old = "fn main() {\n    println!(\"Hello\");\n}\n";
+//! let new = "fn main() {\n    let 🦀 = 1;\n    println!(\"{🦀}\");\n}\n";
+//!
+//! let hunks = diff_positions(old, new);
+//! assert_eq!(hunks.len(), 1);
+//!
+//! let hunk = &hunks[0];
+//! assert_eq!(hunk.old.start_line_col(), LineCol(2, 1));
+//! assert_eq!(hunk.old.end_line_col(), LineCol(3, 1));
+//! assert_eq!(hunk.new.start_line_col(), LineCol(2, 1));
+//! assert_eq!(hunk.new.end_line_col(), LineCol(4, 1));
+//! assert_eq!(&new[hunk.new.byte_range()], "    let 🦀 = 1;\n    println!(\"{🦀}\");\n");
+//!
+//! assert_eq!(hunk.to_string(), "old 2:1-3:1 / new 2:1-4:1");
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use ::similar::{capture_diff_slices, Algorithm, DiffTag};
+
+use crate::{Span, SpanResolver};
+
+/// A changed region between an old and a new text.
+///
+/// An insertion has an empty `old` span, and a deletion an empty `new` span.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct DiffHunk {
+    /// The span of the changed lines in the old text.
+    pub old: Span,
+    /// The span of the changed lines in the new text.
+    pub new: Span,
+}
+
+impl fmt::Display for DiffHunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "old {} / new {}", self.old, self.new)
+    }
+}
+
+/// Returns the changed regions between the lines of `old` and `new`,
+/// using a [Myers diff], as [`Span`]s in both texts.
+///
+/// Spans cover whole lines, including their trailing `'\n'`. Adjacent
+/// deleted and inserted lines are merged into a single [`DiffHunk`].
+///
+/// [Myers diff]: https://docs.rs/similar/*/similar/algorithms/myers/index.html
+pub fn diff_positions(old: &str, new: &str) -> Vec<DiffHunk> {
+    let (old_lines, old_starts) = lines(old);
+    let (new_lines, new_starts) = lines(new);
+
+    let ops = capture_diff_slices(Algorithm::Myers, &old_lines, &new_lines);
+
+    let mut ranges = Vec::<(Range<usize>, Range<usize>)>::new();
+    for op in ops {
+        let (tag, old_range, new_range) = op.as_tag_tuple();
+        if tag == DiffTag::Equal {
+            continue;
+        }
+
+        let old_range = old_starts[old_range.start]..old_starts[old_range.end];
+        let new_range = new_starts[new_range.start]..new_starts[new_range.end];
+
+        match ranges.last_mut() {
+            Some((last_old, last_new))
+                if (last_old.end == old_range.start) && (last_new.end == new_range.start) =>
+            {
+                last_old.end = old_range.end;
+                last_new.end = new_range.end;
+            }
+            _ => ranges.push((old_range, new_range)),
+        }
+    }
+
+    let mut old_resolver = SpanResolver::new(old);
+    let mut new_resolver = SpanResolver::new(new);
+    ranges
+        .into_iter()
+        .filter_map(|(old_range, new_range)| {
+            Some(DiffHunk {
+                old: old_resolver.span(old_range)?,
+                new: new_resolver.span(new_range)?,
+            })
+        })
+        .collect()
+}
+
+/// Returns the lines of `s`, including their trailing `'\n'`, and the
+/// start byte position of each line, followed by the length of `s`.
+fn lines(s: &str) -> (Vec<&str>, Vec<usize>) {
+    let lines = s.split_inclusive('\n').collect::<Vec<_>>();
+    let mut starts = Vec::with_capacity(lines.len() + 1);
+    let mut start = 0;
+    for line in &lines {
+        starts.push(start);
+        start += line.len();
+    }
+    starts.push(start);
+    (lines, starts)
+}
@@ -0,0 +1,106 @@
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::counter::LineColCounter;
+use crate::{CharPosition, LineColByteRange};
+
+pub trait CharSlicePositionsExt {
+    /// Returns an iterator over [`char`]s and their positions, for text
+    /// stored as [`char`]s, e.g. a `Vec<char>` or `[char; N]`.
+    ///
+    /// Lines and columns are the same as for the equivalent [`str`].
+    /// However, the "byte" positions are indices into the [`char`]s,
+    /// i.e. the range of the `i`th [`char`] is `i..(i + 1)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{CharSlicePositionsExt, LineCol, LineColByte};
+    ///
+    /// let chars = "a🦀\nb".chars().collect::<Vec<_>>();
+    ///
+    /// let positions = chars.char_positions::<LineColByte>().collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     positions,
+    ///     [
+    ///         (LineColByte(1, 1, 0), 'a'),
+    ///         (LineColByte(1, 2, 1), '🦀'),
+    ///         (LineColByte(1, 3, 2), '\n'),
+    ///         (LineColByte(2, 1, 3), 'b'),
+    ///     ],
+    /// );
+    ///
+    /// let mut iter = ['x', '\n', 'y'].char_positions::<LineCol>();
+    /// assert_eq!(iter.nth(2), Some((LineCol(2, 1), 'y')));
+    /// ```
+    fn char_positions<T>(&self) -> CharSlicePositions<'_, T>
+    where
+        T: CharPosition,
+        LineColByteRange: Into<T>;
+}
+
+impl CharSlicePositionsExt for [char] {
+    #[inline]
+    fn char_positions<T>(&self) -> CharSlicePositions<'_, T>
+    where
+        T: CharPosition,
+        LineColByteRange: Into<T>,
+    {
+        CharSlicePositions {
+            chars: self,
+            index: 0,
+            pos: LineColCounter::START,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// An iterator over [`char`]s and their positions, where the
+/// "byte" positions are indices into the [`char`]s.
+///
+/// See [`CharSlicePositionsExt::char_positions()`].
+#[derive(Clone, Debug)]
+pub struct CharSlicePositions<'a, T> {
+    chars: &'a [char],
+    /// The index of the next [`char`].
+    index: usize,
+    pos: LineColCounter,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T> CharSlicePositions<'a, T> {
+    /// Returns the remaining [`char`]s.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [char] {
+        &self.chars[self.index..]
+    }
+}
+
+impl<T> Iterator for CharSlicePositions<'_, T>
+where
+    T: CharPosition,
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.index;
+        let &c = self.chars.get(i)?;
+        self.index += 1;
+        let pos = self.pos.at(i..(i + 1));
+        if T::LINE_COL {
+            self.pos.advance(c);
+        }
+        Some((T::from_position(&pos), c))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.chars.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for CharSlicePositions<'_, T> where Self: Iterator {}
+
+impl<T> FusedIterator for CharSlicePositions<'_, T> where Self: Iterator {}
@@ -0,0 +1,97 @@
+use crate::{scan, LineCol, LineColByte};
+
+/// Which side a position sticks to when text is inserted exactly at it,
+/// or when the text around it is replaced.
+///
+/// When snapping a position, which side it moves towards.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub enum Bias {
+    /// Stick to the text before the position, i.e. stay before inserted text.
+    #[default]
+    Left,
+    /// Stick to the text after the position, i.e. move after inserted text.
+    Right,
+}
+
+pub trait SnapPositionsExt {
+    /// Returns `pos` clamped to a valid position in the text.
+    ///
+    /// A line past the last line is clamped to the last line, and
+    /// a column past the end of its line is clamped to the end of the
+    /// line, i.e. the position of its `'\n'`, or its `"\r\n"`. Line
+    /// and column `0` are clamped to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineCol, LineColByte, SnapPositionsExt};
+    ///
+    /// let text = "let 🦀 = 1;\r\nx\n";
+    ///
+    /// assert_eq!(text.clamp_to_line(LineCol(1, 5)), LineColByte(1, 5, 4));
+    /// assert_eq!(text.clamp_to_line(LineCol(1, 50)), LineColByte(1, 11, 13));
+    /// assert_eq!(text.clamp_to_line(LineCol(2, 0)), LineColByte(2, 1, 15));
+    /// assert_eq!(text.clamp_to_line(LineCol(9, 9)), LineColByte(3, 1, 17));
+    /// ```
+    fn clamp_to_line(&self, pos: LineCol) -> LineColByte;
+
+    /// Returns `offset` snapped to the nearest [`char`] boundary in the
+    /// direction of `bias`. An `offset` past the end of the text is
+    /// clamped to the end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{Bias, SnapPositionsExt};
+    ///
+    /// // "🦀" spans bytes 2..6
+    /// let text = "a 🦀 b";
+    ///
+    /// assert_eq!(text.snap_to_char_boundary(4, Bias::Left), 2);
+    /// assert_eq!(text.snap_to_char_boundary(4, Bias::Right), 6);
+    /// assert_eq!(text.snap_to_char_boundary(2, Bias::Right), 2);
+    /// assert_eq!(text.snap_to_char_boundary(50, Bias::Left), 8);
+    /// ```
+    fn snap_to_char_boundary(&self, offset: usize, bias: Bias) -> usize;
+}
+
+impl SnapPositionsExt for str {
+    fn clamp_to_line(&self, LineCol(line, col): LineCol) -> LineColByte {
+        let mut line_start = LineColByte(1, 1, 0);
+        for i in scan::newlines(self).take(line.saturating_sub(1)) {
+            line_start = LineColByte(line_start.0 + 1, 1, i + 1);
+        }
+
+        let text = &self[line_start.2..];
+        let text = match text.find('\n') {
+            Some(end) => text[..end].strip_suffix('\r').unwrap_or(&text[..end]),
+            None => text,
+        };
+
+        let mut pos = line_start;
+        for c in text.chars().take(col.saturating_sub(1)) {
+            pos.advance(c);
+        }
+        pos
+    }
+
+    fn snap_to_char_boundary(&self, offset: usize, bias: Bias) -> usize {
+        if offset >= self.len() {
+            return self.len();
+        }
+        let mut offset = offset;
+        match bias {
+            Bias::Left => {
+                while !self.is_char_boundary(offset) {
+                    offset -= 1;
+                }
+            }
+            Bias::Right => {
+                while !self.is_char_boundary(offset) {
+                    offset += 1;
+                }
+            }
+        }
+        offset
+    }
+}
@@ -0,0 +1,270 @@
+use alloc::borrow::Cow;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+    string::String,
+    vec::Vec,
+};
+
+use crate::{
+    CharPositions, CharPositionsExt, LineCol, LineColByte, LineColByteRange, LineIndex, Span,
+};
+
+#[cfg(feature = "std")]
+type Lazy<T> = std::sync::OnceLock<T>;
+#[cfg(not(feature = "std"))]
+type Lazy<T> = core::cell::OnceCell<T>;
+
+/// A named text, e.g. the contents of a file, along with a lazily
+/// built [`LineIndex`] for resolving positions.
+///
+/// The name and text can be either borrowed or owned.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{LineColByte, SourceFile};
+///
+/// let file = SourceFile::new("main.rs", "fn main() {\n    println!(\"🦀\");\n}\n");
+///
+/// assert_eq!(file.line(2), Some("    println!(\"🦀\");\n"));
+/// assert_eq!(file.position(16), Some(LineColByte(2, 5, 16)));
+///
+/// let span = file.span(16..24).unwrap();
+/// assert_eq!(span.start, LineColByte(2, 5, 16));
+/// assert_eq!(span.end, LineColByte(2, 13, 24));
+/// assert_eq!(file.snippet(span), Some("println!"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SourceFile<'a> {
+    name: Cow<'a, str>,
+    text: Cow<'a, str>,
+    index: Lazy<LineIndex>,
+}
+
+impl<'a> SourceFile<'a> {
+    #[inline]
+    pub fn new(name: impl Into<Cow<'a, str>>, text: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            name: name.into(),
+            text: text.into(),
+            index: Lazy::new(),
+        }
+    }
+
+    /// Reads the file at `path`, using the path as the name.
+    ///
+    /// See [`from_bytes()`](Self::from_bytes) for how the contents are decoded.
+    ///
+    /// The whole file is read into memory. Memory mapping is intentionally
+    /// not supported, as it requires `unsafe`, which this crate forbids.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use char_positions::{LineCol, SourceFile};
+    ///
+    /// let file = SourceFile::open("src/main.rs")?;
+    ///
+    /// for (LineCol(line, col), c) in file.char_positions() {
+    ///     println!("{}:{line}:{col} {c:?}", file.name());
+    /// }
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn open(path: impl AsRef<Path>) -> io::Result<SourceFile<'static>> {
+        let path = path.as_ref();
+        let bytes = fs::read(path)?;
+        SourceFile::from_bytes(path.to_string_lossy().into_owned(), bytes)
+    }
+
+    /// Decodes `bytes` as UTF-8, or as UTF-16 if `bytes` starts
+    /// with a UTF-16 BOM. The BOM is not included in the text.
+    ///
+    /// Byte positions are in the decoded UTF-8 text, and as such only match
+    /// `bytes` if it is UTF-8 without a BOM. For positions in the original
+    /// encoding, see [`encoding_rs`](https://docs.rs/char-positions/*/char_positions/encoding_rs/index.html),
+    /// which requires the `encoding_rs` feature.
+    ///
+    /// Returns an [`ErrorKind::InvalidData`] error if `bytes` is not valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::SourceFile;
+    ///
+    /// let file = SourceFile::from_bytes("bom.txt", b"\xEF\xBB\xBFHello".to_vec()).unwrap();
+    /// assert_eq!(file.text(), "Hello");
+    ///
+    /// let file = SourceFile::from_bytes("utf16.txt", b"\xFF\xFEH\0i\0".to_vec()).unwrap();
+    /// assert_eq!(file.text(), "Hi");
+    ///
+    /// assert!(SourceFile::from_bytes("invalid.txt", b"\xFF".to_vec()).is_err());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_bytes(name: impl Into<Cow<'a, str>>, bytes: Vec<u8>) -> io::Result<Self> {
+        let text =
+            decode_bytes(bytes).map_err(|msg| io::Error::new(ErrorKind::InvalidData, msg))?;
+        Ok(Self::new(name, text))
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns an iterator over the [`char`]s of the text and their positions.
+    ///
+    /// See [`CharPositionsExt::char_positions()`].
+    #[inline]
+    pub fn char_positions<T>(&self) -> CharPositions<'_, T>
+    where
+        LineColByteRange: Into<T>,
+    {
+        self.text.char_positions()
+    }
+
+    /// Returns the [`LineIndex`] of the text, building it
+    /// on first use.
+    #[inline]
+    pub fn line_index(&self) -> &LineIndex {
+        self.index.get_or_init(|| LineIndex::new(&self.text))
+    }
+
+    /// Returns the number of lines.
+    ///
+    /// See [`LineIndex::line_count()`].
+    #[inline]
+    pub fn line_count(&self) -> usize {
+        self.line_index().line_count()
+    }
+
+    /// Returns the 1-indexed `line`, including its `'\n'` if any.
+    #[inline]
+    pub fn line(&self, line: usize) -> Option<&str> {
+        let range = self.line_index().line_range(line)?;
+        Some(&self.text[range])
+    }
+
+    /// Returns an iterator over the line of `pos`, along with up to `n`
+    /// lines before and after it, as `(line, text, byte_range)`, where
+    /// `line` is 1-indexed, and `text` includes its `'\n'` if any.
+    ///
+    /// Returns `None` if the line of `pos` is out of bounds. The column
+    /// of `pos` is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineCol, SourceFile};
+    ///
+    /// let file = SourceFile::new("main.rs", "fn main() {\n    let x = 🦀;\n}\n");
+    ///
+    /// let lines = file.lines_around(LineCol(1, 4), 1).unwrap().collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     lines,
+    ///     [
+    ///         (1, "fn main() {\n", 0..12),
+    ///         (2, "    let x = 🦀;\n", 12..30),
+    ///     ],
+    /// );
+    ///
+    /// assert_eq!(file.lines_around(LineCol(3, 1), 5).unwrap().len(), 4);
+    /// assert!(file.lines_around(LineCol(5, 1), 1).is_none());
+    /// ```
+    pub fn lines_around(
+        &self,
+        pos: LineCol,
+        n: usize,
+    ) -> Option<impl ExactSizeIterator<Item = (usize, &str, Range<usize>)> + '_> {
+        let LineCol(line, _col) = pos;
+        if (line == 0) || (line > self.line_count()) {
+            return None;
+        }
+        let first = line.saturating_sub(n).max(1);
+        let end = line.saturating_add(n).min(self.line_count()) + 1;
+        Some((first..end).map(|line| {
+            let range = self.line_index().line_range(line).unwrap();
+            (line, &self.text[range.clone()], range)
+        }))
+    }
+
+    /// Returns the line, column, and byte position of the byte position `byte`.
+    ///
+    /// Returns `None` if `byte` is out of bounds or not on a [`char`] boundary.
+    #[inline]
+    pub fn position(&self, byte: usize) -> Option<LineColByte> {
+        self.line_index().line_col_byte(&self.text, byte)
+    }
+
+    /// Returns the byte position of the line and column `pos`.
+    ///
+    /// See [`LineIndex::byte_of()`].
+    #[inline]
+    pub fn byte_of(&self, pos: LineCol) -> Option<usize> {
+        self.line_index().byte_of(&self.text, pos)
+    }
+
+    /// Returns the [`Span`] of the byte `range`.
+    ///
+    /// Returns `None` if `range` is inverted, out of bounds, or not
+    /// on [`char`] boundaries.
+    pub fn span(&self, range: Range<usize>) -> Option<Span> {
+        if range.start > range.end {
+            return None;
+        }
+        let start = self.position(range.start)?;
+        let end = self.position(range.end)?;
+        Some(Span::new(start, end))
+    }
+
+    /// Returns the text covered by `span`.
+    #[inline]
+    pub fn snippet(&self, span: Span) -> Option<&str> {
+        self.text.get(span.byte_range())
+    }
+
+    /// Returns an owned `SourceFile`, cloning the name and text if they are borrowed.
+    pub fn into_owned(self) -> SourceFile<'static> {
+        SourceFile {
+            name: Cow::Owned(self.name.into_owned()),
+            text: Cow::Owned(self.text.into_owned()),
+            index: self.index,
+        }
+    }
+}
+
+/// Decodes `bytes` as UTF-8 or BOM prefixed UTF-16.
+#[cfg(feature = "std")]
+fn decode_bytes(mut bytes: Vec<u8>) -> Result<String, &'static str> {
+    let from_utf16 = |bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        let units = bytes.chunks(2).map(|b| match *b {
+            [a, b] => Ok(from_bytes([a, b])),
+            _ => Err("stream ended with an incomplete UTF-16 code unit"),
+        });
+        let mut text = String::with_capacity(bytes.len() / 2);
+        for unit in char::decode_utf16(units.collect::<Result<Vec<_>, _>>()?) {
+            text.push(unit.map_err(|_| "stream did not contain valid UTF-16")?);
+        }
+        Ok(text)
+    };
+
+    match bytes.as_slice() {
+        [0xFF, 0xFE, rest @ ..] => from_utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => from_utf16(rest, u16::from_be_bytes),
+        _ => {
+            if bytes.starts_with(b"\xEF\xBB\xBF") {
+                bytes.drain(..3);
+            }
+            String::from_utf8(bytes).map_err(|_| "stream did not contain valid UTF-8")
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{LineCol, SourceFile, Span};
+
+/// Identifies a [`SourceFile`] in a [`SourceMap`].
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct FileId(u32);
+
+impl FileId {
+    /// Returns the 0-indexed position of the file in its [`SourceMap`].
+    #[inline]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// `FileSpan { file, span }`
+///
+/// A [`Span`] qualified by the [`FileId`] of the file it belongs to.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct FileSpan {
+    pub file: FileId,
+    pub span: Span,
+}
+
+/// A collection of [`SourceFile`]s, each assigned a [`FileId`]
+/// and a range of global byte positions.
+///
+/// Global byte positions allow a single `usize` (or `Range<usize>`) to
+/// refer to a position in any of the files. The files are laid out one
+/// after another, separated by 1 unused byte position, such that the end
+/// of a file is never the same position as the start of the next.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{LineCol, SourceFile, SourceMap};
+///
+/// let mut map = SourceMap::new();
+/// let a = map.add(SourceFile::new("a.txt", "Hello\nWorld\n"));
+/// let b = map.add(SourceFile::new("b.txt", "🦀🦀"));
+///
+/// assert_eq!(map.global_range(a), Some(0..12));
+/// assert_eq!(map.global_range(b), Some(13..21));
+///
+/// assert_eq!(map.lookup(19), Some((b, 6)));
+/// assert_eq!(
+///     map.resolve(6..11),
+///     Some(("a.txt", LineCol(2, 1)..LineCol(2, 6))),
+/// );
+/// assert_eq!(
+///     map.resolve(17..21),
+///     Some(("b.txt", LineCol(1, 2)..LineCol(1, 3))),
+/// );
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct SourceMap<'a> {
+    files: Vec<SourceFile<'a>>,
+    /// The global start byte position of each file.
+    starts: Vec<usize>,
+}
+
+impl<'a> SourceMap<'a> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            files: Vec::new(),
+            starts: Vec::new(),
+        }
+    }
+
+    /// Adds `file`, and returns its [`FileId`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of files exceeds [`u32::MAX`] or the
+    /// global byte positions exceed [`usize::MAX`].
+    pub fn add(&mut self, file: SourceFile<'a>) -> FileId {
+        let id = u32::try_from(self.files.len()).expect("too many files");
+        let start = match (self.starts.last(), self.files.last()) {
+            (Some(&start), Some(last)) => start
+                .checked_add(last.text().len())
+                .and_then(|end| end.checked_add(1))
+                .expect("global byte position overflow"),
+            _ => 0,
+        };
+        self.files.push(file);
+        self.starts.push(start);
+        FileId(id)
+    }
+
+    /// Returns the [`FileId`] of the first file named `name`.
+    pub fn file_id(&self, name: &str) -> Option<FileId> {
+        let i = self.files.iter().position(|file| file.name() == name)?;
+        Some(FileId(i as u32))
+    }
+
+    #[inline]
+    pub fn file(&self, id: FileId) -> Option<&SourceFile<'a>> {
+        self.files.get(id.index())
+    }
+
+    /// Returns an iterator over all files and their [`FileId`]s.
+    #[inline]
+    pub fn files(&self) -> impl ExactSizeIterator<Item = (FileId, &SourceFile<'a>)> + '_ {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| (FileId(i as u32), file))
+    }
+
+    /// Returns the number of files.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns `true` if the map contains no files.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Returns the global byte range of the file.
+    #[inline]
+    pub fn global_range(&self, id: FileId) -> Option<Range<usize>> {
+        let start = *self.starts.get(id.index())?;
+        let len = self.files[id.index()].text().len();
+        Some(start..(start + len))
+    }
+
+    /// Converts a byte position local to the file into a global byte position.
+    #[inline]
+    pub fn to_global(&self, id: FileId, byte: usize) -> Option<usize> {
+        let range = self.global_range(id)?;
+        let byte = range.start.checked_add(byte)?;
+        (byte <= range.end).then_some(byte)
+    }
+
+    /// Returns the file containing the global byte position `byte`,
+    /// along with the byte position local to that file.
+    ///
+    /// Returns `None` for the unused byte position between files.
+    pub fn lookup(&self, byte: usize) -> Option<(FileId, usize)> {
+        let i = match self.starts.binary_search(&byte) {
+            Ok(i) => i,
+            Err(0) => return None,
+            Err(i) => i - 1,
+        };
+        let local = byte - self.starts[i];
+        (local <= self.files[i].text().len()).then_some((FileId(i as u32), local))
+    }
+
+    /// Returns the [`FileSpan`] of the global byte `range`.
+    ///
+    /// Returns `None` if `range` does not lie within a single file,
+    /// or if it is not on [`char`] boundaries.
+    pub fn span(&self, range: Range<usize>) -> Option<FileSpan> {
+        let (file, start) = self.lookup(range.start)?;
+        let end = range.end.checked_sub(self.starts[file.index()])?;
+        let span = self.files[file.index()].span(start..end)?;
+        Some(FileSpan { file, span })
+    }
+
+    /// Resolves the global byte `range` into the name of its file,
+    /// and the line and column range within it.
+    pub fn resolve(&self, range: Range<usize>) -> Option<(&str, Range<LineCol>)> {
+        let FileSpan { file, span } = self.span(range)?;
+        let name = self.files[file.index()].name();
+        Some((name, span.start_line_col()..span.end_line_col()))
+    }
+
+    /// Returns the global byte range of `span`.
+    #[inline]
+    pub fn global_span(&self, span: &FileSpan) -> Option<Range<usize>> {
+        let start = self.to_global(span.file, span.span.byte_start())?;
+        let end = self.to_global(span.file, span.span.byte_end())?;
+        Some(start..end)
+    }
+
+    /// Returns the text covered by `span`.
+    #[inline]
+    pub fn snippet(&self, span: &FileSpan) -> Option<&str> {
+        self.file(span.file)?.snippet(span.span)
+    }
+}
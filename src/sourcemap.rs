@@ -0,0 +1,471 @@
+//! Export and import of [source map v3] JSON, enabled by the `sourcemap` feature.
+//!
+//! Source maps map points in generated output, e.g. JavaScript or CSS,
+//! back to points in the original source, which browser devtools use to
+//! show the original source while debugging the generated output.
+//!
+//! Source map columns are 0-indexed, and counted in UTF-16 code units,
+//! so columns are converted accordingly. Only a single original source
+//! is supported, and names are not included.
+//!
+//! [source map v3]: https://tc39.es/source-map/
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::sourcemap::{from_source_map, to_source_map};
+//! use char_positions::{LineCol, SourceFile, SpanMap};
+//!
+//! let original = SourceFile::new("main.src", "print 🦀\nprint x\n");
+//! let generated = SourceFile::new("main.js", "console.log(\"🦀\");\nconsole.log(x);\n");
+//!
+//! let mut map = SpanMap::new();
+//! map.push(generated.span(0..20).unwrap(), original.span(0..10).unwrap());
+//! map.push(generated.span(21..36).unwrap(), original.span(11..18).unwrap());
+//! map.push(generated.span(33..34).unwrap(), original.span(17..18).unwrap());
+//!
+//! let json = to_source_map(&map, &generated, &original);
+//! assert_eq!(
+//!     json,
+//!     r#"{"version":3,"file":"main.js","sources":["main.src"],"names":[],"mappings":"AAAA,kB;AACA,YAAM,CAAN,E"}"#,
+//! );
+//!
+//! let map = from_source_map(&json, &generated, &original).unwrap();
+//! assert_eq!(map.generated_to_original(LineCol(2, 13)), Some(LineCol(2, 7)));
+//! assert_eq!(map.generated_to_original(LineCol(2, 5)), Some(LineCol(2, 1)));
+//! ```
+
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use crate::json::write_str;
+use crate::span_map::GeneratedSweep;
+use crate::{ColumnUnit, LineCol, SourceFile, Span, SpanMap};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// An error returned by [`from_source_map()`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct SourceMapError {
+    kind: ErrorKind,
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+enum ErrorKind {
+    Json,
+    Version,
+    Mappings,
+    OutOfBounds,
+}
+
+impl SourceMapError {
+    #[inline]
+    const fn new(kind: ErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for SourceMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.kind {
+            ErrorKind::Json => "invalid source map JSON",
+            ErrorKind::Version => "unsupported source map version, expected 3",
+            ErrorKind::Mappings => "invalid source map mappings",
+            ErrorKind::OutOfBounds => "source map position out of bounds",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SourceMapError {}
+
+/// Serializes `map` as a source map v3, where `generated` is the
+/// generated output, and `original` is the original source.
+///
+/// A segment is produced at the start of every generated span, at the
+/// start of every line within it, and at its end, mapped through
+/// [`SpanMap::generated_to_original()`]. Points which are not mapped
+/// produce a segment without an original position.
+///
+/// # Panics
+///
+/// Panics if any span is out of bounds of its text.
+pub fn to_source_map(
+    map: &SpanMap,
+    generated: &SourceFile<'_>,
+    original: &SourceFile<'_>,
+) -> String {
+    let mut out = String::new();
+    _ = write_source_map(&mut out, map, generated, original);
+    out
+}
+
+/// Writes `map` as a source map v3 to `w`.
+///
+/// See [`to_source_map()`].
+///
+/// # Panics
+///
+/// Panics if any span is out of bounds of its text.
+pub fn write_source_map(
+    w: &mut impl Write,
+    map: &SpanMap,
+    generated: &SourceFile<'_>,
+    original: &SourceFile<'_>,
+) -> fmt::Result {
+    let mut points = BTreeSet::new();
+    for m in map.mappings() {
+        let Span { start, end } = m.generated;
+        points.insert(LineCol(start.0, start.1));
+        points.extend(((start.0 + 1)..=end.0).map(|line| LineCol(line, 1)));
+        points.insert(LineCol(end.0, end.1));
+    }
+
+    w.write_str("{\"version\":3,\"file\":")?;
+    write_str(w, generated.name())?;
+    w.write_str(",\"sources\":[")?;
+    write_str(w, original.name())?;
+    w.write_str("],\"names\":[],\"mappings\":\"")?;
+
+    let mut sweep = GeneratedSweep::new(map);
+    let mut line = 1;
+    // The previous values of the relative fields
+    let (mut prev_col, mut prev_orig) = (0, LineCol(0, 0));
+    let mut first = true;
+    for pos in points {
+        let col = utf16_col(generated, pos);
+        if pos.0 > line {
+            for _ in line..pos.0 {
+                w.write_char(';')?;
+            }
+            line = pos.0;
+            prev_col = 0;
+        } else if !first {
+            w.write_char(',')?;
+        }
+        first = false;
+
+        write_vlq(w, col as i64 - prev_col as i64)?;
+        prev_col = col;
+
+        if let Some(orig) = sweep.map(pos) {
+            let orig = LineCol(orig.0 - 1, utf16_col(original, orig));
+            write_vlq(w, 0)?;
+            write_vlq(w, orig.0 as i64 - prev_orig.0 as i64)?;
+            write_vlq(w, orig.1 as i64 - prev_orig.1 as i64)?;
+            prev_orig = orig;
+        }
+    }
+
+    w.write_str("\"}")
+}
+
+/// Parses a source map v3 into a [`SpanMap`], where `generated` is the
+/// generated output, and `original` is the original source.
+///
+/// Each segment produces a mapping from the generated span between it and
+/// the next segment on the same line, or the end of the line, to an empty
+/// original span at its original position. Segments without an original
+/// position, or referring to any other source than the first, are skipped.
+///
+/// Returns an error if the segments of a line are not in increasing
+/// column order, as they would produce inverted spans, if the relative
+/// fields overflow, or if the JSON is nested too deeply.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::sourcemap::from_source_map;
+/// use char_positions::SourceFile;
+///
+/// let original = SourceFile::new("main.src", "print x\n");
+/// let generated = SourceFile::new("main.js", "console.log(x);\n");
+///
+/// // Columns 4 and 2
+/// let json = r#"{"version":3,"sources":["main.src"],"names":[],"mappings":"IAAA,FAAA"}"#;
+/// assert!(from_source_map(json, &generated, &original).is_err());
+///
+/// let json = r#"{"version":3,"sources":["main.src"],"names":[],"mappings":"EAAA,EAAA"}"#;
+/// assert_eq!(from_source_map(json, &generated, &original).unwrap().mappings().len(), 2);
+///
+/// // Columns overflowing `i64`
+/// let json = r#"{"version":3,"sources":["main.src"],"names":[],"mappings":"+///////////P,+///////////P"}"#;
+/// assert!(from_source_map(json, &generated, &original).is_err());
+///
+/// let json = format!(r#"{{"version":3,"x":{}{},"mappings":""}}"#, "[".repeat(1000), "]".repeat(1000));
+/// assert!(from_source_map(&json, &generated, &original).is_err());
+/// ```
+pub fn from_source_map(
+    json: &str,
+    generated: &SourceFile<'_>,
+    original: &SourceFile<'_>,
+) -> Result<SpanMap, SourceMapError> {
+    let mappings = parse_mappings_field(json)?;
+
+    let add = |a: i64, b: i64| {
+        a.checked_add(b)
+            .ok_or(SourceMapError::new(ErrorKind::Mappings))
+    };
+
+    // (generated line, generated column, original line and column), 0-indexed
+    let mut segments = Vec::new();
+    let (mut source, mut orig_line, mut orig_col) = (0i64, 0i64, 0i64);
+    for (line, segs) in mappings.split(';').enumerate() {
+        let mut col = 0i64;
+        for seg in segs.split(',').filter(|seg| !seg.is_empty()) {
+            let fields = decode_vlqs(seg)?;
+            col = add(col, fields[0])?;
+            if fields.len() >= 4 {
+                source = add(source, fields[1])?;
+                orig_line = add(orig_line, fields[2])?;
+                orig_col = add(orig_col, fields[3])?;
+            }
+            let orig = (fields.len() >= 4 && source == 0).then_some((orig_line, orig_col));
+            segments.push((line, col, orig));
+        }
+    }
+
+    let to_usize =
+        |n: i64| usize::try_from(n).map_err(|_| SourceMapError::new(ErrorKind::Mappings));
+    let mut map = SpanMap::new();
+    for (i, &(line, col, orig)) in segments.iter().enumerate() {
+        let Some((orig_line, orig_col)) = orig else {
+            continue;
+        };
+
+        let start = position(generated, line, to_usize(col)?)?;
+        let end = match segments.get(i + 1) {
+            Some(&(next_line, next_col, _)) if next_line == line => {
+                position(generated, line, to_usize(next_col)?)?
+            }
+            _ => {
+                let text = generated.line(line + 1).unwrap_or("");
+                let text = text.strip_suffix('\n').unwrap_or(text);
+                let text = text.strip_suffix('\r').unwrap_or(text);
+                generated
+                    .position(generated.line_index().line_start(line + 1).unwrap_or(0) + text.len())
+                    .ok_or(SourceMapError::new(ErrorKind::OutOfBounds))?
+            }
+        };
+        let orig = position(original, to_usize(orig_line)?, to_usize(orig_col)?)?;
+        let generated =
+            Span::try_new(start, end).ok_or(SourceMapError::new(ErrorKind::Mappings))?;
+        map.push(generated, Span::new(orig, orig));
+    }
+    Ok(map)
+}
+
+/// Returns the 0-indexed UTF-16 column of `pos` in `file`.
+fn utf16_col(file: &SourceFile<'_>, pos: LineCol) -> usize {
+    let byte = file.byte_of(pos).expect("position out of bounds");
+    let pos = file
+        .line_index()
+        .line_col_with_unit(file.text(), byte, ColumnUnit::Utf16)
+        .expect("position out of bounds");
+    pos.1 - 1
+}
+
+/// Returns the position of the 0-indexed `line` and UTF-16 `col` in `file`.
+fn position(
+    file: &SourceFile<'_>,
+    line: usize,
+    col: usize,
+) -> Result<crate::LineColByte, SourceMapError> {
+    file.line_index()
+        .byte_of_with_unit(file.text(), LineCol(line + 1, col + 1), ColumnUnit::Utf16)
+        .and_then(|byte| file.position(byte))
+        .ok_or(SourceMapError::new(ErrorKind::OutOfBounds))
+}
+
+/// Writes `n` as a base64 VLQ.
+fn write_vlq(w: &mut impl Write, n: i64) -> fmt::Result {
+    let mut n = (n.unsigned_abs() << 1) | u64::from(n < 0);
+    loop {
+        let mut digit = (n & 0b11111) as usize;
+        n >>= 5;
+        if n > 0 {
+            digit |= 0b100000;
+        }
+        w.write_char(BASE64[digit] as char)?;
+        if n == 0 {
+            return Ok(());
+        }
+    }
+}
+
+/// Decodes the base64 VLQs of a segment.
+fn decode_vlqs(seg: &str) -> Result<Vec<i64>, SourceMapError> {
+    let err = || SourceMapError::new(ErrorKind::Mappings);
+
+    let mut values = Vec::new();
+    let (mut n, mut shift) = (0u64, 0);
+    for b in seg.bytes() {
+        let digit = BASE64.iter().position(|&c| c == b).ok_or_else(err)? as u64;
+        n |= (digit & 0b11111).checked_shl(shift).ok_or_else(err)?;
+        shift += 5;
+        if digit & 0b100000 == 0 {
+            let value = (n >> 1) as i64;
+            values.push(if n & 1 == 1 { -value } else { value });
+            (n, shift) = (0, 0);
+        }
+    }
+
+    if (shift != 0) || !matches!(values.len(), 1 | 4 | 5) {
+        return Err(err());
+    }
+    Ok(values)
+}
+
+/// Returns the `mappings` of a source map, after checking its `version`.
+fn parse_mappings_field(json: &str) -> Result<String, SourceMapError> {
+    let mut p = JsonParser {
+        s: json.trim(),
+        depth: 0,
+    };
+    let mut version = None;
+    let mut mappings = None;
+
+    p.expect('{')?;
+    if !p.eat('}') {
+        loop {
+            let key = p.string()?;
+            p.expect(':')?;
+            match key.as_str() {
+                "version" => version = Some(p.value()?),
+                "mappings" => mappings = Some(p.string()?),
+                _ => _ = p.value()?,
+            }
+            if p.eat('}') {
+                break;
+            }
+            p.expect(',')?;
+        }
+    }
+
+    if version != Some("3") {
+        return Err(SourceMapError::new(ErrorKind::Version));
+    }
+    mappings.ok_or(SourceMapError::new(ErrorKind::Json))
+}
+
+/// The maximum nesting of arrays and objects skipped by [`JsonParser::value()`].
+const MAX_DEPTH: usize = 128;
+
+/// A minimal JSON parser, which skips over values it does not need.
+struct JsonParser<'a> {
+    s: &'a str,
+    /// The nesting of the arrays and objects currently being skipped.
+    depth: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn eat(&mut self, c: char) -> bool {
+        self.s = self.s.trim_start();
+        match self.s.strip_prefix(c) {
+            Some(rest) => {
+                self.s = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), SourceMapError> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(SourceMapError::new(ErrorKind::Json)),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, SourceMapError> {
+        let err = || SourceMapError::new(ErrorKind::Json);
+        self.expect('"')?;
+
+        let mut out = String::new();
+        let mut chars = self.s.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.s = &self.s[(i + 1)..];
+                    return Ok(out);
+                }
+                '\\' => match chars.next().ok_or_else(err)?.1 {
+                    'b' => out.push('\u{8}'),
+                    'f' => out.push('\u{c}'),
+                    'n' => out.push('\n'),
+                    'r' => out.push('\r'),
+                    't' => out.push('\t'),
+                    'u' => {
+                        let hex = (0..4)
+                            .map(|_| chars.next().map(|(_, c)| c))
+                            .collect::<Option<String>>()
+                            .ok_or_else(err)?;
+                        let c = u32::from_str_radix(&hex, 16).map_err(|_| err())?;
+                        out.push(char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    c => out.push(c),
+                },
+                c => out.push(c),
+            }
+        }
+        Err(err())
+    }
+
+    /// Skips a value, returning its text.
+    ///
+    /// Returns an error if arrays and objects are nested deeper than [`MAX_DEPTH`].
+    fn value(&mut self) -> Result<&'a str, SourceMapError> {
+        self.s = self.s.trim_start();
+        let start = self.s;
+        if self.s.starts_with('"') {
+            self.string()?;
+        } else if self.eat('[') {
+            self.enter()?;
+            if !self.eat(']') {
+                loop {
+                    self.value()?;
+                    if self.eat(']') {
+                        break;
+                    }
+                    self.expect(',')?;
+                }
+            }
+            self.depth -= 1;
+        } else if self.eat('{') {
+            self.enter()?;
+            if !self.eat('}') {
+                loop {
+                    self.string()?;
+                    self.expect(':')?;
+                    self.value()?;
+                    if self.eat('}') {
+                        break;
+                    }
+                    self.expect(',')?;
+                }
+            }
+            self.depth -= 1;
+        } else {
+            let end = self
+                .s
+                .find(|c: char| matches!(c, ',' | '}' | ']') || c.is_whitespace())
+                .unwrap_or(self.s.len());
+            if end == 0 {
+                return Err(SourceMapError::new(ErrorKind::Json));
+            }
+            self.s = &self.s[end..];
+        }
+        Ok(&start[..(start.len() - self.s.len())])
+    }
+
+    /// Enters a nested array or object.
+    fn enter(&mut self) -> Result<(), SourceMapError> {
+        if self.depth == MAX_DEPTH {
+            return Err(SourceMapError::new(ErrorKind::Json));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,376 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{ByteRange, LineCol, LineColByte, RelativePos};
+
+/// `Span { start, end }`
+///
+/// A range of text, with the line, column, and byte position
+/// of both its `start` (inclusive) and `end` (exclusive).
+///
+/// All operations compare positions by their byte position, so spans
+/// being combined must be from the same text.
+///
+/// Spans are ordered by the byte position of their start, then of their
+/// end, with lines and columns only breaking ties, such that ordering
+/// agrees with equality.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{LineColByte, Span};
+///
+/// // "Hello World"
+/// let hello = Span::new(LineColByte(1, 1, 0), LineColByte(1, 6, 5));
+/// let world = Span::new(LineColByte(1, 7, 6), LineColByte(1, 12, 11));
+/// let lo_wo = Span::new(LineColByte(1, 4, 3), LineColByte(1, 9, 8));
+///
+/// assert_eq!(hello.len_bytes(), 5);
+/// assert!(hello.contains(4));
+/// assert!(!hello.contains(5));
+///
+/// assert!(!hello.intersects(&world));
+/// assert!(hello.intersects(&lo_wo));
+/// assert_eq!(
+///     hello.intersection(&lo_wo),
+///     Some(Span::new(LineColByte(1, 4, 3), LineColByte(1, 6, 5))),
+/// );
+///
+/// let hello_world = hello.union(&world);
+/// assert_eq!(hello_world.byte_range(), 0..11);
+/// assert!(hello_world.contains_span(&lo_wo));
+///
+/// assert_eq!(hello.merge_adjacent(&world), None);
+/// assert!(hello < lo_wo && lo_wo < world);
+///
+/// // Same bytes, but different lines and columns, e.g. from different texts
+/// let other = Span::new(LineColByte(2, 1, 0), LineColByte(2, 6, 5));
+/// assert_ne!(hello, other);
+/// assert_ne!(hello.cmp(&other), std::cmp::Ordering::Equal);
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde::RawSpan"))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(::schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Span {
+    /// The start (inclusive) position.
+    pub start: LineColByte,
+    /// The end (exclusive) position.
+    pub end: LineColByte,
+}
+
+impl Span {
+    /// Returns a span from `start` to `end`.
+    ///
+    /// Use [`Span::try_new()`] when the positions come from external data.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `end` is before `start`, or if either
+    /// has a line or column of `0`.
+    #[inline]
+    pub const fn new(start: LineColByte, end: LineColByte) -> Self {
+        debug_assert!(is_valid(start, end), "invalid span");
+        Self { start, end }
+    }
+
+    /// Returns `None` if `end` is before `start`, or if either
+    /// has a line or column of `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineColByte, Span};
+    ///
+    /// let (start, end) = (LineColByte(1, 7, 6), LineColByte(1, 12, 11));
+    /// assert_eq!(Span::try_new(start, end), Some(Span::new(start, end)));
+    /// assert_eq!(Span::try_new(end, start), None);
+    /// assert_eq!(Span::try_new(LineColByte(0, 7, 6), end), None);
+    /// ```
+    #[inline]
+    pub const fn try_new(start: LineColByte, end: LineColByte) -> Option<Self> {
+        if !is_valid(start, end) {
+            return None;
+        }
+        Some(Self { start, end })
+    }
+
+    /// Returns the line and column of `start`.
+    #[inline]
+    pub const fn start_line_col(&self) -> LineCol {
+        LineCol(self.start.0, self.start.1)
+    }
+
+    /// Returns the line and column of `end`.
+    #[inline]
+    pub const fn end_line_col(&self) -> LineCol {
+        LineCol(self.end.0, self.end.1)
+    }
+
+    /// Inclusive.
+    #[inline]
+    pub const fn byte_start(&self) -> usize {
+        self.start.2
+    }
+
+    /// Exclusive.
+    #[inline]
+    pub const fn byte_end(&self) -> usize {
+        self.end.2
+    }
+
+    #[inline]
+    pub const fn byte_range(&self) -> Range<usize> {
+        self.start.2..self.end.2
+    }
+
+    /// Returns the length of the span in bytes.
+    #[inline]
+    pub const fn len_bytes(&self) -> usize {
+        self.end.2.saturating_sub(self.start.2)
+    }
+
+    /// Returns `true` if the span covers no bytes.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.end.2 <= self.start.2
+    }
+
+    /// Returns `true` if the byte position `byte` is within the span.
+    #[inline]
+    pub const fn contains(&self, byte: usize) -> bool {
+        (self.start.2 <= byte) && (byte < self.end.2)
+    }
+
+    /// Returns `true` if `other` is entirely within the span.
+    #[inline]
+    pub const fn contains_span(&self, other: &Self) -> bool {
+        (self.start.2 <= other.start.2) && (other.end.2 <= self.end.2)
+    }
+
+    /// Returns `true` if the spans share at least 1 byte.
+    #[inline]
+    pub const fn intersects(&self, other: &Self) -> bool {
+        (self.start.2 < other.end.2) && (other.start.2 < self.end.2)
+    }
+
+    /// Returns `true` if the spans share at least 1 byte, or
+    /// if one ends exactly where the other starts.
+    #[inline]
+    pub const fn touches(&self, other: &Self) -> bool {
+        (self.start.2 <= other.end.2) && (other.start.2 <= self.end.2)
+    }
+
+    /// Returns the span covered by both spans, if they [intersect](Self::intersects).
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        if !self.intersects(other) {
+            return None;
+        }
+        let start = max_pos(self.start, other.start);
+        let end = min_pos(self.end, other.end);
+        Some(Self::new(start, end))
+    }
+
+    /// Returns the smallest span covering both spans,
+    /// including anything in between them.
+    pub fn union(&self, other: &Self) -> Self {
+        let start = min_pos(self.start, other.start);
+        let end = max_pos(self.end, other.end);
+        Self::new(start, end)
+    }
+
+    /// Returns the [union](Self::union) of the spans, if they
+    /// [touch](Self::touches), i.e. if there is nothing in between them.
+    #[inline]
+    pub fn merge_adjacent(&self, other: &Self) -> Option<Self> {
+        self.touches(other).then(|| self.union(other))
+    }
+
+    /// Relocates the span from a text where `from_base` is the
+    /// position of the relocated part, to a text where `to_base`
+    /// is the position of the relocated part.
+    ///
+    /// Returns `None` if the span starts before `from_base`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineColByte, Span};
+    ///
+    /// // "🦀" in "let x = 🦀;", expanded into a document at line 5, column 3
+    /// let crab = Span::new(LineColByte(1, 9, 8), LineColByte(1, 10, 12));
+    ///
+    /// assert_eq!(
+    ///     crab.rebase(LineColByte(1, 1, 0), LineColByte(5, 3, 40)),
+    ///     Some(Span::new(LineColByte(5, 11, 48), LineColByte(5, 12, 52))),
+    /// );
+    /// ```
+    pub fn rebase(&self, from_base: LineColByte, to_base: LineColByte) -> Option<Self> {
+        let start = RelativePos::between(from_base, self.start)?;
+        let end = RelativePos::between(from_base, self.end)?;
+        Some(Self::new(to_base + start, to_base + end))
+    }
+
+    /// Returns an iterator over the parts of the span on each line,
+    /// where `text` is the text the span is from.
+    ///
+    /// Each part is a single-line [`Span`], which excludes the `'\n'`
+    /// of the line. If the span ends right after a `'\n'`, then no
+    /// empty part is produced for the following line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the span's byte range is not within `text`, or
+    /// not on [`char`] boundaries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineCol, LineColByte, Span};
+    ///
+    /// let text = "let 🦀 = [\n\n    1,\n];\n";
+    /// let span = Span::new(LineColByte(1, 9, 11), LineColByte(4, 2, 22));
+    ///
+    /// let lines = span
+    ///     .split_by_lines(text)
+    ///     .map(|span| (span.start_line_col(), span.end_line_col(), &text[span.byte_range()]))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     lines,
+    ///     [
+    ///         (LineCol(1, 9), LineCol(1, 10), "["),
+    ///         (LineCol(2, 1), LineCol(2, 1), ""),
+    ///         (LineCol(3, 1), LineCol(3, 7), "    1,"),
+    ///         (LineCol(4, 1), LineCol(4, 2), "]"),
+    ///     ],
+    /// );
+    /// ```
+    #[inline]
+    pub fn split_by_lines<'a>(&self, text: &'a str) -> SpanLines<'a> {
+        SpanLines {
+            rest: Some(&text[self.byte_range()]),
+            pos: self.start,
+        }
+    }
+}
+
+/// An iterator over the parts of a [`Span`] on each line.
+///
+/// See [`Span::split_by_lines()`].
+#[derive(Clone, Debug)]
+pub struct SpanLines<'a> {
+    /// `None` when the iterator is exhausted.
+    rest: Option<&'a str>,
+    /// The position of the start of `rest`.
+    pos: LineColByte,
+}
+
+impl Iterator for SpanLines<'_> {
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest?;
+        let (line, next) = match rest.split_once('\n') {
+            Some((line, next)) => (line, Some(next).filter(|next| !next.is_empty())),
+            None => (rest, None),
+        };
+
+        let start = self.pos;
+        let mut end = start;
+        end.advance_str(line);
+
+        self.rest = next;
+        self.pos = LineColByte(end.0 + 1, 1, end.2 + 1);
+
+        Some(Span::new(start, end))
+    }
+}
+
+impl FusedIterator for SpanLines<'_> {}
+
+#[inline]
+fn min_pos(a: LineColByte, b: LineColByte) -> LineColByte {
+    if b.2 < a.2 {
+        b
+    } else {
+        a
+    }
+}
+
+#[inline]
+fn max_pos(a: LineColByte, b: LineColByte) -> LineColByte {
+    if b.2 > a.2 {
+        b
+    } else {
+        a
+    }
+}
+
+impl PartialOrd for Span {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Span {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |span: &Self| {
+            let (LineColByte(l1, c1, b1), LineColByte(l2, c2, b2)) = (span.start, span.end);
+            (b1, b2, l1, c1, l2, c2)
+        };
+        key(self).cmp(&key(other))
+    }
+}
+
+impl From<Span> for Range<usize> {
+    #[inline]
+    fn from(span: Span) -> Self {
+        span.byte_range()
+    }
+}
+
+impl From<Span> for ByteRange {
+    #[inline]
+    fn from(span: Span) -> Self {
+        Self(span.byte_range())
+    }
+}
+
+impl fmt::Display for Span {
+    /// Formats the lines and columns of the span as `line:col-line:col`,
+    /// which can be parsed again using [`Span::from_str()`] or
+    /// [`LineCol::parse_range()`].
+    ///
+    /// [`Span::from_str()`]: core::str::FromStr::from_str
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineColByte, Span};
+    ///
+    /// let span = Span::new(LineColByte(12, 5, 100), LineColByte(14, 2, 130));
+    /// assert_eq!(span.to_string(), "12:5-14:2");
+    /// ```
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start_line_col(), self.end_line_col())
+    }
+}
+
+const fn is_valid(start: LineColByte, end: LineColByte) -> bool {
+    (start.0 != 0)
+        && (start.1 != 0)
+        && (end.0 != 0)
+        && (end.1 != 0)
+        && (start.2 <= end.2)
+        && ((start.0 < end.0) || ((start.0 == end.0) && (start.1 <= end.1)))
+}
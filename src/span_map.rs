@@ -0,0 +1,215 @@
+use alloc::vec::Vec;
+
+use crate::{LineCol, LineColByte, PositionTrackingWriter, RelativePos, Span};
+
+/// `SpanMapping { generated, original }`
+///
+/// A [`Span`] of generated output, and the [`Span`] of the
+/// original source it was generated from.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct SpanMapping {
+    pub generated: Span,
+    pub original: Span,
+}
+
+/// Mappings between spans of generated output and the original source,
+/// e.g. recorded by a transpiler while emitting code through a
+/// [`PositionTrackingWriter`].
+///
+/// Positions are mapped through the innermost, i.e. shortest, mapping
+/// containing them. If both spans of the mapping have the same extent,
+/// e.g. for text copied verbatim, positions map exactly. Otherwise,
+/// positions map to the start of the other span.
+///
+/// # Example
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// use char_positions::{LineCol, PositionTrackingWriter, SourceFile, SpanMap};
+///
+/// let source = SourceFile::new("main.src", "print 🦀\nprint x + 1\n");
+///
+/// let mut w = PositionTrackingWriter::new(String::new());
+/// let mut map = SpanMap::new();
+///
+/// for (range, arg) in [(0..10, 6..10), (11..22, 17..22)] {
+///     let stmt = w.position();
+///     w.write_str("console.log(").unwrap();
+///
+///     let expr = w.position();
+///     w.write_str(source.snippet(source.span(arg.clone()).unwrap()).unwrap()).unwrap();
+///     map.record(&w, expr, source.span(arg).unwrap());
+///
+///     w.write_str(");\n").unwrap();
+///     map.record(&w, stmt, source.span(range).unwrap());
+/// }
+///
+/// assert_eq!(w.get_ref(), "console.log(🦀);\nconsole.log(x + 1);\n");
+///
+/// // Within `x + 1`, which is copied verbatim
+/// assert_eq!(map.generated_to_original(LineCol(2, 17)), Some(LineCol(2, 11)));
+/// assert_eq!(map.original_to_generated(LineCol(2, 11)), Some(LineCol(2, 17)));
+///
+/// // Within `console.log(`, which maps to the start of the statement
+/// assert_eq!(map.generated_to_original(LineCol(2, 4)), Some(LineCol(2, 1)));
+///
+/// assert_eq!(map.generated_to_original(LineCol(9, 1)), None);
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct SpanMap {
+    mappings: Vec<SpanMapping>,
+}
+
+impl SpanMap {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            mappings: Vec::new(),
+        }
+    }
+
+    /// Records that the `generated` span was generated from the `original` span.
+    #[inline]
+    pub fn push(&mut self, generated: Span, original: Span) {
+        self.mappings.push(SpanMapping {
+            generated,
+            original,
+        });
+    }
+
+    /// Records that everything written to `w` since the position `start`
+    /// was generated from the `original` span.
+    ///
+    /// See [`PositionTrackingWriter::span_from()`].
+    #[inline]
+    pub fn record<W>(&mut self, w: &PositionTrackingWriter<W>, start: LineColByte, original: Span) {
+        self.push(w.span_from(start), original);
+    }
+
+    /// Returns the recorded mappings, in the order they were recorded.
+    #[inline]
+    pub fn mappings(&self) -> &[SpanMapping] {
+        &self.mappings
+    }
+
+    /// Returns the innermost mapping whose generated span contains `pos`.
+    #[inline]
+    pub fn find_generated(&self, pos: LineCol) -> Option<&SpanMapping> {
+        innermost(&self.mappings, pos, |m| (&m.generated, &m.original)).map(|(m, _)| m)
+    }
+
+    /// Returns the innermost mapping whose original span contains `pos`.
+    #[inline]
+    pub fn find_original(&self, pos: LineCol) -> Option<&SpanMapping> {
+        innermost(&self.mappings, pos, |m| (&m.original, &m.generated)).map(|(m, _)| m)
+    }
+
+    /// Maps `pos` in the generated output to the original source.
+    ///
+    /// Returns `None` if no mapping contains `pos`.
+    #[inline]
+    pub fn generated_to_original(&self, pos: LineCol) -> Option<LineCol> {
+        innermost(&self.mappings, pos, |m| (&m.generated, &m.original)).map(|(_, pos)| pos)
+    }
+
+    /// Maps `pos` in the original source to the generated output.
+    ///
+    /// Returns `None` if no mapping contains `pos`.
+    #[inline]
+    pub fn original_to_generated(&self, pos: LineCol) -> Option<LineCol> {
+        innermost(&self.mappings, pos, |m| (&m.original, &m.generated)).map(|(_, pos)| pos)
+    }
+}
+
+/// Returns the shortest mapping where the `from` span contains `pos`,
+/// along with `pos` mapped to the `to` span.
+fn innermost(
+    mappings: &[SpanMapping],
+    pos: LineCol,
+    f: impl Fn(&SpanMapping) -> (&Span, &Span),
+) -> Option<(&SpanMapping, LineCol)> {
+    let m = mappings
+        .iter()
+        .filter(|m| contains(f(m).0, pos))
+        .min_by_key(|m| f(m).0.len_bytes())?;
+
+    let (from, to) = f(m);
+    Some((m, map_pos(from, to, pos)?))
+}
+
+/// Returns whether `span` contains `pos`, where empty spans contain their start.
+#[inline]
+fn contains(span: &Span, pos: LineCol) -> bool {
+    let (start, end) = (span.start_line_col(), span.end_line_col());
+    (start <= pos) && ((pos < end) || (pos == start))
+}
+
+/// Maps `pos` within `from` to `to`.
+fn map_pos(from: &Span, to: &Span, pos: LineCol) -> Option<LineCol> {
+    let extent = |span: &Span| RelativePos::between(span.start, span.end);
+    if extent(from) == extent(to) {
+        let start = from.start_line_col();
+        let offset = RelativePos::between(
+            LineColByte(start.0, start.1, 0),
+            LineColByte(pos.0, pos.1, 0),
+        )?;
+        Some(to.start_line_col() + offset)
+    } else {
+        Some(to.start_line_col())
+    }
+}
+
+/// Maps increasing positions in the generated output to the original
+/// source, like [`SpanMap::generated_to_original()`], in a single pass
+/// over the mappings sorted by their generated start.
+#[cfg(feature = "sourcemap")]
+pub(crate) struct GeneratedSweep<'a> {
+    mappings: &'a [SpanMapping],
+    /// Indices of the mappings, sorted by their generated start.
+    sorted: Vec<usize>,
+    next: usize,
+    /// Indices of the mappings which may contain the current position.
+    active: Vec<usize>,
+}
+
+#[cfg(feature = "sourcemap")]
+impl<'a> GeneratedSweep<'a> {
+    pub(crate) fn new(map: &'a SpanMap) -> Self {
+        let mappings = map.mappings();
+        let mut sorted = (0..mappings.len()).collect::<Vec<_>>();
+        sorted.sort_by_key(|&i| mappings[i].generated.start_line_col());
+        Self {
+            mappings,
+            sorted,
+            next: 0,
+            active: Vec::new(),
+        }
+    }
+
+    /// Returns `pos` mapped to the original source, where `pos` must not
+    /// be before any previous position.
+    pub(crate) fn map(&mut self, pos: LineCol) -> Option<LineCol> {
+        let mappings = self.mappings;
+        while let Some(&i) = self.sorted.get(self.next) {
+            if mappings[i].generated.start_line_col() > pos {
+                break;
+            }
+            self.active.push(i);
+            self.next += 1;
+        }
+        // Mappings which no longer contain `pos`, cannot contain any later position
+        self.active
+            .retain(|&i| contains(&mappings[i].generated, pos));
+
+        // Ties are resolved by the order the mappings were recorded in
+        let &i = self
+            .active
+            .iter()
+            .min_by_key(|&&i| (mappings[i].generated.len_bytes(), i))?;
+        let m = &mappings[i];
+        map_pos(&m.generated, &m.original, pos)
+    }
+}
@@ -0,0 +1,293 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{LineCol, LineColByte, Span};
+
+/// A set of [`Span`]s supporting interval queries, e.g. finding
+/// the spans under the cursor for hover or go-to-definition.
+///
+/// Spans are kept sorted by their [order](Span#impl-Ord-for-Span), along
+/// with the maximum end of all spans up to each span, such that queries
+/// only visit spans which start before and end after the queried range.
+/// Inserting is `O(n)`, so prefer collecting into a `SpanSet` when
+/// building it from many spans.
+///
+/// All queries compare byte positions, except those taking a [`LineCol`],
+/// which rely on the lines and columns being in the same order as the
+/// byte positions, i.e. the spans must be from the same text.
+///
+/// Spans with the same byte positions, but different lines or columns,
+/// are distinct, the same as for [`Span`]'s `==`.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{SpanResolver, SpanSet};
+///
+/// let text = "fn main() { let 🦀 = foo(1, 2); }";
+/// let mut resolver = SpanResolver::new(text);
+///
+/// let set = [0..35, 10..35, 12..33, 23..32, 27..28, 30..31]
+///     .into_iter()
+///     .map(|range| resolver.span(range).unwrap())
+///     .collect::<SpanSet>();
+///
+/// let containing = set.spans_containing(27).map(|s| s.byte_range()).collect::<Vec<_>>();
+/// assert_eq!(containing, [0..35, 10..35, 12..33, 23..32, 27..28]);
+///
+/// let overlapping = set.spans_overlapping(28..31).map(|s| s.byte_range()).collect::<Vec<_>>();
+/// assert_eq!(overlapping, [0..35, 10..35, 12..33, 23..32, 30..31]);
+///
+/// assert_eq!(set.nearest(29).map(|s| s.byte_range()), Some(23..32));
+/// assert_eq!(set.nearest(40).map(|s| s.byte_range()), Some(0..35));
+///
+/// assert_eq!(set.spans_containing(usize::MAX).count(), 0);
+/// assert_eq!(set.spans_overlapping(usize::MAX..usize::MAX).count(), 0);
+/// assert_eq!(set.nearest(usize::MAX).map(|s| s.byte_range()), Some(0..35));
+/// ```
+///
+/// Spans with equal byte positions:
+///
+/// ```
+/// use char_positions::{LineColByte, Span, SpanSet};
+///
+/// let a = Span::new(LineColByte(1, 1, 0), LineColByte(1, 2, 1));
+/// let b = Span::new(LineColByte(2, 1, 0), LineColByte(2, 2, 1));
+///
+/// let mut set = [a, b, a].into_iter().collect::<SpanSet>();
+/// assert_eq!(set.len(), 2);
+/// assert!(set.contains(&a) && set.contains(&b));
+/// assert!(!set.insert(b));
+///
+/// assert!(set.remove(&a));
+/// assert!(!set.contains(&a) && set.contains(&b));
+/// assert_eq!(set.spans_containing(0).collect::<Vec<_>>(), [&b]);
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Default, Debug)]
+pub struct SpanSet {
+    spans: Vec<Span>,
+    /// The maximum end position of `spans[..=i]`.
+    max_ends: Vec<LineColByte>,
+}
+
+impl SpanSet {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            spans: Vec::new(),
+            max_ends: Vec::new(),
+        }
+    }
+
+    /// Inserts `span`, returning `false` if it was already present.
+    pub fn insert(&mut self, span: Span) -> bool {
+        let i = match self.spans.binary_search(&span) {
+            Ok(_) => return false,
+            Err(i) => i,
+        };
+        self.spans.insert(i, span);
+        self.max_ends.insert(i, span.end);
+        self.update_max_ends(i);
+        true
+    }
+
+    /// Removes `span`, returning `false` if it was not present.
+    pub fn remove(&mut self, span: &Span) -> bool {
+        let Ok(i) = self.spans.binary_search(span) else {
+            return false;
+        };
+        self.spans.remove(i);
+        self.max_ends.remove(i);
+        self.update_max_ends(i);
+        true
+    }
+
+    #[inline]
+    pub fn contains(&self, span: &Span) -> bool {
+        self.spans.binary_search(span).is_ok()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Returns an iterator over the spans in order.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, Span> {
+        self.spans.iter()
+    }
+
+    /// Returns an iterator over the spans [containing](Span::contains) the
+    /// byte position `byte`, in order, i.e. outermost first if nested.
+    #[inline]
+    pub fn spans_containing(&self, byte: usize) -> impl Iterator<Item = &Span> + '_ {
+        self.spans_overlapping(byte..byte.saturating_add(1))
+    }
+
+    /// Returns an iterator over the spans sharing at least 1 byte with
+    /// the byte `range`, in order.
+    ///
+    /// An empty `range` finds the spans containing its start.
+    pub fn spans_overlapping(&self, range: Range<usize>) -> impl Iterator<Item = &Span> + '_ {
+        let range = range.start..range.end.max(range.start.saturating_add(1));
+        let first = self.max_ends.partition_point(|end| end.2 <= range.start);
+        let last = self.spans.partition_point(|s| s.byte_start() < range.end);
+        self.spans[first..last.max(first)]
+            .iter()
+            .filter(move |s| (s.byte_end() > range.start) && !s.is_empty())
+    }
+
+    /// Returns the innermost span [containing](Span::contains) `byte`,
+    /// or if none, the span closest to `byte`.
+    ///
+    /// Ties are resolved in favor of the span which comes first.
+    pub fn nearest(&self, byte: usize) -> Option<&Span> {
+        if let Some(span) = self.spans_containing(byte).min_by_key(|s| s.len_bytes()) {
+            return Some(span);
+        }
+        self.spans.iter().min_by_key(|s| {
+            if byte < s.byte_start() {
+                s.byte_start() - byte
+            } else {
+                // The span ends at or before `byte`
+                (byte - s.byte_end()).saturating_add(1)
+            }
+        })
+    }
+
+    /// Returns the innermost span containing the line and column `pos`,
+    /// e.g. the node under the cursor for hover.
+    ///
+    /// A span contains `pos` if it is at or after its start, and before
+    /// its end. Of the spans containing `pos`, the shortest is innermost.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineCol, SpanResolver, SpanSet};
+    ///
+    /// let text = "fn main() {\n    foo(🦀, 2);\n}";
+    /// let mut resolver = SpanResolver::new(text);
+    ///
+    /// // Function, body, call, and arguments
+    /// let set = [0..31, 10..31, 16..28, 20..24, 26..27]
+    ///     .into_iter()
+    ///     .map(|range| resolver.span(range).unwrap())
+    ///     .collect::<SpanSet>();
+    ///
+    /// let span = set.innermost_span_at(LineCol(2, 9)).unwrap();
+    /// assert_eq!(span.byte_range(), 20..24);
+    ///
+    /// let span = set.innermost_span_at(LineCol(2, 10)).unwrap();
+    /// assert_eq!(span.byte_range(), 16..28);
+    ///
+    /// assert_eq!(set.innermost_span_at(LineCol(5, 1)), None);
+    /// ```
+    pub fn innermost_span_at(&self, pos: LineCol) -> Option<&Span> {
+        self.spans_containing_line_col(pos)
+            .min_by_key(|s| s.len_bytes())
+    }
+
+    /// Returns the chain of nested spans at the line and column `pos`,
+    /// from the [innermost](Self::innermost_span_at) to the outermost,
+    /// e.g. for selection expansion.
+    ///
+    /// Each span in the chain [contains](Span::contains_span) the previous
+    /// span. Spans which contain `pos`, but only partially overlap the
+    /// previous span, are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineCol, SpanResolver, SpanSet};
+    ///
+    /// let text = "fn main() {\n    foo(🦀, 2);\n}";
+    /// let mut resolver = SpanResolver::new(text);
+    ///
+    /// let set = [0..31, 10..31, 16..28, 20..24, 26..27]
+    ///     .into_iter()
+    ///     .map(|range| resolver.span(range).unwrap())
+    ///     .collect::<SpanSet>();
+    ///
+    /// let chain = set
+    ///     .ancestor_chain_at(LineCol(2, 9))
+    ///     .into_iter()
+    ///     .map(|s| &text[s.byte_range()])
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(chain, ["🦀", "foo(🦀, 2)", "{\n    foo(🦀, 2);\n}", text]);
+    /// ```
+    pub fn ancestor_chain_at(&self, pos: LineCol) -> Vec<&Span> {
+        let mut spans = self.spans_containing_line_col(pos).collect::<Vec<_>>();
+        spans.sort_by_key(|s| s.len_bytes());
+
+        let mut chain: Vec<&Span> = Vec::with_capacity(spans.len());
+        for span in spans {
+            match chain.last() {
+                Some(last) if !span.contains_span(last) => {}
+                _ => chain.push(span),
+            }
+        }
+        chain
+    }
+
+    /// Returns an iterator over the spans containing the line and column `pos`, in order.
+    fn spans_containing_line_col(&self, pos: LineCol) -> impl Iterator<Item = &Span> + '_ {
+        let first = self
+            .max_ends
+            .partition_point(|end| LineCol(end.0, end.1) <= pos);
+        let last = self.spans.partition_point(|s| s.start_line_col() <= pos);
+        self.spans[first..last.max(first)]
+            .iter()
+            .filter(move |s| s.end_line_col() > pos)
+    }
+
+    /// Recomputes `max_ends[i..]`.
+    fn update_max_ends(&mut self, i: usize) {
+        let mut max = i.checked_sub(1).map(|i| self.max_ends[i]);
+        for (span, end) in self.spans[i..].iter().zip(&mut self.max_ends[i..]) {
+            let new = match max {
+                Some(max) if max.2 >= span.end.2 => max,
+                _ => span.end,
+            };
+            *end = new;
+            max = Some(new);
+        }
+    }
+}
+
+impl FromIterator<Span> for SpanSet {
+    fn from_iter<I: IntoIterator<Item = Span>>(iter: I) -> Self {
+        let mut spans = iter.into_iter().collect::<Vec<_>>();
+        spans.sort_unstable();
+        spans.dedup();
+
+        let mut set = Self {
+            max_ends: spans.iter().map(|s| s.end).collect(),
+            spans,
+        };
+        set.update_max_ends(0);
+        set
+    }
+}
+
+impl Extend<Span> for SpanSet {
+    fn extend<I: IntoIterator<Item = Span>>(&mut self, iter: I) {
+        *self = self.spans.drain(..).chain(iter).collect();
+    }
+}
+
+impl<'a> IntoIterator for &'a SpanSet {
+    type Item = &'a Span;
+    type IntoIter = core::slice::Iter<'a, Span>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
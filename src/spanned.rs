@@ -0,0 +1,94 @@
+use core::ops::Deref;
+
+use crate::Span;
+
+/// `Spanned { value, span }`
+///
+/// A `value` along with the [`Span`] of the text it originates from,
+/// e.g. a token or a node in a syntax tree.
+///
+/// # Example
+///
+/// ```
+/// use char_positions::{LineColByte, Span, Spanned};
+///
+/// // "let x"
+/// let kw = Spanned::new("let", Span::new(LineColByte(1, 1, 0), LineColByte(1, 4, 3)));
+/// let name = Spanned::new('x', Span::new(LineColByte(1, 5, 4), LineColByte(1, 6, 5)));
+///
+/// assert_eq!(kw.map(str::len).value, 3);
+///
+/// let decl = kw.join(name);
+/// assert_eq!(decl.value, ("let", 'x'));
+/// assert_eq!(decl.span.byte_range(), 0..5);
+///
+/// let (value, span) = decl.split();
+/// assert_eq!(value, ("let", 'x'));
+/// assert_eq!(span.byte_range(), 0..5);
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    #[inline]
+    pub const fn new(value: T, span: Span) -> Self {
+        Self { value, span }
+    }
+
+    /// Maps the value with `f`, keeping the span.
+    #[inline]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Spanned<U> {
+        Spanned::new(f(self.value), self.span)
+    }
+
+    #[inline]
+    pub const fn as_ref(&self) -> Spanned<&T> {
+        Spanned::new(&self.value, self.span)
+    }
+
+    #[inline]
+    pub fn as_mut(&mut self) -> Spanned<&mut T> {
+        Spanned::new(&mut self.value, self.span)
+    }
+
+    #[inline]
+    pub fn as_deref(&self) -> Spanned<&T::Target>
+    where
+        T: Deref,
+    {
+        Spanned::new(self.value.deref(), self.span)
+    }
+
+    /// Returns the value and the span.
+    #[inline]
+    pub fn split(self) -> (T, Span) {
+        (self.value, self.span)
+    }
+
+    /// Returns both values, with a span covering both spans,
+    /// and anything in between.
+    ///
+    /// See [`Span::union()`].
+    #[inline]
+    pub fn join<U>(self, other: Spanned<U>) -> Spanned<(T, U)> {
+        Spanned::new((self.value, other.value), self.span.union(&other.span))
+    }
+}
+
+impl<T> From<(T, Span)> for Spanned<T> {
+    #[inline]
+    fn from((value, span): (T, Span)) -> Self {
+        Self::new(value, span)
+    }
+}
+
+impl<T> From<Spanned<T>> for (T, Span) {
+    #[inline]
+    fn from(spanned: Spanned<T>) -> Self {
+        spanned.split()
+    }
+}
@@ -0,0 +1,139 @@
+use crate::TrailingNewline;
+
+pub trait PositionStatsExt {
+    /// Returns [`PositionStats`] of the text, computed in a single pass.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{PositionStats, PositionStatsExt};
+    ///
+    /// let text = "fn main() {\r\n    println!(\"🦀\");\n}\n";
+    ///
+    /// assert_eq!(
+    ///     text.position_stats(),
+    ///     PositionStats {
+    ///         chars: 34,
+    ///         bytes: 37,
+    ///         lines: 4,
+    ///         longest_line: 2,
+    ///         longest_line_chars: 18,
+    ///         lf: 2,
+    ///         crlf: 1,
+    ///         cr: 0,
+    ///         trailing_newline: true,
+    ///     },
+    /// );
+    /// ```
+    fn position_stats(&self) -> PositionStats;
+}
+
+impl PositionStatsExt for str {
+    fn position_stats(&self) -> PositionStats {
+        let mut stats = PositionStats {
+            chars: 0,
+            bytes: self.len(),
+            lines: 1,
+            longest_line: 1,
+            longest_line_chars: 0,
+            lf: 0,
+            crlf: 0,
+            cr: 0,
+            trailing_newline: self.ends_with('\n'),
+        };
+
+        // Number of `char`s in the current line, so far
+        let mut line_chars = 0;
+        let mut prev = 0;
+        for &b in self.as_bytes() {
+            // Skip UTF-8 continuation bytes
+            if (b & 0xC0) == 0x80 {
+                continue;
+            }
+            stats.chars += 1;
+
+            match b {
+                b'\n' => {
+                    if prev == b'\r' {
+                        stats.crlf += 1;
+                        stats.cr -= 1;
+                        line_chars -= 1;
+                    } else {
+                        stats.lf += 1;
+                    }
+                    stats.end_line(line_chars);
+                    line_chars = 0;
+                }
+                b'\r' => {
+                    stats.cr += 1;
+                    line_chars += 1;
+                }
+                _ => line_chars += 1,
+            }
+            prev = b;
+        }
+        stats.end_line(line_chars);
+        stats.lines -= 1;
+
+        stats
+    }
+}
+
+/// Statistics of the positions in a text.
+///
+/// See [`PositionStatsExt::position_stats()`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct PositionStats {
+    /// The number of [`char`]s.
+    pub chars: usize,
+    /// The length in bytes.
+    pub bytes: usize,
+    /// The number of lines.
+    ///
+    /// An empty text has 1 line, and a text ending with
+    /// `'\n'` has an empty last line.
+    pub lines: usize,
+    /// The 1-indexed line number of the first longest line.
+    pub longest_line: usize,
+    /// The number of [`char`]s in the longest line, excluding
+    /// its line terminator.
+    pub longest_line_chars: usize,
+    /// The number of `"\n"` line terminators, not preceded by `'\r'`.
+    pub lf: usize,
+    /// The number of `"\r\n"` line terminators.
+    pub crlf: usize,
+    /// The number of `'\r'`s not followed by `'\n'`.
+    ///
+    /// These do not break lines, and are counted as part of the line.
+    pub cr: usize,
+    /// Whether the text ends with `'\n'`.
+    pub trailing_newline: bool,
+}
+
+impl PositionStats {
+    /// Returns the total number of line terminators, i.e. the
+    /// number of `'\n'`s.
+    #[inline]
+    pub const fn newlines(&self) -> usize {
+        self.lf + self.crlf
+    }
+
+    /// Returns the number of lines, where `trailing_newline`
+    /// decides whether a trailing `'\n'` creates an empty final line.
+    ///
+    /// See [`TrailingNewlineExt::line_count()`](crate::TrailingNewlineExt::line_count).
+    #[inline]
+    pub const fn line_count(&self, trailing_newline: TrailingNewline) -> usize {
+        trailing_newline.adjust(self.lines, (self.bytes == 0) || self.trailing_newline)
+    }
+
+    /// Ends the line numbered `self.lines`, and starts the next line.
+    #[inline]
+    fn end_line(&mut self, line_chars: usize) {
+        if line_chars > self.longest_line_chars {
+            self.longest_line = self.lines;
+            self.longest_line_chars = line_chars;
+        }
+        self.lines += 1;
+    }
+}
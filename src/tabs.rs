@@ -0,0 +1,123 @@
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::{CharPositions, CharPositionsExt, LineColByteRange};
+
+pub trait ExpandTabsExt {
+    /// Returns an iterator over the [`char`]s of the text with tabs
+    /// expanded to spaces, along with the position of the [`char`]
+    /// they originate from.
+    ///
+    /// A tab expands to the number of spaces needed to reach the next
+    /// tab stop, i.e. the next multiple of `tab_width` columns in the
+    /// expanded line, where every other [`char`] counts as 1 column.
+    /// Every space produced by a tab has the position of the tab.
+    ///
+    /// A `tab_width` of 0 is treated as 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{ExpandTabsExt, LineCol};
+    ///
+    /// let text = "a\tb\n\t🦀";
+    ///
+    /// let expanded = text
+    ///     .expand_tabs::<LineCol>(4)
+    ///     .map(|(LineCol(line, col), c)| (line, col, c))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     expanded,
+    ///     [
+    ///         (1, 1, 'a'),
+    ///         (1, 2, ' '),
+    ///         (1, 2, ' '),
+    ///         (1, 2, ' '),
+    ///         (1, 3, 'b'),
+    ///         (1, 4, '\n'),
+    ///         (2, 1, ' '),
+    ///         (2, 1, ' '),
+    ///         (2, 1, ' '),
+    ///         (2, 1, ' '),
+    ///         (2, 2, '🦀'),
+    ///     ],
+    /// );
+    ///
+    /// let expanded = text.expand_tabs::<usize>(4).map(|(_, c)| c).collect::<String>();
+    /// assert_eq!(expanded, "a   b\n    🦀");
+    /// ```
+    fn expand_tabs<T>(&self, tab_width: usize) -> ExpandedTabs<'_, T>
+    where
+        LineColByteRange: Into<T>;
+}
+
+impl ExpandTabsExt for str {
+    #[inline]
+    fn expand_tabs<T>(&self, tab_width: usize) -> ExpandedTabs<'_, T>
+    where
+        LineColByteRange: Into<T>,
+    {
+        ExpandedTabs {
+            iter: self.char_positions::<LineColByteRange>(),
+            tab_width: tab_width.max(1),
+            col: 0,
+            tab: None,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// An iterator over [`char`]s with tabs expanded to spaces,
+/// and their original positions.
+///
+/// See [`ExpandTabsExt::expand_tabs()`].
+#[derive(Clone, Debug)]
+pub struct ExpandedTabs<'a, T> {
+    iter: CharPositions<'a, LineColByteRange>,
+    tab_width: usize,
+    /// The 0-indexed column in the expanded line.
+    col: usize,
+    /// The position of the tab being expanded,
+    /// and the number of spaces remaining.
+    tab: Option<(LineColByteRange, usize)>,
+    phantom: PhantomData<T>,
+}
+
+impl<T> Iterator for ExpandedTabs<'_, T>
+where
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((pos, spaces)) = &mut self.tab {
+            let pos = pos.clone();
+            *spaces -= 1;
+            if *spaces == 0 {
+                self.tab = None;
+            }
+            self.col += 1;
+            return Some((pos.into(), ' '));
+        }
+
+        let (pos, c) = self.iter.next()?;
+        match c {
+            '\t' => {
+                let spaces = self.tab_width - (self.col % self.tab_width);
+                self.tab = Some((pos, spaces));
+                self.next()
+            }
+            '\n' => {
+                self.col = 0;
+                Some((pos.into(), c))
+            }
+            _ => {
+                self.col += 1;
+                Some((pos.into(), c))
+            }
+        }
+    }
+}
+
+impl<T> FusedIterator for ExpandedTabs<'_, T> where Self: Iterator {}
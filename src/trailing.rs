@@ -0,0 +1,103 @@
+use crate::LineColByte;
+
+/// Whether a trailing `'\n'` creates an empty final line, when
+/// counting lines.
+///
+/// Tools disagree on the number of lines in `"a\nb\n"`. Editors
+/// and this crate count 3 lines, with the last being empty, as
+/// a [`char`] appended at the end is on line 3. Whereas, e.g.
+/// `wc -l` and [`str::lines()`] count 2, as `'\n'` terminates
+/// a line, rather than separating lines.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug)]
+pub enum TrailingNewline {
+    /// A trailing `'\n'` creates an empty final line, i.e. `"a\n"`
+    /// has 2 lines, and `""` has 1 line.
+    ///
+    /// This is the convention used throughout this crate.
+    #[default]
+    EmptyLine,
+    /// A trailing `'\n'` terminates the final line, i.e. `"a\n"`
+    /// has 1 line, and `""` has 0 lines.
+    Terminator,
+}
+
+pub trait TrailingNewlineExt {
+    /// Returns `true` if the text ends with `'\n'`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::TrailingNewlineExt;
+    ///
+    /// assert!("Hello\r\n".has_trailing_newline());
+    /// assert!(!"Hello".has_trailing_newline());
+    /// assert!(!"".has_trailing_newline());
+    /// ```
+    fn has_trailing_newline(&self) -> bool;
+
+    /// Returns the position a [`char`] appended at the end
+    /// of the text would have.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineColByte, TrailingNewlineExt};
+    ///
+    /// assert_eq!("".position_after_end(), LineColByte(1, 1, 0));
+    /// assert_eq!("Hello 🦀".position_after_end(), LineColByte(1, 8, 10));
+    /// assert_eq!("Hello\n".position_after_end(), LineColByte(2, 1, 6));
+    /// ```
+    fn position_after_end(&self) -> LineColByte;
+
+    /// Returns the number of lines, where `trailing_newline`
+    /// decides whether a trailing `'\n'` creates an empty final line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{TrailingNewline, TrailingNewlineExt};
+    ///
+    /// assert_eq!("a\nb\n".line_count(TrailingNewline::EmptyLine), 3);
+    /// assert_eq!("a\nb\n".line_count(TrailingNewline::Terminator), 2);
+    ///
+    /// assert_eq!("a\nb".line_count(TrailingNewline::EmptyLine), 2);
+    /// assert_eq!("a\nb".line_count(TrailingNewline::Terminator), 2);
+    ///
+    /// assert_eq!("".line_count(TrailingNewline::EmptyLine), 1);
+    /// assert_eq!("".line_count(TrailingNewline::Terminator), 0);
+    /// ```
+    fn line_count(&self, trailing_newline: TrailingNewline) -> usize;
+}
+
+impl TrailingNewlineExt for str {
+    #[inline]
+    fn has_trailing_newline(&self) -> bool {
+        self.ends_with('\n')
+    }
+
+    #[inline]
+    fn position_after_end(&self) -> LineColByte {
+        let mut pos = LineColByte(1, 1, 0);
+        pos.advance_str(self);
+        pos
+    }
+
+    #[inline]
+    fn line_count(&self, trailing_newline: TrailingNewline) -> usize {
+        let lines = crate::scan::count_newlines(self) + 1;
+        trailing_newline.adjust(lines, self.is_empty() || self.has_trailing_newline())
+    }
+}
+
+impl TrailingNewline {
+    /// Adjusts `lines` counted as [`TrailingNewline::EmptyLine`],
+    /// where `empty_last_line` is whether the last line is empty.
+    #[inline]
+    pub(crate) const fn adjust(self, lines: usize, empty_last_line: bool) -> usize {
+        match self {
+            Self::EmptyLine => lines,
+            Self::Terminator if empty_last_line => lines - 1,
+            Self::Terminator => lines,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Integration with [`tree_sitter`], enabled by the `tree-sitter` feature.
+//!
+//! A tree-sitter [`Point`] has a 0-indexed row and a 0-indexed column,
+//! where the column is counted in bytes, not [`char`]s. The functions
+//! in this module convert between byte positions and [`Point`]s.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{tree_sitter as ts, LineCol, SourceFile};
+//! use tree_sitter::Point;
+//!
+//! let file = SourceFile::new("main.rs", "fn main() {\n    🦀 = x;\n}\n");
+//!
+//! // The `x`
+//! assert_eq!(ts::point(&file, 23), Some(Point::new(1, 11)));
+//! assert_eq!(ts::byte_of(&file, Point::new(1, 11)), Some(23));
+//! // Within the 🦀
+//! assert_eq!(ts::byte_of(&file, Point::new(1, 5)), None);
+//!
+//! let range = ts::range(&file, 16..23).unwrap();
+//! let span = ts::span_of(&file, range).unwrap();
+//! assert_eq!(span.start_line_col(), LineCol(2, 5));
+//! assert_eq!(span.end_line_col(), LineCol(2, 9));
+//! ```
+
+use core::ops::Range;
+
+use ::tree_sitter::Point;
+
+use crate::{ByteRange, ColumnUnit, LineCol, SourceFile, Span};
+
+/// Returns the [`Point`] of the byte position `byte` in `file`.
+///
+/// Returns `None` if `byte` is out of bounds or not on a [`char`] boundary.
+pub fn point(file: &SourceFile<'_>, byte: usize) -> Option<Point> {
+    let LineCol(line, col) =
+        file.line_index()
+            .line_col_with_unit(file.text(), byte, ColumnUnit::Utf8)?;
+    Some(Point::new(line - 1, col - 1))
+}
+
+/// Returns the byte position of `point` in `file`.
+///
+/// Returns `None` if `point` is out of bounds or not on a [`char`] boundary.
+pub fn byte_of(file: &SourceFile<'_>, point: Point) -> Option<usize> {
+    let pos = LineCol(point.row + 1, point.column + 1);
+    file.line_index()
+        .byte_of_with_unit(file.text(), pos, ColumnUnit::Utf8)
+}
+
+/// Returns the tree-sitter [`Range`](::tree_sitter::Range) of the byte `range` in `file`.
+///
+/// Returns `None` if `range` is out of bounds or not on [`char`] boundaries.
+pub fn range(file: &SourceFile<'_>, range: Range<usize>) -> Option<::tree_sitter::Range> {
+    Some(::tree_sitter::Range {
+        start_byte: range.start,
+        end_byte: range.end,
+        start_point: point(file, range.start)?,
+        end_point: point(file, range.end)?,
+    })
+}
+
+/// Returns the [`Span`] of the tree-sitter `range` in `file`.
+///
+/// Returns `None` if `range` is out of bounds or not on [`char`] boundaries.
+#[inline]
+pub fn span_of(file: &SourceFile<'_>, range: ::tree_sitter::Range) -> Option<Span> {
+    file.span(range.start_byte..range.end_byte)
+}
+
+impl From<::tree_sitter::Range> for ByteRange {
+    #[inline]
+    fn from(range: ::tree_sitter::Range) -> Self {
+        Self(range.start_byte..range.end_byte)
+    }
+}
@@ -0,0 +1,303 @@
+//! Decoding of escape sequences in string literals, e.g. `\n`, `\x7F`,
+//! and `\u{1F980}`, while keeping track of the position of each escape
+//! sequence in the original source.
+//!
+//! Both Rust and JSON escape sequences are supported:
+//!
+//! | Escape | Produces |
+//! |:---|:---|
+//! | `\n`, `\r`, `\t`, `\0`, `\\`, `\'`, `\"` | _Same as Rust_ |
+//! | `\/`, `\b`, `\f` | _Same as JSON_ |
+//! | `\xNN` | ASCII `char`, at most `\x7F` |
+//! | `\u{N}` | Any `char`, from 1 to 6 hex digits |
+//! | `\uNNNN` | Any `char`, with UTF-16 surrogate pairs, e.g. `\uD83E\uDD80` |
+//! | `\` followed by a line break | Nothing, skipping the line break and any following whitespace |
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::unescape::{unescape, UnescapeErrorKind};
+//! use char_positions::{LineCol, LineColByte};
+//!
+//! // The literal `"a\t🦀\u{1F980}\q"` starting at line 3, column 9
+//! let literal = r"a\t🦀\u{1F980}\q";
+//! let mut iter = unescape(literal).starting_at(LineColByte(3, 10, 40));
+//!
+//! let (span, c) = iter.next().unwrap().unwrap();
+//! assert_eq!((span.start_line_col(), c), (LineCol(3, 10), 'a'));
+//!
+//! let (span, c) = iter.next().unwrap().unwrap();
+//! assert_eq!(c, '\t');
+//! assert_eq!(span.start_line_col(), LineCol(3, 11));
+//! assert_eq!(span.end_line_col(), LineCol(3, 13));
+//!
+//! let (span, c) = iter.next().unwrap().unwrap();
+//! assert_eq!((span.start_line_col(), c), (LineCol(3, 13), '🦀'));
+//!
+//! let (span, c) = iter.next().unwrap().unwrap();
+//! assert_eq!((span.start_line_col(), c), (LineCol(3, 14), '🦀'));
+//! assert_eq!(span.byte_range(), 47..56);
+//!
+//! let err = iter.next().unwrap().unwrap_err();
+//! assert_eq!(err.kind(), UnescapeErrorKind::UnknownEscape);
+//! assert_eq!(err.span().start_line_col(), LineCol(3, 23));
+//! assert_eq!(err.to_string(), "unknown escape sequence at line 3, column 23");
+//!
+//! assert!(iter.next().is_none());
+//! ```
+
+use core::fmt;
+use core::iter::FusedIterator;
+
+use crate::{LineColByte, Span};
+
+/// Returns an iterator over the decoded [`char`]s of `literal`, i.e. the
+/// contents of a string literal without its quotes, along with the
+/// [`Span`] of the escape sequence or [`char`] it was decoded from.
+///
+/// The spans are relative to the start of `literal`,
+/// unless [`Unescape::starting_at()`] is used.
+///
+/// See the [module](self) documentation.
+#[inline]
+pub fn unescape(literal: &str) -> Unescape<'_> {
+    Unescape {
+        text: literal,
+        offset: 0,
+        pos: LineColByte(1, 1, 0),
+    }
+}
+
+/// An iterator over decoded [`char`]s and the [`Span`]s they were decoded from.
+///
+/// An invalid escape sequence produces an [`UnescapeError`], after
+/// which iteration continues past the invalid escape sequence.
+///
+/// See [`unescape()`].
+#[derive(Clone, Debug)]
+pub struct Unescape<'a> {
+    text: &'a str,
+    /// The byte position of the remaining text in `text`.
+    offset: usize,
+    pos: LineColByte,
+}
+
+impl<'a> Unescape<'a> {
+    /// Sets the position of the start of the literal, e.g. the
+    /// position following the opening quote in the source.
+    #[inline]
+    pub fn starting_at(mut self, pos: LineColByte) -> Self {
+        let mut start = pos;
+        start.advance_str(&self.text[..self.offset]);
+        self.pos = start;
+        self
+    }
+
+    /// Returns the remaining substring.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+}
+
+impl Iterator for Unescape<'_> {
+    type Item = Result<(Span, char), UnescapeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = self.as_str();
+            let c = rest.chars().next()?;
+
+            let (len, result) = match c {
+                '\\' => parse_escape(rest),
+                c => (c.len_utf8(), Ok(Some(c))),
+            };
+
+            let start = self.pos;
+            self.pos.advance_str(&rest[..len]);
+            self.offset += len;
+            let span = Span::new(start, self.pos);
+
+            match result {
+                Ok(Some(c)) => return Some(Ok((span, c))),
+                Ok(None) => continue,
+                Err(kind) => return Some(Err(UnescapeError { span, kind })),
+            }
+        }
+    }
+}
+
+impl FusedIterator for Unescape<'_> {}
+
+/// An invalid escape sequence.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct UnescapeError {
+    span: Span,
+    kind: UnescapeErrorKind,
+}
+
+impl UnescapeError {
+    /// Returns the span of the invalid escape sequence.
+    #[inline]
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    #[inline]
+    pub fn kind(&self) -> UnescapeErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for UnescapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let LineColByte(line, col, _) = self.span.start;
+        write!(f, "{} at line {line}, column {col}", self.kind)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnescapeError {}
+
+/// The reason an escape sequence is invalid.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum UnescapeErrorKind {
+    /// A `\` at the end of the literal.
+    Incomplete,
+    /// A `\` followed by an unsupported character, e.g. `\q`.
+    UnknownEscape,
+    /// A `\x` not followed by 2 hex digits.
+    InvalidHexEscape,
+    /// A `\xNN` above `\x7F`.
+    HexEscapeOutOfRange,
+    /// A `\u` not followed by `{`, 1 to 6 hex digits, and `}`,
+    /// or by 4 hex digits.
+    InvalidUnicodeEscape,
+    /// A `\u{N}` or `\uNNNN` which is not a valid [`char`].
+    InvalidCodePoint,
+    /// A `\uNNNN` high surrogate not followed by a low surrogate,
+    /// or a low surrogate not preceded by a high surrogate.
+    LoneSurrogate,
+}
+
+impl fmt::Display for UnescapeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Incomplete => "incomplete escape sequence",
+            Self::UnknownEscape => "unknown escape sequence",
+            Self::InvalidHexEscape => "invalid hex escape sequence",
+            Self::HexEscapeOutOfRange => "hex escape sequence out of range",
+            Self::InvalidUnicodeEscape => "invalid unicode escape sequence",
+            Self::InvalidCodePoint => "invalid unicode code point",
+            Self::LoneSurrogate => "lone surrogate in unicode escape sequence",
+        })
+    }
+}
+
+type EscapeResult = Result<Option<char>, UnescapeErrorKind>;
+
+/// Parses the escape sequence at the start of `s`, returning its
+/// length in bytes, and the decoded [`char`], if any.
+fn parse_escape(s: &str) -> (usize, EscapeResult) {
+    debug_assert!(s.starts_with('\\'));
+
+    let c = match s[1..].chars().next() {
+        Some(c) => c,
+        None => return (1, Err(UnescapeErrorKind::Incomplete)),
+    };
+
+    let decoded = match c {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        '0' => '\0',
+        '\\' | '\'' | '"' | '/' => c,
+        'b' => '\u{8}',
+        'f' => '\u{C}',
+        'x' => return parse_hex_escape(s),
+        'u' if s[2..].starts_with('{') => return parse_braced_unicode_escape(s),
+        'u' => return parse_utf16_escape(s),
+        '\n' | '\r' => {
+            let skipped = s[1..]
+                .find(|c: char| !c.is_ascii_whitespace())
+                .unwrap_or(s.len() - 1);
+            return (1 + skipped, Ok(None));
+        }
+        c => return (1 + c.len_utf8(), Err(UnescapeErrorKind::UnknownEscape)),
+    };
+    (2, Ok(Some(decoded)))
+}
+
+/// Parses `\xNN`.
+fn parse_hex_escape(s: &str) -> (usize, EscapeResult) {
+    let digits = hex_digits(&s[2..], 2);
+    if digits.len() != 2 {
+        return (2 + digits.len(), Err(UnescapeErrorKind::InvalidHexEscape));
+    }
+    match u8::from_str_radix(digits, 16) {
+        Ok(b @ 0..=0x7F) => (4, Ok(Some(char::from(b)))),
+        _ => (4, Err(UnescapeErrorKind::HexEscapeOutOfRange)),
+    }
+}
+
+/// Parses `\u{N}`.
+fn parse_braced_unicode_escape(s: &str) -> (usize, EscapeResult) {
+    let digits = hex_digits(&s[3..], 6);
+    let len = 3 + digits.len();
+    if digits.is_empty() || !s[len..].starts_with('}') {
+        return (len, Err(UnescapeErrorKind::InvalidUnicodeEscape));
+    }
+    let c = u32::from_str_radix(digits, 16)
+        .ok()
+        .and_then(char::from_u32);
+    (
+        len + 1,
+        c.map(Some).ok_or(UnescapeErrorKind::InvalidCodePoint),
+    )
+}
+
+/// Parses `\uNNNN`, or a `\uNNNN\uNNNN` surrogate pair.
+fn parse_utf16_escape(s: &str) -> (usize, EscapeResult) {
+    let Some(high) = parse_utf16_unit(s) else {
+        let len = 2 + hex_digits(&s[2..], 4).len();
+        return (len, Err(UnescapeErrorKind::InvalidUnicodeEscape));
+    };
+    match high {
+        0xD800..=0xDBFF => {}
+        0xDC00..=0xDFFF => return (6, Err(UnescapeErrorKind::LoneSurrogate)),
+        _ => {
+            let c = char::from_u32(u32::from(high));
+            return (6, c.map(Some).ok_or(UnescapeErrorKind::InvalidCodePoint));
+        }
+    }
+
+    match s[6..]
+        .strip_prefix("\\u")
+        .and_then(|_| parse_utf16_unit(&s[6..]))
+    {
+        Some(low @ 0xDC00..=0xDFFF) => {
+            let c = char::decode_utf16([high, low]).next().and_then(Result::ok);
+            (12, c.map(Some).ok_or(UnescapeErrorKind::InvalidCodePoint))
+        }
+        _ => (6, Err(UnescapeErrorKind::LoneSurrogate)),
+    }
+}
+
+/// Parses the 4 hex digits of `\uNNNN` at the start of `s`.
+fn parse_utf16_unit(s: &str) -> Option<u16> {
+    let digits = hex_digits(s.get(2..)?, 4);
+    if digits.len() != 4 {
+        return None;
+    }
+    u16::from_str_radix(digits, 16).ok()
+}
+
+/// Returns up to `max` leading ASCII hex digits of `s`.
+fn hex_digits(s: &str, max: usize) -> &str {
+    let n = s
+        .bytes()
+        .take(max)
+        .take_while(u8::is_ascii_hexdigit)
+        .count();
+    &s[..n]
+}
@@ -0,0 +1,146 @@
+//! Integration with [`unicode_normalization`], enabled by the
+//! `unicode-normalization` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::unicode_normalization::NfcPositionsExt;
+//! use char_positions::LineColByteRange;
+//!
+//! // "Café" with a decomposed "é", i.e. 'e' followed by U+0301
+//! let text = "Cafe\u{301}\n🦀";
+//!
+//! let mut iter = text
+//!     .nfc_char_positions::<LineColByteRange>()
+//!     .map(|(LineColByteRange(line, col, range), c)| (line, col, range, c))
+//!     .skip(3);
+//!
+//! assert_eq!(iter.next(), Some((1, 4, 3..6, 'é')));
+//! assert_eq!(iter.next(), Some((1, 6, 6..7, '\n')));
+//! assert_eq!(iter.next(), Some((2, 1, 7..11, '🦀')));
+//! assert_eq!(iter.next(), None);
+//! ```
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::str::Chars;
+
+use ::unicode_normalization::char::{canonical_combining_class, compose};
+use ::unicode_normalization::{Recompositions, UnicodeNormalization};
+
+use crate::{LineCol, LineColByteRange};
+
+pub trait NfcPositionsExt {
+    /// Returns an iterator over the [`char`]s of the NFC normalized text,
+    /// along with the position of the original sequence of [`char`]s
+    /// they were normalized from.
+    ///
+    /// The text is normalized in segments, where each segment is a
+    /// [`char`] followed by any [`char`]s which may combine with it,
+    /// e.g. combining marks. Every [`char`] produced by a segment has
+    /// the line and column of the start of the segment, and the byte
+    /// range of the whole segment, in the original text.
+    fn nfc_char_positions<T>(&self) -> NfcCharPositions<'_, T>
+    where
+        LineColByteRange: Into<T>;
+}
+
+impl NfcPositionsExt for str {
+    #[inline]
+    fn nfc_char_positions<T>(&self) -> NfcCharPositions<'_, T>
+    where
+        LineColByteRange: Into<T>,
+    {
+        NfcCharPositions {
+            text: self,
+            byte: 0,
+            pos: LineCol::START,
+            segment: None,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// An iterator over NFC normalized [`char`]s and their original positions.
+///
+/// See [`NfcPositionsExt::nfc_char_positions()`].
+pub struct NfcCharPositions<'a, T> {
+    text: &'a str,
+    /// The start byte position of the next segment.
+    byte: usize,
+    /// The line and column of `byte`.
+    pos: LineCol,
+    /// The current segment.
+    segment: Option<(LineColByteRange, Recompositions<Chars<'a>>)>,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T> NfcCharPositions<'a, T> {
+    /// Returns the remaining substring, excluding the current segment.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.byte..]
+    }
+}
+
+impl<T> Iterator for NfcCharPositions<'_, T>
+where
+    LineColByteRange: Into<T>,
+{
+    type Item = (T, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((pos, chars)) = &mut self.segment {
+                if let Some(c) = chars.next() {
+                    return Some((pos.clone().into(), c));
+                }
+                self.segment = None;
+            }
+
+            let rest = self.as_str();
+            if rest.is_empty() {
+                return None;
+            }
+
+            let segment = &rest[..segment_len(rest)];
+            let start = self.byte;
+            self.byte += segment.len();
+
+            let pos = LineColByteRange(self.pos.0, self.pos.1, start..self.byte);
+            segment.chars().for_each(|c| self.pos.advance(c));
+
+            self.segment = Some((pos, segment.nfc()));
+        }
+    }
+}
+
+impl<T> FusedIterator for NfcCharPositions<'_, T> where Self: Iterator {}
+
+impl<T> fmt::Debug for NfcCharPositions<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NfcCharPositions")
+            .field("text", &self.text)
+            .field("byte", &self.byte)
+            .field("pos", &self.pos)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the length in bytes of the segment at the start of `s`,
+/// i.e. the first [`char`] and any following [`char`]s which may
+/// combine with it.
+fn segment_len(s: &str) -> usize {
+    let mut chars = s.char_indices();
+    let Some((_, mut prev)) = chars.next() else {
+        return 0;
+    };
+    for (i, c) in chars {
+        if canonical_combining_class(c) == 0 && compose(prev, c).is_none() {
+            return i;
+        }
+        prev = c;
+    }
+    s.len()
+}
@@ -0,0 +1,198 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::counter::LineColCounter;
+use crate::{CharPosition, LineColByte, LineColByteRange, Span};
+
+pub trait CheckedUtf8Ext {
+    /// Returns an iterator over the [`char`]s of UTF-8 encoded bytes and
+    /// their positions, which produces an [`InvalidUtf8`] error for every
+    /// invalid or incomplete UTF-8 sequence, and then continues after it.
+    ///
+    /// Every invalid sequence, i.e. maximal subpart of an ill-formed
+    /// sequence, counts as a single column, the same as the `U+FFFD` it would
+    /// be replaced by in [`String::from_utf8_lossy()`]. All of this crate's
+    /// byte oriented iterators and readers count invalid UTF-8 the same way.
+    ///
+    /// If [`T::LINE_COL`](CharPosition::LINE_COL) is `false`, then the lines
+    /// and columns of the errors are unspecified.
+    ///
+    /// [`String::from_utf8_lossy()`]: https://doc.rust-lang.org/std/string/struct.String.html#method.from_utf8_lossy
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{CheckedUtf8Ext, LineColByte, Span};
+    ///
+    /// let bytes = b"ok\nx\xF0\x9F\xA6y\xF0\x9F\xA6\x80";
+    ///
+    /// let mut iter = bytes.char_positions_checked::<LineColByte>();
+    /// assert_eq!(iter.next(), Some(Ok((LineColByte(1, 1, 0), 'o'))));
+    /// assert_eq!(iter.next(), Some(Ok((LineColByte(1, 2, 1), 'k'))));
+    /// assert_eq!(iter.next(), Some(Ok((LineColByte(1, 3, 2), '\n'))));
+    /// assert_eq!(iter.next(), Some(Ok((LineColByte(2, 1, 3), 'x'))));
+    ///
+    /// let err = iter.next().unwrap().unwrap_err();
+    /// assert_eq!(err.span(), Span::new(LineColByte(2, 2, 4), LineColByte(2, 3, 7)));
+    /// assert_eq!(err.to_string(), "invalid UTF-8 at line 2, col 2 (bytes 4..7)");
+    ///
+    /// assert_eq!(iter.next(), Some(Ok((LineColByte(2, 3, 7), 'y'))));
+    /// assert_eq!(iter.next(), Some(Ok((LineColByte(2, 4, 8), '🦀'))));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    fn char_positions_checked<T>(&self) -> CheckedUtf8CharPositions<'_, T>
+    where
+        T: CharPosition,
+        LineColByteRange: Into<T>;
+}
+
+impl CheckedUtf8Ext for [u8] {
+    #[inline]
+    fn char_positions_checked<T>(&self) -> CheckedUtf8CharPositions<'_, T>
+    where
+        T: CharPosition,
+        LineColByteRange: Into<T>,
+    {
+        CheckedUtf8CharPositions {
+            bytes: self,
+            index: 0,
+            pos: LineColCounter::START,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// An iterator over the [`char`]s of UTF-8 encoded bytes and their
+/// positions, which produces an error for invalid UTF-8 sequences.
+///
+/// See [`CheckedUtf8Ext::char_positions_checked()`].
+#[derive(Clone, Debug)]
+pub struct CheckedUtf8CharPositions<'a, T> {
+    bytes: &'a [u8],
+    /// The index of the next byte.
+    index: usize,
+    pos: LineColCounter,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T> CheckedUtf8CharPositions<'a, T> {
+    /// Returns the remaining bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        &self.bytes[self.index..]
+    }
+}
+
+impl<T> Iterator for CheckedUtf8CharPositions<'_, T>
+where
+    T: CharPosition,
+    LineColByteRange: Into<T>,
+{
+    type Item = Result<(T, char), InvalidUtf8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.as_bytes();
+        let start = self.index;
+        let (len, c) = match decode(rest) {
+            Decoded::Char(c) => (c.len_utf8(), Some(c)),
+            Decoded::Invalid(n) => (n, None),
+            Decoded::Incomplete if rest.is_empty() => return None,
+            Decoded::Incomplete => (rest.len(), None),
+        };
+        self.index += len;
+
+        let pos = self.pos.at(start..self.index);
+        if T::LINE_COL {
+            self.pos.advance(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+        }
+
+        Some(match c {
+            Some(c) => Ok((T::from_position(&pos), c)),
+            None => {
+                let LineColByteRange(line, col, _) = pos;
+                let end = self.pos.line_col();
+                Err(InvalidUtf8 {
+                    span: Span::new(
+                        LineColByte(line, col, start),
+                        LineColByte(end.0, end.1, self.index),
+                    ),
+                })
+            }
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bytes.len() - self.index;
+        (len.div_ceil(4), Some(len))
+    }
+}
+
+impl<T> FusedIterator for CheckedUtf8CharPositions<'_, T> where Self: Iterator {}
+
+/// An invalid or incomplete UTF-8 sequence.
+///
+/// See [`CheckedUtf8Ext::char_positions_checked()`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct InvalidUtf8 {
+    span: Span,
+}
+
+impl InvalidUtf8 {
+    /// Returns the span of the invalid bytes, which counts as a single column.
+    #[inline]
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl fmt::Display for InvalidUtf8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let LineColByte(line, col, start) = self.span.start;
+        let end = self.span.end.2;
+        write!(
+            f,
+            "invalid UTF-8 at line {line}, col {col} (bytes {start}..{end})"
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidUtf8 {}
+
+/// The result of decoding the first [`char`] of a byte slice.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(crate) enum Decoded {
+    /// A valid `char`, which is [`char::len_utf8()`] bytes.
+    Char(char),
+    /// The bytes are empty, or a valid prefix of a `char`.
+    Incomplete,
+    /// The first `n` bytes are invalid UTF-8, i.e. a maximal subpart,
+    /// which counts as a single column.
+    Invalid(usize),
+}
+
+/// Decodes the first UTF-8 encoded [`char`] of `bytes`.
+pub(crate) fn decode(bytes: &[u8]) -> Decoded {
+    let Some(&first) = bytes.first() else {
+        return Decoded::Incomplete;
+    };
+    let width = match first {
+        0x00..=0x7F => 1,
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => return Decoded::Invalid(1),
+    };
+    match core::str::from_utf8(&bytes[..width.min(bytes.len())]) {
+        Ok(s) => match s.chars().next() {
+            Some(c) if s.len() == width => Decoded::Char(c),
+            _ => Decoded::Incomplete,
+        },
+        Err(err) => match err.error_len() {
+            Some(n) => Decoded::Invalid(n),
+            None => Decoded::Incomplete,
+        },
+    }
+}
@@ -0,0 +1,143 @@
+//! JavaScript bindings with [`wasm_bindgen`], enabled by the `wasm` feature.
+//!
+//! Lines and columns are 1-indexed, and columns are counted in UTF-16 code
+//! units, i.e. the same as indexing into a JavaScript string. Byte offsets
+//! are UTF-8 byte positions in the text.
+//!
+//! ```js
+//! import { positionAt, offsetAt, charPositions } from "char-positions";
+//!
+//! const pos = positionAt("a\n🦀b", 6); // { line: 2, column: 3 }
+//! const offset = offsetAt("a\n🦀b", 2, 3); // 6
+//! const positions = charPositions("a\n🦀b"); // Uint32Array
+//!
+//! // Indexes the lines once, for repeated lookups in the same text
+//! const text = new IndexedText("a\n🦀b");
+//! text.positionAt(6); // { line: 2, column: 3 }
+//! text.offsetAt(2, 3); // 6
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::wasm::{char_positions, offset_at, position_at, IndexedText, Position};
+//!
+//! let text = "a\n🦀b";
+//!
+//! assert_eq!(position_at(text, 6), Some(Position { line: 2, column: 3 }));
+//! assert_eq!(offset_at(text, 2, 3), Some(6));
+//!
+//! assert_eq!(
+//!     char_positions(text),
+//!     [1, 1, 0, 1, 1, 2, 1, 2, 2, 1, 2, 6, 2, 3, 6, 7],
+//! );
+//!
+//! let indexed = IndexedText::new(text.to_string());
+//! assert_eq!(indexed.position_at(6), Some(Position { line: 2, column: 3 }));
+//! assert_eq!(indexed.offset_at(2, 3), Some(6));
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use ::wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{ColumnUnit, LineCol, LineIndex};
+
+/// A 1-indexed line and column, with the column counted in UTF-16 code units.
+#[wasm_bindgen]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Returns the [`Position`] of the UTF-8 `byte_offset` in `text`.
+///
+/// Returns `None` (`undefined`) if `byte_offset` is out of bounds,
+/// or not on a [`char`] boundary.
+///
+/// Every call indexes the lines of `text`, i.e. is O(n). Use
+/// [`IndexedText`] for repeated lookups in the same text.
+#[wasm_bindgen(js_name = positionAt)]
+pub fn position_at(text: &str, byte_offset: usize) -> Option<Position> {
+    let LineCol(line, column) =
+        LineIndex::new(text).line_col_with_unit(text, byte_offset, ColumnUnit::Utf16)?;
+    Some(Position { line, column })
+}
+
+/// Returns the UTF-8 byte offset of the 1-indexed `line` and `col` in `text`,
+/// with `col` counted in UTF-16 code units.
+///
+/// Returns `None` (`undefined`) if the line or column is out of bounds,
+/// or if the column is within a [`char`].
+///
+/// Every call indexes the lines of `text`, i.e. is O(n). Use
+/// [`IndexedText`] for repeated lookups in the same text.
+#[wasm_bindgen(js_name = offsetAt)]
+pub fn offset_at(text: &str, line: usize, col: usize) -> Option<usize> {
+    LineIndex::new(text).byte_of_with_unit(text, LineCol(line, col), ColumnUnit::Utf16)
+}
+
+/// A text and its [`LineIndex`], which is built once when constructed,
+/// such that lookups only scan the line they are on, instead of the
+/// whole text.
+///
+/// See [`position_at()`] and [`offset_at()`].
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct IndexedText {
+    text: String,
+    index: LineIndex,
+}
+
+#[wasm_bindgen]
+impl IndexedText {
+    #[wasm_bindgen(constructor)]
+    pub fn new(text: String) -> Self {
+        let index = LineIndex::new(&text);
+        Self { text, index }
+    }
+
+    /// Returns the [`Position`] of the UTF-8 `byte_offset`.
+    ///
+    /// See [`position_at()`].
+    #[wasm_bindgen(js_name = positionAt)]
+    pub fn position_at(&self, byte_offset: usize) -> Option<Position> {
+        let LineCol(line, column) =
+            self.index
+                .line_col_with_unit(&self.text, byte_offset, ColumnUnit::Utf16)?;
+        Some(Position { line, column })
+    }
+
+    /// Returns the UTF-8 byte offset of the 1-indexed `line` and `col`,
+    /// with `col` counted in UTF-16 code units.
+    ///
+    /// See [`offset_at()`].
+    #[wasm_bindgen(js_name = offsetAt)]
+    pub fn offset_at(&self, line: usize, col: usize) -> Option<usize> {
+        self.index
+            .byte_of_with_unit(&self.text, LineCol(line, col), ColumnUnit::Utf16)
+    }
+}
+
+/// Returns the positions of every [`char`] in `text`, as a flat array
+/// (`Uint32Array`) of `[line, column, byteStart, byteEnd]` per [`char`].
+///
+/// Positions are truncated to `u32`, i.e. `text` must be smaller than 4 GiB.
+#[wasm_bindgen(js_name = charPositions)]
+pub fn char_positions(text: &str) -> Vec<u32> {
+    let mut positions = Vec::with_capacity(text.len() * 4);
+    let (mut line, mut col) = (1, 1);
+    for (start, c) in text.char_indices() {
+        let end = start + c.len_utf8();
+        positions.extend([line, col, start as u32, end as u32]);
+        if c == '\n' {
+            line += 1;
+            col = 1;
+        } else {
+            col += c.len_utf16() as u32;
+        }
+    }
+    positions
+}
@@ -0,0 +1,131 @@
+use core::iter::FusedIterator;
+
+use crate::{LineColByte, LineTerminatorsExt, Span, SplitLineTerminators};
+
+pub trait WhitespacePositionsExt {
+    /// Returns an iterator over the [`Span`]s of trailing whitespace,
+    /// i.e. whitespace at the end of a line, before its line terminator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineCol, WhitespacePositionsExt};
+    ///
+    /// let text = "let 🦀 = 1;  \r\n\tlet x = 2;\n\t \n";
+    ///
+    /// let spans = text
+    ///     .trailing_whitespace_spans()
+    ///     .map(|span| (span.start_line_col(), span.end_line_col()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     spans,
+    ///     [
+    ///         (LineCol(1, 11), LineCol(1, 13)),
+    ///         (LineCol(3, 1), LineCol(3, 3)),
+    ///     ],
+    /// );
+    /// ```
+    fn trailing_whitespace_spans(&self) -> TrailingWhitespace<'_>;
+
+    /// Returns an iterator over the [`Span`]s of indentation which
+    /// mixes tabs and spaces within the same line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use char_positions::{LineCol, WhitespacePositionsExt};
+    ///
+    /// let text = "fn main() {\n\t  let x = 🦀;\n    \tdbg!(x);\n\t}\n";
+    ///
+    /// let spans = text
+    ///     .mixed_indent_spans()
+    ///     .map(|span| (span.start_line_col(), span.end_line_col()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     spans,
+    ///     [
+    ///         (LineCol(2, 1), LineCol(2, 4)),
+    ///         (LineCol(3, 1), LineCol(3, 6)),
+    ///     ],
+    /// );
+    /// ```
+    fn mixed_indent_spans(&self) -> MixedIndent<'_>;
+}
+
+impl WhitespacePositionsExt for str {
+    #[inline]
+    fn trailing_whitespace_spans(&self) -> TrailingWhitespace<'_> {
+        TrailingWhitespace {
+            lines: self.split_line_terminators(),
+        }
+    }
+
+    #[inline]
+    fn mixed_indent_spans(&self) -> MixedIndent<'_> {
+        MixedIndent {
+            lines: self.split_line_terminators(),
+        }
+    }
+}
+
+/// An iterator over the [`Span`]s of trailing whitespace.
+///
+/// See [`WhitespacePositionsExt::trailing_whitespace_spans()`].
+#[derive(Clone, Debug)]
+pub struct TrailingWhitespace<'a> {
+    lines: SplitLineTerminators<'a>,
+}
+
+impl Iterator for TrailingWhitespace<'_> {
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (content, _, line, range) = self.lines.next()?;
+            let trimmed = content.trim_end();
+            if trimmed.len() == content.len() {
+                continue;
+            }
+            let line_start = LineColByte(line.0, 1, range.0.start);
+            return Some(span_of(line_start, trimmed, &content[trimmed.len()..]));
+        }
+    }
+}
+
+impl FusedIterator for TrailingWhitespace<'_> {}
+
+/// An iterator over the [`Span`]s of indentation mixing tabs and spaces.
+///
+/// See [`WhitespacePositionsExt::mixed_indent_spans()`].
+#[derive(Clone, Debug)]
+pub struct MixedIndent<'a> {
+    lines: SplitLineTerminators<'a>,
+}
+
+impl Iterator for MixedIndent<'_> {
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (content, _, line, range) = self.lines.next()?;
+            let indent_len = content.len() - content.trim_start_matches([' ', '\t']).len();
+            let indent = &content[..indent_len];
+            if !(indent.contains(' ') && indent.contains('\t')) {
+                continue;
+            }
+            let line_start = LineColByte(line.0, 1, range.0.start);
+            return Some(span_of(line_start, "", indent));
+        }
+    }
+}
+
+impl FusedIterator for MixedIndent<'_> {}
+
+/// Returns the span of `s`, which follows `prefix` at the start of a line.
+fn span_of(line_start: LineColByte, prefix: &str, s: &str) -> Span {
+    let mut start = line_start;
+    start.advance_str(prefix);
+    let mut end = start;
+    end.advance_str(s);
+    Span::new(start, end)
+}
@@ -0,0 +1,252 @@
+//! Integration with [`winnow`], enabled by the `winnow` feature.
+//!
+//! [`PositionedStream`] wraps a `&str` and implements [`winnow`]'s
+//! [`Stream`], [`Location`], and [`Offset`] traits, while tracking the
+//! line, column, and byte position of the remaining input, with columns
+//! counted in [`char`]s like the rest of this crate.
+//!
+//! # Example
+//!
+//! ```
+//! use char_positions::{winnow::PositionedStream, LineCol, LineColByte};
+//! use winnow::ascii::{alpha1, multispace0};
+//! use winnow::prelude::*;
+//! use winnow::token::take_until;
+//!
+//! fn word<'a>(input: &mut PositionedStream<'a>) -> ModalResult<(LineColByte, &'a str)> {
+//!     multispace0.parse_next(input)?;
+//!     let pos = input.position();
+//!     let word = alpha1.parse_next(input)?;
+//!     Ok((pos, word))
+//! }
+//!
+//! let mut input = PositionedStream::new("🦀🦀\n  Hello World");
+//! take_until(0.., "Hello").parse_next(&mut input).unwrap();
+//!
+//! assert_eq!(word(&mut input).unwrap(), (LineColByte(2, 3, 11), "Hello"));
+//! assert_eq!(word(&mut input).unwrap(), (LineColByte(2, 9, 17), "World"));
+//! assert_eq!(input.line_col(), LineCol(2, 14));
+//! ```
+
+use core::fmt;
+use core::ops::Range;
+use core::str::CharIndices;
+
+use ::winnow::error::Needed;
+use ::winnow::stream::{
+    Compare, CompareResult, FindSlice, Location, Offset, SliceLen, Stream, StreamIsPartial,
+};
+
+use crate::{LineCol, LineColByte, Span};
+
+/// A `&str` input for [`winnow`] parsers, which tracks the line,
+/// column, and byte position of the remaining input.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct PositionedStream<'a> {
+    input: &'a str,
+    pos: LineColByte,
+}
+
+impl<'a> PositionedStream<'a> {
+    /// Returns the stream for the whole `text`, starting at line 1, column 1.
+    #[inline]
+    pub const fn new(text: &'a str) -> Self {
+        Self::with_position(text, LineColByte(1, 1, 0))
+    }
+
+    /// Returns the stream for `input`, starting at `pos`.
+    #[inline]
+    pub const fn with_position(input: &'a str, pos: LineColByte) -> Self {
+        Self { input, pos }
+    }
+
+    /// Returns the remaining input.
+    #[inline]
+    pub const fn as_str(&self) -> &'a str {
+        self.input
+    }
+
+    /// Returns the position of the start of the remaining input.
+    #[inline]
+    pub const fn position(&self) -> LineColByte {
+        self.pos
+    }
+
+    /// Returns the line and column of the start of the remaining input.
+    #[inline]
+    pub const fn line_col(&self) -> LineCol {
+        LineCol(self.pos.0, self.pos.1)
+    }
+
+    /// Returns the [`Span`] from `checkpoint` to the start of the
+    /// remaining input.
+    #[inline]
+    pub fn span_from(&self, checkpoint: &Checkpoint<'a>) -> Span {
+        Span::new(checkpoint.0.pos, self.pos)
+    }
+}
+
+/// A [`Stream::Checkpoint`] of a [`PositionedStream`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Checkpoint<'a>(PositionedStream<'a>);
+
+impl Checkpoint<'_> {
+    /// Returns the position of the checkpoint.
+    #[inline]
+    pub const fn position(&self) -> LineColByte {
+        self.0.pos
+    }
+}
+
+impl<'a> Stream for PositionedStream<'a> {
+    type Token = char;
+    type Slice = &'a str;
+    type IterOffsets = CharIndices<'a>;
+    type Checkpoint = Checkpoint<'a>;
+
+    #[inline]
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        self.input.char_indices()
+    }
+
+    #[inline]
+    fn eof_offset(&self) -> usize {
+        self.input.len()
+    }
+
+    #[inline]
+    fn next_token(&mut self) -> Option<Self::Token> {
+        let c = self.input.chars().next()?;
+        self.next_slice(c.len_utf8());
+        Some(c)
+    }
+
+    #[inline]
+    fn peek_token(&self) -> Option<Self::Token> {
+        self.input.chars().next()
+    }
+
+    #[inline]
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.input.offset_for(predicate)
+    }
+
+    #[inline]
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        self.input.offset_at(tokens)
+    }
+
+    #[inline]
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        let (slice, rest) = self.input.split_at(offset);
+        self.input = rest;
+        self.pos.advance_str(slice);
+        slice
+    }
+
+    #[inline]
+    fn peek_slice(&self, offset: usize) -> Self::Slice {
+        &self.input[..offset]
+    }
+
+    #[inline]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        Checkpoint(*self)
+    }
+
+    #[inline]
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        *self = checkpoint.0;
+    }
+
+    #[inline]
+    fn raw(&self) -> &dyn fmt::Debug {
+        &self.input
+    }
+}
+
+impl StreamIsPartial for PositionedStream<'_> {
+    type PartialState = ();
+
+    #[inline]
+    fn complete(&mut self) -> Self::PartialState {}
+
+    #[inline]
+    fn restore_partial(&mut self, _state: Self::PartialState) {}
+
+    #[inline]
+    fn is_partial_supported() -> bool {
+        false
+    }
+}
+
+impl Location for PositionedStream<'_> {
+    #[inline]
+    fn previous_token_end(&self) -> usize {
+        self.pos.2
+    }
+
+    #[inline]
+    fn current_token_start(&self) -> usize {
+        self.pos.2
+    }
+}
+
+impl Offset for PositionedStream<'_> {
+    #[inline]
+    fn offset_from(&self, start: &Self) -> usize {
+        self.pos.2 - start.pos.2
+    }
+}
+
+impl<'a> Offset<Checkpoint<'a>> for PositionedStream<'a> {
+    #[inline]
+    fn offset_from(&self, start: &Checkpoint<'a>) -> usize {
+        self.offset_from(&start.0)
+    }
+}
+
+impl Offset for Checkpoint<'_> {
+    #[inline]
+    fn offset_from(&self, start: &Self) -> usize {
+        self.0.offset_from(&start.0)
+    }
+}
+
+impl SliceLen for PositionedStream<'_> {
+    #[inline]
+    fn slice_len(&self) -> usize {
+        self.input.len()
+    }
+}
+
+impl<'b> Compare<&'b str> for PositionedStream<'_> {
+    #[inline]
+    fn compare(&self, t: &'b str) -> CompareResult {
+        self.input.compare(t)
+    }
+}
+
+impl Compare<char> for PositionedStream<'_> {
+    #[inline]
+    fn compare(&self, t: char) -> CompareResult {
+        self.input.compare(t)
+    }
+}
+
+impl<'b> FindSlice<&'b str> for PositionedStream<'_> {
+    #[inline]
+    fn find_slice(&self, substr: &'b str) -> Option<Range<usize>> {
+        self.input.find_slice(substr)
+    }
+}
+
+impl fmt::Display for PositionedStream<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.input.fmt(f)
+    }
+}
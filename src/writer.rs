@@ -0,0 +1,163 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::{LineColByte, Span};
+
+/// A writer which tracks the line, column, and byte position of
+/// everything written through it, e.g. for code generators to know
+/// where each node ended up in the generated output.
+///
+/// Implements [`fmt::Write`], and [`io::Write`](std::io::Write) with
+/// the `std` feature, forwarding everything to the inner writer.
+///
+/// # Example
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// use char_positions::{LineColByte, PositionTrackingWriter};
+///
+/// let mut w = PositionTrackingWriter::new(String::new());
+///
+/// writeln!(w, "fn main() {{").unwrap();
+/// w.write_str("    ").unwrap();
+///
+/// let start = w.position();
+/// write!(w, "let {} = {};", "🦀", 1).unwrap();
+/// let span = w.span_from(start);
+///
+/// w.write_str("\n}\n").unwrap();
+///
+/// assert_eq!(span.start, LineColByte(2, 5, 16));
+/// assert_eq!(span.end, LineColByte(2, 15, 29));
+/// assert_eq!(&w.get_ref()[span.byte_range()], "let 🦀 = 1;");
+/// assert_eq!(w.position(), LineColByte(4, 1, 32));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PositionTrackingWriter<W> {
+    inner: W,
+    pos: LineColByte,
+    #[cfg(feature = "alloc")]
+    marks: Vec<LineColByte>,
+}
+
+impl<W> PositionTrackingWriter<W> {
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        Self::with_position(inner, LineColByte(1, 1, 0))
+    }
+
+    /// Returns a writer where the first written [`char`] has the position `start`,
+    /// e.g. when appending to already written output.
+    #[inline]
+    pub fn with_position(inner: W, start: LineColByte) -> Self {
+        Self {
+            inner,
+            pos: start,
+            #[cfg(feature = "alloc")]
+            marks: Vec::new(),
+        }
+    }
+
+    /// Returns the position of the next written [`char`].
+    ///
+    /// When written through [`io::Write`](std::io::Write), the position
+    /// is only meaningful on [`char`] boundaries, as a [`char`] counts
+    /// as a column as soon as its first byte is written.
+    #[inline]
+    pub fn position(&self) -> LineColByte {
+        self.pos
+    }
+
+    /// Returns the [`Span`] from `start` to the current [`position()`](Self::position).
+    #[inline]
+    pub fn span_from(&self, start: LineColByte) -> Span {
+        Span::new(start, self.pos)
+    }
+
+    /// Records the current [`position()`](Self::position) as a mark,
+    /// and returns it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// use char_positions::{LineColByte, PositionTrackingWriter};
+    ///
+    /// let mut w = PositionTrackingWriter::new(String::new());
+    /// for name in ["a", "🦀"] {
+    ///     w.mark();
+    ///     writeln!(w, "let {name};").unwrap();
+    /// }
+    ///
+    /// assert_eq!(w.marks(), [LineColByte(1, 1, 0), LineColByte(2, 1, 7)]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn mark(&mut self) -> LineColByte {
+        self.marks.push(self.pos);
+        self.pos
+    }
+
+    /// Returns the positions recorded by [`mark()`](Self::mark), in order.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn marks(&self) -> &[LineColByte] {
+        &self.marks
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// Anything written directly to it is not tracked.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for PositionTrackingWriter<W> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_str(s)?;
+        self.pos.advance_str(s);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> io::Write for PositionTrackingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        for &b in &buf[..n] {
+            if b == b'\n' {
+                self.pos.0 += 1;
+                self.pos.1 = 1;
+            } else if (b & 0xC0) != 0x80 {
+                // Not a UTF-8 continuation byte
+                self.pos.1 += 1;
+            }
+        }
+        self.pos.2 += n;
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}